@@ -135,6 +135,8 @@ pub struct MessageDesc {
     pub signature: &'static [ArgumentType],
     /// Minimum required version of the interface
     pub since: u32,
+    /// Version of the interface since which this message is deprecated, if any
+    pub deprecated_since: Option<u32>,
     /// Whether this message is a destructor
     pub is_destructor: bool,
     /// The child interface created from this message.
@@ -145,6 +147,24 @@ pub struct MessageDesc {
     pub arg_interfaces: &'static [&'static Interface],
 }
 
+/// Metadata of a given message, usable in constant contexts
+///
+/// Unlike [`MessageDesc`], it does not reference the interfaces involved in the message, and is
+/// meant for introspection rather than for the serialization of the message.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MessageInfo {
+    /// Name of this message
+    pub name: &'static str,
+    /// Opcode of this message
+    pub opcode: u16,
+    /// Minimum required version of the interface
+    pub since: u32,
+    /// Version of the interface since which this message is deprecated, if any
+    pub deprecated_since: Option<u32>,
+    /// Whether this message is a destructor
+    pub is_destructor: bool,
+}
+
 /// Special interface representing an anonymous object
 pub static ANONYMOUS_INTERFACE: Interface =
     Interface { name: "<anonymous>", version: 0, requests: &[], events: &[], c_ptr: None };
@@ -177,8 +197,19 @@ pub struct ProtocolError {
     pub object_interface: String,
     /// The message sent by the server describing the error
     pub message: String,
+    /// The name of the message in which the error was detected
+    ///
+    /// This is only set for protocol violations detected by the client itself. In this case `code` is
+    /// a code of the `wl_display.error` enum, and the object is the one that received the offending event.
+    pub message_name: Option<&'static str>,
 }
 
+// Codes of the `wl_display.error` enum, used for the protocol violations detected by the client
+#[allow(dead_code)]
+pub(crate) const DISPLAY_ERROR_INVALID_OBJECT: u32 = 0;
+#[allow(dead_code)]
+pub(crate) const DISPLAY_ERROR_INVALID_METHOD: u32 = 1;
+
 /// Number of arguments that are stocked inline in a `Message` before allocating
 ///
 /// This is a ad-hoc number trying to reach a good balance between avoiding too many allocations
@@ -201,11 +232,19 @@ impl std::error::Error for ProtocolError {}
 #[cfg(not(tarpaulin_include))]
 impl std::fmt::Display for ProtocolError {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
-        write!(
-            f,
-            "Protocol error {} on object {}@{}: {}",
-            self.code, self.object_interface, self.object_id, self.message
-        )
+        if let Some(message_name) = self.message_name {
+            write!(
+                f,
+                "Protocol error {} on object {}@{} in {}: {}",
+                self.code, self.object_interface, self.object_id, message_name, self.message
+            )
+        } else {
+            write!(
+                f,
+                "Protocol error {} on object {}@{}: {}",
+                self.code, self.object_interface, self.object_id, self.message
+            )
+        }
     }
 }
 
@@ -227,6 +266,22 @@ pub(crate) fn check_for_signature<Id>(signature: &[ArgumentType], args: &[Argume
     true
 }
 
+/// Log a warning if a message sent by an object of given version is deprecated
+#[inline]
+pub(crate) fn warn_if_deprecated(interface: &Interface, id: u32, version: u32, desc: &MessageDesc) {
+    if let Some(deprecated_since) = desc.deprecated_since {
+        if version >= deprecated_since {
+            log::warn!(
+                "Sending {}@{}.{}, which is deprecated since version {} of the interface.",
+                interface.name,
+                id,
+                desc.name,
+                deprecated_since
+            );
+        }
+    }
+}
+
 #[inline]
 #[allow(dead_code)]
 pub(crate) fn same_interface_or_anonymous(a: &'static Interface, b: &'static Interface) -> bool {
@@ -6,14 +6,16 @@ use std::{
         io::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
         net::UnixStream,
     },
-    sync::{Arc, Condvar, Mutex},
+    sync::{Arc, Condvar, Mutex, Weak},
+    task::{Context, Poll, Waker},
 };
 
 use crate::{
     core_interfaces::WL_DISPLAY_INTERFACE,
     protocol::{
-        check_for_signature, same_interface, same_interface_or_anonymous, AllowNull, Argument,
-        ArgumentType, Interface, Message, ObjectInfo, ProtocolError, ANONYMOUS_INTERFACE,
+        check_for_signature, same_interface, same_interface_or_anonymous, warn_if_deprecated,
+        AllowNull, Argument, ArgumentType, Interface, Message, ObjectInfo, ProtocolError,
+        ANONYMOUS_INTERFACE, DISPLAY_ERROR_INVALID_METHOD, DISPLAY_ERROR_INVALID_OBJECT,
         INLINE_ARGS,
     },
 };
@@ -22,7 +24,7 @@ use smallvec::SmallVec;
 use super::{
     debug::DisplaySlice,
     map::{Object, ObjectMap, SERVER_ID_LIMIT},
-    socket::{BufferedSocket, Socket},
+    socket::{IncomingBuffers, OutgoingBuffers, Socket},
     wire::MessageParseError,
 };
 
@@ -46,7 +48,7 @@ pub trait ObjectData: downcast_rs::DowncastSync {
         msg: Message<ObjectId>,
     ) -> Option<Arc<dyn ObjectData>>;
     /// Notification that the object has been destroyed and is no longer active
-    fn destroyed(&self, object_id: ObjectId);
+    fn destroyed(self: Arc<Self>, object_id: ObjectId);
     /// Helper for forwarding a Debug implementation of your `ObjectData` type
     ///
     /// By default will just print `ObjectData { ... }`
@@ -90,6 +92,14 @@ impl std::cmp::PartialEq for ObjectId {
 
 impl std::cmp::Eq for ObjectId {}
 
+impl std::hash::Hash for ObjectId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.serial.hash(state);
+        self.id.hash(state);
+        self.interface.name.hash(state);
+    }
+}
+
 #[cfg(not(tarpaulin_include))]
 impl fmt::Display for ObjectId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -124,20 +134,55 @@ impl ObjectId {
     }
 }
 
+/// The state of a connection, shared by all clones of its [`Backend`] and [`Handle`]
+///
+/// Its different parts are synchronized separately, so that sending requests, accessing the object
+/// map and reading events from the socket do not contend with each other:
+///
+/// - `protocol` holds the object map and is locked for short periods, never while a callback runs
+/// - `outgoing` holds the outgoing buffers, it is always locked while `protocol` is locked, in order to
+///   keep the order of the messages consistent with the state of the object map
+/// - `incoming` holds the incoming buffers and is locked for the whole reading of events, callbacks
+///   included
+#[derive(Debug)]
+struct ConnectionState {
+    socket: Socket,
+    protocol: Mutex<ProtocolState>,
+    outgoing: Mutex<OutgoingBuffers>,
+    incoming: Mutex<IncomingBuffers>,
+    read_sync: Mutex<ReadSync>,
+    read_condvar: Condvar,
+    debug: bool,
+}
+
+#[derive(Debug)]
+struct ProtocolState {
+    map: ObjectMap<Data>,
+    last_error: Option<WaylandError>,
+    error_wakers: Vec<Waker>,
+    last_serial: u32,
+}
+
+#[derive(Debug)]
+struct ReadSync {
+    prepared_reads: usize,
+    read_serial: usize,
+}
+
 /// Main handle of a backend to the Wayland protocol
 ///
 /// This type hosts most of the protocol-related functionality of the backend, and is the
 /// main entry point for manipulating Wayland objects. It can be retrieved both from
 /// the backend via [`Backend::handle()`](Backend::handle), and is given to you as argument
 /// in most event callbacks.
-#[derive(Debug)]
+///
+/// Cloning a handle is cheap, and all clones refer to the same connection. Each of them can be
+/// used from a different thread without contending with the others beyond the serialization of
+/// the messages.
+#[derive(Debug, Clone)]
 pub struct Handle {
-    socket: BufferedSocket,
-    map: ObjectMap<Data>,
-    last_error: Option<WaylandError>,
-    last_serial: u32,
+    state: Arc<ConnectionState>,
     pending_placeholder: Option<(&'static Interface, u32)>,
-    debug: bool,
 }
 
 /// A pure rust implementation of a Wayland client backend
@@ -145,12 +190,32 @@ pub struct Handle {
 /// This type hosts the plumbing functionalities for interacting with the wayland protocol,
 /// and most of the protocol-level interactions are made through the [`Handle`] type, accessed
 /// via the [`handle()`](Backend::handle) method.
-#[derive(Debug)]
+///
+/// Cloning the backend is cheap, and all clones refer to the same connection, which is closed
+/// once all of them are dropped.
+#[derive(Debug, Clone)]
 pub struct Backend {
     handle: Handle,
-    prepared_reads: usize,
-    read_condvar: Arc<Condvar>,
-    read_serial: usize,
+}
+
+/// A weak reference to a [`Backend`]
+///
+/// It does not keep the connection alive, and can be upgraded back into a [`Backend`] as long
+/// as the connection is.
+#[derive(Debug, Clone, Default)]
+pub struct WeakBackend {
+    state: Weak<ConnectionState>,
+}
+
+impl WeakBackend {
+    /// Try to upgrade this weak reference into a [`Backend`]
+    ///
+    /// Returns `None` if the connection has been dropped.
+    pub fn upgrade(&self) -> Option<Backend> {
+        self.state
+            .upgrade()
+            .map(|state| Backend { handle: Handle { state, pending_placeholder: None } })
+    }
 }
 
 impl Backend {
@@ -159,7 +224,7 @@ impl Backend {
     /// The provided stream should correspond to an already established unix connection with
     /// the Wayland server. On this rust backend, this method never fails.
     pub fn connect(stream: UnixStream) -> Result<Self, NoWaylandLib> {
-        let socket = BufferedSocket::new(unsafe { Socket::from_raw_fd(stream.into_raw_fd()) });
+        let socket = unsafe { Socket::from_raw_fd(stream.into_raw_fd()) };
         let mut map = ObjectMap::new();
         map.insert_at(
             1,
@@ -179,26 +244,36 @@ impl Backend {
         let debug =
             matches!(std::env::var_os("WAYLAND_DEBUG"), Some(str) if str == "1" || str == "client");
 
-        Ok(Backend {
-            handle: Handle {
-                socket,
+        let state = ConnectionState {
+            socket,
+            protocol: Mutex::new(ProtocolState {
                 map,
                 last_error: None,
+                error_wakers: Vec::new(),
                 last_serial: 0,
-                pending_placeholder: None,
-                debug,
-            },
-            prepared_reads: 0,
-            read_condvar: Arc::new(Condvar::new()),
-            read_serial: 0,
-        })
+            }),
+            outgoing: Mutex::new(OutgoingBuffers::new()),
+            incoming: Mutex::new(IncomingBuffers::new()),
+            read_sync: Mutex::new(ReadSync { prepared_reads: 0, read_serial: 0 }),
+            read_condvar: Condvar::new(),
+            debug,
+        };
+
+        Ok(Backend { handle: Handle { state: Arc::new(state), pending_placeholder: None } })
+    }
+
+    /// Create a weak reference to this backend
+    pub fn downgrade(&self) -> WeakBackend {
+        WeakBackend { state: Arc::downgrade(&self.handle.state) }
     }
 
     /// Flush all pending outgoing requests to the server
-    pub fn flush(&mut self) -> Result<(), WaylandError> {
-        self.handle.no_last_error()?;
-        if let Err(e) = self.handle.socket.flush() {
-            return Err(self.handle.store_if_not_wouldblock_and_return_error(e));
+    pub fn flush(&self) -> Result<(), WaylandError> {
+        let state = &self.handle.state;
+        state.protocol.lock().unwrap().no_last_error()?;
+        let ret = state.outgoing.lock().unwrap().flush(&state.socket);
+        if let Err(e) = ret {
+            return Err(state.protocol.lock().unwrap().store_if_not_wouldblock_and_return_error(e));
         }
         Ok(())
     }
@@ -210,23 +285,30 @@ impl Backend {
     ///
     /// **Note:** this function should only be used if you know that you are the only thread
     /// reading events from the wayland socket. If this may not be the case, see [`ReadEventsGuard`]
-    pub fn dispatch_events(&mut self) -> Result<usize, WaylandError> {
-        self.handle.no_last_error()?;
+    pub fn dispatch_events(&self) -> Result<usize, WaylandError> {
+        let state = &self.handle.state;
+        state.protocol.lock().unwrap().no_last_error()?;
+        let mut incoming = state.incoming.lock().unwrap();
         let mut dispatched = 0;
         loop {
             // Attempt to read a message
-            let map = &self.handle.map;
-            let message = match self.handle.socket.read_one_message(|id, opcode| {
-                map.find(id)
+            let ret = incoming.read_one_message(|id, opcode| {
+                state
+                    .protocol
+                    .lock()
+                    .unwrap()
+                    .map
+                    .find(id)
                     .and_then(|o| o.interface.events.get(opcode as usize))
                     .map(|desc| desc.signature)
-            }) {
+            });
+            let message = match ret {
                 Ok(msg) => msg,
                 Err(MessageParseError::MissingData) | Err(MessageParseError::MissingFD) => {
                     // need to read more data
-                    if let Err(e) = self.handle.socket.fill_incoming_buffers() {
+                    if let Err(e) = incoming.fill(&state.socket) {
                         if e.kind() != std::io::ErrorKind::WouldBlock {
-                            return Err(self.handle.store_and_return_error(e));
+                            return Err(state.protocol.lock().unwrap().store_and_return_error(e));
                         } else if dispatched == 0 {
                             return Err(e.into());
                         } else {
@@ -237,24 +319,46 @@ impl Backend {
                 }
                 Err(MessageParseError::Malformed) => {
                     // malformed error, protocol error
-                    let err = WaylandError::Protocol(ProtocolError {
-                        code: 0,
-                        object_id: 0,
-                        object_interface: "".into(),
-                        message: "Malformed Wayland message.".into(),
-                    });
-                    return Err(self.handle.store_and_return_error(err));
+                    let (sender_id, opcode) = incoming.peek_header().unwrap_or((0, 0));
+                    let mut protocol = state.protocol.lock().unwrap();
+                    let err = match protocol.map.find(sender_id) {
+                        Some(obj) if (opcode as usize) < obj.interface.events.len() => protocol
+                            .violation(
+                                sender_id,
+                                opcode,
+                                DISPLAY_ERROR_INVALID_METHOD,
+                                "Malformed Wayland message.".into(),
+                            ),
+                        Some(obj) => protocol.violation(
+                            sender_id,
+                            opcode,
+                            DISPLAY_ERROR_INVALID_METHOD,
+                            format!(
+                                "Unknown opcode {} for interface {}.",
+                                opcode, obj.interface.name
+                            ),
+                        ),
+                        None => protocol.violation(
+                            sender_id,
+                            opcode,
+                            DISPLAY_ERROR_INVALID_OBJECT,
+                            format!("Received an event for unknown object {}.", sender_id),
+                        ),
+                    };
+                    return Err(err);
                 }
             };
 
+            let mut protocol = state.protocol.lock().unwrap();
+
             // We got a message, retrieve its associated object & details
             // These lookups must succeed otherwise we would not have been able to parse this message
-            let receiver = self.handle.map.find(message.sender_id).unwrap();
+            let receiver = protocol.map.find(message.sender_id).unwrap();
             let message_desc = receiver.interface.events.get(message.opcode as usize).unwrap();
 
             // Short-circuit display-associated events
             if message.sender_id == 1 {
-                self.handle.handle_display_event(message)?;
+                protocol.handle_display_event(message)?;
                 continue;
             }
 
@@ -274,30 +378,28 @@ impl Backend {
                     Argument::Object(o) => {
                         if o != 0 {
                             // Lookup the object to make the appropriate Id
-                            let obj = match self.handle.map.find(o) {
+                            let obj = match protocol.map.find(o) {
                                 Some(o) => o,
                                 None => {
-                                    let err = WaylandError::Protocol(ProtocolError {
-                                        code: 0,
-                                        object_id: 0,
-                                        object_interface: "".into(),
-                                        message: format!("Unknown object {}.", o),
-                                    });
-                                    return Err(self.handle.store_and_return_error(err));
+                                    return Err(protocol.violation(
+                                        message.sender_id,
+                                        message.opcode,
+                                        DISPLAY_ERROR_INVALID_OBJECT,
+                                        format!("Unknown object {}.", o),
+                                    ));
                                 }
                             };
                             if let Some(next_interface) = arg_interfaces.next() {
                                 if !same_interface_or_anonymous(next_interface, obj.interface) {
-                                    let err = WaylandError::Protocol(ProtocolError {
-                                        code: 0,
-                                        object_id: 0,
-                                        object_interface: "".into(),
-                                        message: format!(
-                                            "Protocol error: server sent object {} for interface {}, but it has interface {}.",
+                                    return Err(protocol.violation(
+                                        message.sender_id,
+                                        message.opcode,
+                                        DISPLAY_ERROR_INVALID_OBJECT,
+                                        format!(
+                                            "Server sent object {} for interface {}, but it has interface {}.",
                                             o, next_interface.name, obj.interface.name
                                         ),
-                                    });
-                                    return Err(self.handle.store_and_return_error(err));
+                                    ));
                                 }
                             }
                             Argument::Object(ObjectId { id: o, serial: obj.data.serial, interface: obj.interface })
@@ -316,9 +418,9 @@ impl Backend {
 
                         // if this ID belonged to a now destroyed server object, we can replace it
                         if new_id >= SERVER_ID_LIMIT
-                            && self.handle.map.with(new_id, |obj| obj.data.client_destroyed).unwrap_or(false)
+                            && protocol.map.with(new_id, |obj| obj.data.client_destroyed).unwrap_or(false)
                         {
-                            self.handle.map.remove(new_id);
+                            protocol.map.remove(new_id);
                         }
 
                         let child_obj = Object {
@@ -328,26 +430,24 @@ impl Backend {
                                 client_destroyed: receiver.data.client_destroyed,
                                 server_destroyed: false,
                                 user_data: child_udata,
-                                serial: self.handle.next_serial(),
+                                serial: protocol.next_serial(),
                             }
                         };
 
                         let child_id = ObjectId { id: new_id, serial: child_obj.data.serial, interface: child_obj.interface };
                         created_id = Some(child_id.clone());
 
-                        if let Err(()) = self.handle.map.insert_at(new_id, child_obj) {
+                        if let Err(()) = protocol.map.insert_at(new_id, child_obj) {
                             // abort parsing, this is an unrecoverable error
-                            let err = WaylandError::Protocol(ProtocolError {
-                                code: 0,
-                                object_id: 0,
-                                object_interface: "".into(),
-                                message: format!(
-                                    "Protocol error: server tried to create \
-                                    an object \"{}\" with invalid id {}.",
+                            return Err(protocol.violation(
+                                message.sender_id,
+                                message.opcode,
+                                DISPLAY_ERROR_INVALID_OBJECT,
+                                format!(
+                                    "Server tried to create an object \"{}\" with invalid id {}.",
                                     child_interface.name, new_id
                                 ),
-                            });
-                            return Err(self.handle.store_and_return_error(err));
+                            ));
                         }
 
                         Argument::NewId(child_id)
@@ -355,7 +455,11 @@ impl Backend {
                 });
             }
 
-            if self.handle.debug {
+            // The callbacks are invoked without holding the lock on the object map, so that
+            // they (and other threads) can send requests
+            std::mem::drop(protocol);
+
+            if state.debug {
                 super::debug::print_dispatched_message(
                     receiver.interface.name,
                     message.sender_id,
@@ -382,22 +486,26 @@ impl Backend {
                 interface: receiver.interface,
             };
             log::debug!("Dispatching {}.{} ({})", id, receiver.version, DisplaySlice(&args));
+            let mut handle = Handle { state: state.clone(), pending_placeholder: None };
             let ret = receiver
                 .data
                 .user_data
                 .clone()
-                .event(&mut self.handle, Message { sender_id: id, opcode: message.opcode, args });
+                .event(&mut handle, Message { sender_id: id, opcode: message.opcode, args });
 
             // If this event is a destructor, destroy the object
             if message_desc.is_destructor {
-                self.handle
+                state
+                    .protocol
+                    .lock()
+                    .unwrap()
                     .map
                     .with(message.sender_id, |obj| {
                         obj.data.server_destroyed = true;
                         obj.data.client_destroyed = true;
                     })
                     .unwrap();
-                receiver.data.user_data.destroyed(ObjectId {
+                receiver.data.user_data.clone().destroyed(ObjectId {
                     id: message.sender_id,
                     serial: receiver.data.serial,
                     interface: receiver.interface,
@@ -406,7 +514,10 @@ impl Backend {
 
             match (created_id, ret) {
                 (Some(child_id), Some(child_data)) => {
-                    self.handle
+                    state
+                        .protocol
+                        .lock()
+                        .unwrap()
                         .map
                         .with(child_id.id, |obj| obj.data.user_data = child_data)
                         .unwrap();
@@ -447,9 +558,11 @@ impl Backend {
 /// If you plan to poll the Wayland socket for readiness, the file descriptor can be retrieved via
 /// the [`connection_fd`](ReadEventsGuard::connection_fd) method. Note that for the synchronization to
 /// correctly occur, you must *always* create the `ReadEventsGuard` *before* polling the socket.
+///
+/// Holding a guard, or reading events, does not prevent other threads from sending requests.
 #[derive(Debug)]
 pub struct ReadEventsGuard {
-    backend: Arc<Mutex<Backend>>,
+    backend: Backend,
     done: bool,
 }
 
@@ -458,14 +571,14 @@ impl ReadEventsGuard {
     ///
     /// This call will not block, but event callbacks may be invoked in the process
     /// of preparing the guard.
-    pub fn try_new(backend: Arc<Mutex<Backend>>) -> Result<Self, WaylandError> {
-        backend.lock().unwrap().prepared_reads += 1;
+    pub fn try_new(backend: Backend) -> Result<Self, WaylandError> {
+        backend.handle.state.read_sync.lock().unwrap().prepared_reads += 1;
         Ok(ReadEventsGuard { backend, done: false })
     }
 
     /// Access the Wayland socket FD for polling
     pub fn connection_fd(&self) -> RawFd {
-        self.backend.lock().unwrap().handle.socket.as_raw_fd()
+        self.backend.handle.state.socket.as_raw_fd()
     }
 
     /// Attempt to read events from the Wayland socket
@@ -478,23 +591,28 @@ impl ReadEventsGuard {
     /// This returns the number of dispatched events, or `0` if an other thread handled the dispatching.
     /// If no events are available to read from the socket, this returns a `WouldBlock` IO error.
     pub fn read(mut self) -> Result<usize, WaylandError> {
-        let mut backend = self.backend.lock().unwrap();
-        backend.prepared_reads -= 1;
         self.done = true;
-        if backend.prepared_reads == 0 {
-            // We should be the one reading
-            let ret = backend.dispatch_events();
+        let state = &self.backend.handle.state;
+        let mut sync = state.read_sync.lock().unwrap();
+        sync.prepared_reads -= 1;
+        if sync.prepared_reads == 0 {
+            // We should be the one reading, other threads waiting for us only need the read
+            // synchronization state, so it is not locked while dispatching
+            std::mem::drop(sync);
+            let ret = self.backend.dispatch_events();
             // wake up other threads
-            backend.read_serial = backend.read_serial.wrapping_add(1);
-            backend.read_condvar.notify_all();
+            let mut sync = state.read_sync.lock().unwrap();
+            sync.read_serial = sync.read_serial.wrapping_add(1);
+            state.read_condvar.notify_all();
             // forward the return value
             ret
         } else {
             // We should wait for an other thread to read (or cancel)
-            let serial = backend.read_serial;
-            let condvar = backend.read_condvar.clone();
-            backend = condvar.wait_while(backend, |backend| serial == backend.read_serial).unwrap();
-            backend.handle.no_last_error()?;
+            let serial = sync.read_serial;
+            let sync =
+                state.read_condvar.wait_while(sync, |sync| serial == sync.read_serial).unwrap();
+            std::mem::drop(sync);
+            state.protocol.lock().unwrap().no_last_error()?;
             Ok(0)
         }
     }
@@ -503,12 +621,13 @@ impl ReadEventsGuard {
 impl Drop for ReadEventsGuard {
     fn drop(&mut self) {
         if !self.done {
-            let mut backend = self.backend.lock().unwrap();
-            backend.prepared_reads -= 1;
-            if backend.prepared_reads == 0 {
+            let state = &self.backend.handle.state;
+            let mut sync = state.read_sync.lock().unwrap();
+            sync.prepared_reads -= 1;
+            if sync.prepared_reads == 0 {
                 // Cancel the read
-                backend.read_serial = backend.read_serial.wrapping_add(1);
-                backend.read_condvar.notify_all();
+                sync.read_serial = sync.read_serial.wrapping_add(1);
+                state.read_condvar.notify_all();
             }
         }
     }
@@ -524,14 +643,29 @@ impl Handle {
     ///
     /// If this returns an error, your Wayland connection is already dead.
     pub fn last_error(&self) -> Option<WaylandError> {
-        self.last_error.clone()
+        self.state.protocol.lock().unwrap().last_error.clone()
+    }
+
+    /// Poll for the error that kills this connection
+    ///
+    /// This returns the last error if there is one, and otherwise registers the waker of the
+    /// provided context to be woken up once an error is stored.
+    pub fn poll_error(&self, cx: &mut Context<'_>) -> Poll<WaylandError> {
+        let mut protocol = self.state.protocol.lock().unwrap();
+        if let Some(ref err) = protocol.last_error {
+            return Poll::Ready(err.clone());
+        }
+        if !protocol.error_wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            protocol.error_wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
     }
 
     /// Get the detailed information about a wayland object
     ///
     /// Returns an error if the provided object ID is no longer valid.
     pub fn info(&self, id: ObjectId) -> Result<ObjectInfo, InvalidId> {
-        let object = self.get_object(id.clone())?;
+        let object = self.state.protocol.lock().unwrap().get_object(id.clone())?;
         if object.data.client_destroyed {
             Err(InvalidId)
         } else {
@@ -555,6 +689,9 @@ impl Handle {
     /// If a specification is provided it'll be checked against what can be deduced from the
     /// protocol specification, and [`send_request`](Handle::send_request) will panic if they
     /// do not match.
+    ///
+    /// The placeholder is specific to this `Handle`, and is consumed by its next call to
+    /// [`send_request`](Handle::send_request).
     pub fn placeholder_id(&mut self, spec: Option<(&'static Interface, u32)>) -> ObjectId {
         self.pending_placeholder = spec;
         ObjectId {
@@ -583,7 +720,9 @@ impl Handle {
         Message { sender_id: id, opcode, args }: Message<ObjectId>,
         data: Option<Arc<dyn ObjectData>>,
     ) -> Result<ObjectId, InvalidId> {
-        let object = self.get_object(id.clone())?;
+        let state = self.state.clone();
+        let mut protocol = state.protocol.lock().unwrap();
+        let object = protocol.get_object(id.clone())?;
         if object.data.client_destroyed {
             return Err(InvalidId);
         }
@@ -601,6 +740,7 @@ impl Handle {
                 object.interface.name, id.id, message_desc.name, message_desc.signature, args
             );
         }
+        warn_if_deprecated(object.interface, id.id, object.version, message_desc);
 
         // Prepare the child object
         let child_spec = if message_desc
@@ -646,7 +786,7 @@ impl Handle {
         };
 
         let child = if let Some((child_interface, child_version)) = child_spec {
-            let child_serial = self.next_serial();
+            let child_serial = protocol.next_serial();
 
             let child = Object {
                 interface: child_interface,
@@ -659,9 +799,10 @@ impl Handle {
                 },
             };
 
-            let child_id = self.map.client_insert_new(child);
+            let child_id = protocol.map.client_insert_new(child);
 
-            self.map
+            protocol
+                .map
                 .with(child_id, |obj| {
                     obj.data.user_data = data.expect(
                         "Sending a request creating an object without providing an object data.",
@@ -689,7 +830,7 @@ impl Handle {
             }
         }).collect::<SmallVec<[_; INLINE_ARGS]>>();
 
-        if self.debug {
+        if state.debug {
             super::debug::print_send_message(
                 object.interface.name,
                 id.id,
@@ -714,7 +855,7 @@ impl Handle {
                 Argument::Fd(f) => Argument::Fd(f),
                 Argument::Object(o) => {
                     if o.id != 0 {
-                        let object = self.get_object(o.clone())?;
+                        let object = protocol.get_object(o.clone())?;
                         let next_interface = arg_interfaces.next().unwrap();
                         if !same_interface_or_anonymous(next_interface, object.interface) {
                             panic!("Request {}@{}.{} expects an argument of interface {} but {} was provided instead.", object.interface.name, id.id, message_desc.name, next_interface.name, object.interface.name);
@@ -729,17 +870,28 @@ impl Handle {
 
         let msg = Message { sender_id: id.id, opcode, args: msg_args };
 
-        if let Err(err) = self.socket.write_message(&msg) {
-            self.last_error = Some(WaylandError::Io(err));
-        }
-
         // Handle destruction if relevant
         if message_desc.is_destructor {
-            self.map
+            protocol
+                .map
                 .with(id.id, |obj| {
                     obj.data.client_destroyed = true;
                 })
                 .unwrap();
+        }
+
+        // The outgoing buffers are locked before releasing the object map, so that messages are
+        // serialized in the same order as they were checked against the state of the map
+        let mut outgoing = state.outgoing.lock().unwrap();
+        std::mem::drop(protocol);
+        let ret = outgoing.write_message(&state.socket, &msg);
+        std::mem::drop(outgoing);
+
+        if let Err(err) = ret {
+            state.protocol.lock().unwrap().store_error(WaylandError::Io(err));
+        }
+
+        if message_desc.is_destructor {
             object.data.user_data.destroyed(id);
         }
         if let Some((child_id, child_serial, child_interface)) = child {
@@ -753,7 +905,7 @@ impl Handle {
     ///
     /// Returns an error if the object ID is not longer valid
     pub fn get_data(&self, id: ObjectId) -> Result<Arc<dyn ObjectData>, InvalidId> {
-        let object = self.get_object(id)?;
+        let object = self.state.protocol.lock().unwrap().get_object(id)?;
         Ok(object.data.user_data)
     }
 
@@ -761,7 +913,11 @@ impl Handle {
     ///
     /// Returns an error if the object ID is not longer valid
     pub fn set_data(&mut self, id: ObjectId, data: Arc<dyn ObjectData>) -> Result<(), InvalidId> {
-        self.map
+        self.state
+            .protocol
+            .lock()
+            .unwrap()
+            .map
             .with(id.id, move |objdata| {
                 if objdata.data.serial != id.serial {
                     Err(InvalidId)
@@ -774,7 +930,7 @@ impl Handle {
     }
 }
 
-impl Handle {
+impl ProtocolState {
     fn next_serial(&mut self) -> u32 {
         self.last_serial = self.last_serial.wrapping_add(1);
         self.last_serial
@@ -789,11 +945,18 @@ impl Handle {
         }
     }
 
+    fn store_error(&mut self, err: WaylandError) {
+        self.last_error = Some(err);
+        for waker in self.error_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
     #[inline]
     fn store_and_return_error(&mut self, err: impl Into<WaylandError>) -> WaylandError {
         let err = err.into();
         log::error!("{}", err);
-        self.last_error = Some(err.clone());
+        self.store_error(err.clone());
         err
     }
 
@@ -806,6 +969,31 @@ impl Handle {
         }
     }
 
+    /// Store and return an error for a protocol violation by the server, detected while
+    /// processing the event `opcode` received by object `object_id`
+    fn violation(
+        &mut self,
+        object_id: u32,
+        opcode: u16,
+        code: u32,
+        message: String,
+    ) -> WaylandError {
+        let interface = self.map.find(object_id).map(|obj| obj.interface);
+        let err = WaylandError::Protocol(ProtocolError {
+            code,
+            object_id,
+            object_interface: interface.map(|iface| iface.name).unwrap_or("<unknown>").into(),
+            message,
+            message_name: Some(
+                interface
+                    .and_then(|iface| iface.events.get(opcode as usize))
+                    .map(|desc| desc.name)
+                    .unwrap_or("<unknown>"),
+            ),
+        });
+        self.store_and_return_error(err)
+    }
+
     fn get_object(&self, id: ObjectId) -> Result<Object<Data>, InvalidId> {
         let object = self.map.find(id.id).ok_or(InvalidId)?;
         if object.data.serial != id.serial {
@@ -830,6 +1018,7 @@ impl Handle {
                             .unwrap_or("<unknown>")
                             .into(),
                         message: message.to_string_lossy().into(),
+                        message_name: None,
                     });
                     return Err(self.store_and_return_error(err));
                 } else {
@@ -870,7 +1059,7 @@ impl ObjectData for DumbObjectData {
         unreachable!()
     }
 
-    fn destroyed(&self, _object_id: ObjectId) {
+    fn destroyed(self: Arc<Self>, _object_id: ObjectId) {
         unreachable!()
     }
 }
@@ -886,7 +1075,7 @@ impl ObjectData for UninitObjectData {
         panic!("Received a message on an uninitialized object: {:?}", msg);
     }
 
-    fn destroyed(&self, _object_id: ObjectId) {}
+    fn destroyed(self: Arc<Self>, _object_id: ObjectId) {}
 
     fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UninitObjectData").finish()
@@ -1,20 +1,21 @@
 use std::{
     ffi::CString,
     os::unix::{
-        io::{FromRawFd, IntoRawFd},
+        io::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
         net::UnixStream,
     },
     sync::Arc,
+    time::Instant,
 };
 
 use crate::{
     core_interfaces::{WL_CALLBACK_INTERFACE, WL_DISPLAY_INTERFACE, WL_REGISTRY_INTERFACE},
     protocol::{
-        check_for_signature, same_interface, same_interface_or_anonymous, AllowNull, Argument,
-        ArgumentType, Interface, Message, ObjectInfo, ProtocolError, ANONYMOUS_INTERFACE,
-        INLINE_ARGS,
+        check_for_signature, same_interface, same_interface_or_anonymous, warn_if_deprecated,
+        AllowNull, Argument, ArgumentType, Interface, Message, ObjectInfo, ProtocolError,
+        ANONYMOUS_INTERFACE, INLINE_ARGS,
     },
-    types::server::{DisconnectReason, InvalidId},
+    types::server::{DisconnectReason, InvalidId, CLOSING_TIMEOUT},
 };
 
 use smallvec::SmallVec;
@@ -49,6 +50,7 @@ pub(crate) struct Client<D> {
     last_serial: u32,
     pub(crate) id: ClientId,
     pub(crate) killed: bool,
+    closing: Option<(DisconnectReason, Instant)>,
     pub(crate) data: Arc<dyn ClientData<D>>,
 }
 
@@ -80,7 +82,7 @@ impl<D> Client<D> {
 
         data.initialized(id.clone());
 
-        Client { socket, map, debug, id, killed: false, last_serial: 0, data }
+        Client { socket, map, debug, id, killed: false, closing: None, last_serial: 0, data }
     }
 
     pub(crate) fn create_object(
@@ -108,7 +110,7 @@ impl<D> Client<D> {
         Message { sender_id: object_id, opcode, args }: Message<ObjectId>,
         pending_destructors: Option<&mut Vec<super::handle::PendingDestructor<D>>>,
     ) -> Result<(), InvalidId> {
-        if self.killed {
+        if self.killed || self.closing.is_some() {
             return Ok(());
         }
         let object = self.get_object(object_id.clone())?;
@@ -133,6 +135,7 @@ impl<D> Client<D> {
                 args
             );
         }
+        warn_if_deprecated(object.interface, object_id.id, object.version, message_desc);
 
         if self.debug {
             crate::rs::debug::print_send_message(
@@ -191,8 +194,8 @@ impl<D> Client<D> {
 
         let msg = Message { sender_id: object_id.id, opcode, args: msg_args };
 
-        if self.socket.write_message(&msg).is_err() {
-            self.kill(DisconnectReason::ConnectionClosed);
+        if let Err(e) = self.socket.write_message(&msg) {
+            self.kill(DisconnectReason::from_io_error(e));
         }
 
         // Handle destruction if relevant
@@ -209,8 +212,8 @@ impl<D> Client<D> {
 
     pub(crate) fn send_delete_id(&mut self, object_id: ObjectId) {
         let msg = message!(1, 1, [Argument::Uint(object_id.id)]);
-        if self.socket.write_message(&msg).is_err() {
-            self.kill(DisconnectReason::ConnectionClosed);
+        if let Err(e) = self.socket.write_message(&msg) {
+            self.kill(DisconnectReason::from_io_error(e));
         }
         self.map.remove(object_id.id);
     }
@@ -240,33 +243,35 @@ impl<D> Client<D> {
             .unwrap_or(Err(InvalidId))
     }
 
+    fn display_id(&self) -> ObjectId {
+        ObjectId { id: 1, interface: &WL_DISPLAY_INTERFACE, client_id: self.id.clone(), serial: 0 }
+    }
+
     pub(crate) fn post_display_error(&mut self, code: DisplayError, message: CString) {
-        self.post_error(
-            ObjectId {
-                id: 1,
-                interface: &WL_DISPLAY_INTERFACE,
-                client_id: self.id.clone(),
-                serial: 0,
-            },
-            code as u32,
-            message,
-        )
+        self.post_error(self.display_id(), code as u32, message)
     }
 
     pub(crate) fn post_error(&mut self, object_id: ObjectId, error_code: u32, message: CString) {
         let converted_message = message.to_string_lossy().into();
+        self.send_error(object_id.clone(), error_code, message);
+        let _ = self.flush();
+        self.kill(DisconnectReason::ProtocolError(ProtocolError {
+            code: error_code,
+            object_id: object_id.id,
+            object_interface: object_id.interface.name.into(),
+            message: converted_message,
+            message_name: None,
+        }));
+    }
+
+    fn send_error(&mut self, object_id: ObjectId, error_code: u32, message: CString) {
         // errors are ignored, as the client will be killed anyway
         let _ = self.send_event(
             message!(
-                ObjectId {
-                    id: 1,
-                    interface: &WL_DISPLAY_INTERFACE,
-                    client_id: self.id.clone(),
-                    serial: 0
-                },
+                self.display_id(),
                 0, // wl_display.error
                 [
-                    Argument::Object(object_id.clone()),
+                    Argument::Object(object_id),
                     Argument::Uint(error_code),
                     Argument::Str(Box::new(message)),
                 ],
@@ -274,18 +279,37 @@ impl<D> Client<D> {
             // wl_display.error is not a destructor, this argument will not be used
             None,
         );
+    }
+
+    /// Disconnects the client once all its pending events have been sent, or after [`CLOSING_TIMEOUT`]
+    ///
+    /// If the reason is a protocol error, the matching `wl_display.error` is sent first.
+    pub(crate) fn kill_after_flush(&mut self, reason: DisconnectReason) {
+        if self.killed || self.closing.is_some() {
+            return;
+        }
+        if let DisconnectReason::ProtocolError(ref error) = reason {
+            let object_id =
+                self.object_for_protocol_id(error.object_id).unwrap_or_else(|_| self.display_id());
+            let message = CString::new(error.message.clone()).unwrap_or_default();
+            self.send_error(object_id, error.code, message);
+        }
+        self.closing = Some((reason, Instant::now() + CLOSING_TIMEOUT));
+        let _ = self.flush();
+    }
+
+    /// Posts a `wl_display.error` to the client and disconnects it once it has been sent, or after `deadline`
+    pub(crate) fn shutdown(&mut self, message: CString, deadline: Instant) {
+        if self.killed || self.closing.is_some() {
+            return;
+        }
+        self.send_error(self.display_id(), DisplayError::Implementation as u32, message);
+        self.closing = Some((DisconnectReason::ServerKill, deadline));
         let _ = self.flush();
-        self.kill(DisconnectReason::ProtocolError(ProtocolError {
-            code: error_code,
-            object_id: object_id.id,
-            object_interface: object_id.interface.name.into(),
-            message: converted_message,
-        }));
     }
 
     #[cfg(target_os = "linux")]
     pub(crate) fn get_credentials(&self) -> Credentials {
-        use std::os::unix::io::AsRawFd;
         let creds = nix::sys::socket::getsockopt(
             self.socket.as_raw_fd(),
             nix::sys::socket::sockopt::PeerCredentials,
@@ -301,12 +325,37 @@ impl<D> Client<D> {
     }
 
     pub(crate) fn kill(&mut self, reason: DisconnectReason) {
+        if self.killed {
+            return;
+        }
         self.killed = true;
+        self.closing = None;
         self.data.disconnected(self.id.clone(), reason);
     }
 
     pub(crate) fn flush(&mut self) -> std::io::Result<()> {
-        self.socket.flush()
+        let ret = self.socket.flush();
+        if let Some((_, deadline)) = self.closing {
+            let sending = match ret {
+                Err(ref e) => e.kind() == std::io::ErrorKind::WouldBlock,
+                Ok(()) => self.socket.has_pending_output(),
+            };
+            // finish the disconnection once everything was sent, the client is gone, or it took too long
+            if !sending || Instant::now() >= deadline {
+                let (reason, _) = self.closing.take().unwrap();
+                self.kill(reason);
+            }
+        }
+        ret
+    }
+
+    /// Whether the client is waiting for its outgoing buffer to be flushed before being disconnected
+    pub(crate) fn is_closing(&self) -> bool {
+        self.closing.is_some()
+    }
+
+    pub(crate) fn socket_fd(&self) -> RawFd {
+        self.socket.as_raw_fd()
     }
 
     pub(crate) fn all_objects(&self) -> impl Iterator<Item = ObjectId> + '_ {
@@ -323,6 +372,19 @@ impl<D> Client<D> {
         if self.killed {
             return Err(nix::errno::Errno::EPIPE.into());
         }
+        if let Some((_, deadline)) = self.closing {
+            // requests of a client being disconnected are no longer processed
+            loop {
+                if let Err(e) = self.socket.fill_incoming_buffers() {
+                    if e.kind() != std::io::ErrorKind::WouldBlock || Instant::now() >= deadline {
+                        let (reason, _) = self.closing.take().unwrap();
+                        self.kill(reason);
+                    }
+                    return Err(e);
+                }
+                self.socket.discard_incoming();
+            }
+        }
         loop {
             let map = &self.map;
             let msg = match self.socket.read_one_message(|id, opcode| {
@@ -335,7 +397,12 @@ impl<D> Client<D> {
                     // need to read more data
                     if let Err(e) = self.socket.fill_incoming_buffers() {
                         if e.kind() != std::io::ErrorKind::WouldBlock {
-                            self.kill(DisconnectReason::ConnectionClosed);
+                            let ret = e
+                                .raw_os_error()
+                                .map(std::io::Error::from_raw_os_error)
+                                .unwrap_or_else(|| e.kind().into());
+                            self.kill(DisconnectReason::from_io_error(e));
+                            return Err(ret);
                         }
                         return Err(e);
                     }
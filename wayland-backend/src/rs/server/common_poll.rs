@@ -1,9 +1,11 @@
 use std::{
+    ffi::CString,
     os::unix::{
         io::{AsRawFd, RawFd},
         net::UnixStream,
     },
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use super::ClientData;
@@ -30,6 +32,7 @@ use super::{ClientId, Handle};
 pub struct Backend<D> {
     handle: Handle<D>,
     poll_fd: RawFd,
+    poll_clients: bool,
 }
 
 impl<D> Backend<D> {
@@ -48,7 +51,17 @@ impl<D> Backend<D> {
         ))]
         let poll_fd = kqueue().map_err(Into::into).map_err(InitError::Io)?;
 
-        Ok(Backend { handle: Handle::new(), poll_fd })
+        Ok(Backend { handle: Handle::new(), poll_fd, poll_clients: true })
+    }
+
+    /// Initialize a new Wayland backend, without monitoring the clients with an internal poll
+    ///
+    /// The sockets of the clients should then be monitored directly, using the file descriptors given by
+    /// [`Handle::get_client_fd()`], and dispatched individually with [`Backend::dispatch_client()`].
+    pub fn new_without_poll() -> Result<Self, InitError> {
+        let mut backend = Self::new()?;
+        backend.poll_clients = false;
+        Ok(backend)
     }
 
     /// Initializes a connection to a client.
@@ -62,6 +75,10 @@ impl<D> Backend<D> {
         let client_fd = stream.as_raw_fd();
         let id = self.handle.clients.create_client(stream, data);
 
+        if !self.poll_clients {
+            return Ok(id);
+        }
+
         // register the client to the internal epoll
         #[cfg(target_os = "linux")]
         let ret = {
@@ -104,6 +121,67 @@ impl<D> Backend<D> {
         self.handle.flush(client)
     }
 
+    /// Gracefully disconnects all clients.
+    ///
+    /// A `wl_display.error` with the `implementation` code and given message is posted to all clients, and this
+    /// method then blocks until it has been sent to all of them, or until `timeout` has elapsed. Once this method
+    /// returns, all clients are disconnected with [`DisconnectReason::ServerKill`] and their objects are destroyed.
+    ///
+    /// Returns an error of kind `TimedOut` if some clients could not be flushed in time.
+    pub fn shutdown(
+        &mut self,
+        data: &mut D,
+        message: CString,
+        timeout: Duration,
+    ) -> std::io::Result<()> {
+        let deadline = Instant::now() + timeout;
+        for client in self.handle.clients.clients_mut() {
+            client.shutdown(message.clone(), deadline);
+        }
+
+        let ret = loop {
+            let _ = self.handle.flush(None);
+            let mut fds = self
+                .handle
+                .clients
+                .clients_mut()
+                .filter(|client| client.is_closing())
+                .map(|client| {
+                    nix::poll::PollFd::new(client.socket_fd(), nix::poll::PollFlags::POLLOUT)
+                })
+                .collect::<Vec<_>>();
+            if fds.is_empty() {
+                break Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break Err(std::io::ErrorKind::TimedOut.into());
+            }
+            let timeout = (deadline - now).as_millis().min(i32::MAX as u128) as i32;
+            match nix::poll::poll(&mut fds, timeout) {
+                Ok(_) | Err(nix::errno::Errno::EINTR) => {}
+                Err(e) => break Err(e.into()),
+            }
+        };
+
+        // clients that could not be flushed in time are disconnected anyway
+        for client in self.handle.clients.clients_mut() {
+            client.kill(DisconnectReason::ServerKill);
+        }
+        self.handle.cleanup(data);
+        ret
+    }
+
+    /// Sets whether panics occurring in request and bind callbacks should be caught.
+    ///
+    /// When enabled, a panic in the handler of a request only disconnects the client that sent it, with
+    /// a `wl_display.error` of code `implementation`, and the destructors of all its objects are invoked.
+    /// The panic is still reported by the panic hook, and the server-wide data may have been left in an
+    /// inconsistent state by the panicking callback. This is disabled by default.
+    pub fn set_catch_panics(&mut self, enabled: bool) {
+        self.handle.catch_panics = enabled;
+    }
+
     /// Returns a handle which represents the server side state of the backend.
     ///
     /// The handle provides a variety of functionality, such as querying information about wayland objects,
@@ -118,7 +196,8 @@ impl<D> Backend<D> {
     /// When it becomes readable, this means there are pending messages that would be dispatched if you call
     /// [`Backend::dispatch_all_clients`].
     ///
-    /// The file descriptor should not be used for any other purpose than monitoring it.
+    /// The file descriptor should not be used for any other purpose than monitoring it. It never becomes
+    /// readable if the backend was created with [`Backend::new_without_poll()`].
     pub fn poll_fd(&self) -> RawFd {
         self.poll_fd
     }
@@ -148,6 +227,11 @@ impl<D> Backend<D> {
     /// available.
     #[cfg(target_os = "linux")]
     pub fn dispatch_all_clients(&mut self, data: &mut D) -> std::io::Result<usize> {
+        if !self.poll_clients {
+            return Ok(self.dispatch_each_client(data));
+        }
+        // clients killed outside of a dispatch, for example by a flush, are cleaned up as well
+        self.handle.cleanup(data);
         let mut dispatched = 0;
         loop {
             let mut events = [EpollEvent::empty(); 32];
@@ -186,6 +270,11 @@ impl<D> Backend<D> {
         target_os = "openbsd"
     ))]
     pub fn dispatch_all_clients(&mut self, data: &mut D) -> std::io::Result<usize> {
+        if !self.poll_clients {
+            return Ok(self.dispatch_each_client(data));
+        }
+        // clients killed outside of a dispatch, for example by a flush, are cleaned up as well
+        self.handle.cleanup(data);
         let mut dispatched = 0;
         loop {
             let mut events = [KEvent::new(
@@ -215,4 +304,17 @@ impl<D> Backend<D> {
 
         Ok(dispatched)
     }
+
+    /// Dispatches the clients one by one, as they are not registered to the internal poll
+    fn dispatch_each_client(&mut self, data: &mut D) -> usize {
+        let clients = self.handle.clients.all_clients_id().collect::<Vec<_>>();
+        let mut dispatched = 0;
+        for id in clients {
+            if let Ok(count) = self.handle.dispatch_events_for(data, id) {
+                dispatched += count;
+            }
+        }
+        self.handle.cleanup(data);
+        dispatched
+    }
 }
@@ -1,4 +1,4 @@
-use std::{ffi::CString, sync::Arc};
+use std::{ffi::CString, os::unix::io::RawFd, sync::Arc};
 
 use crate::{
     core_interfaces::{WL_DISPLAY_INTERFACE, WL_REGISTRY_INTERFACE},
@@ -8,8 +8,9 @@ use crate::{
 use smallvec::SmallVec;
 
 use super::{
-    client::ClientStore, registry::Registry, ClientData, ClientId, Credentials, Data,
-    GlobalHandler, GlobalId, ObjectData, ObjectId,
+    client::{ClientStore, DisplayError},
+    registry::Registry,
+    ClientData, ClientId, Credentials, Data, GlobalHandler, GlobalId, ObjectData, ObjectId,
 };
 use crate::rs::map::Object;
 
@@ -26,6 +27,7 @@ pub struct Handle<D> {
     pub(crate) clients: ClientStore<D>,
     pub(crate) registry: Registry<D>,
     pub(crate) pending_destructors: Vec<PendingDestructor<D>>,
+    pub(crate) catch_panics: bool,
 }
 
 enum DispatchAction<D> {
@@ -53,6 +55,7 @@ impl<D> Handle<D> {
             clients: ClientStore::new(debug),
             registry: Registry::new(),
             pending_destructors: Vec::new(),
+            catch_panics: false,
         }
     }
 
@@ -134,12 +137,28 @@ impl<D> Handle<D> {
                     is_destructor,
                     created_id,
                 } => {
-                    let ret = object.data.user_data.clone().request(
-                        self,
-                        data,
-                        client_id.clone(),
-                        Message { sender_id: object_id.clone(), opcode, args: arguments },
-                    );
+                    let ret = self.catch_panic(data, |handle, data| {
+                        object.data.user_data.clone().request(
+                            handle,
+                            data,
+                            client_id.clone(),
+                            Message { sender_id: object_id.clone(), opcode, args: arguments },
+                        )
+                    });
+                    let ret = match ret {
+                        Ok(ret) => ret,
+                        Err(()) => {
+                            let request_name = object.interface.requests[opcode as usize].name;
+                            self.kill_panicked_client(
+                                client_id,
+                                format!(
+                                    "request {}@{}.{}",
+                                    object.interface.name, object_id.id, request_name
+                                ),
+                            );
+                            break;
+                        }
+                    };
                     if is_destructor {
                         object.data.user_data.destroyed(data, client_id.clone(), object_id.clone());
                         if let Ok(client) = self.clients.get_client_mut(client_id.clone()) {
@@ -168,8 +187,18 @@ impl<D> Handle<D> {
                     }
                 }
                 DispatchAction::Bind { object, client, global, handler } => {
-                    let child_data =
-                        handler.bind(self, data, client.clone(), global, object.clone());
+                    let child_data = match self.catch_panic(data, |handle, data| {
+                        handler.bind(handle, data, client.clone(), global, object.clone())
+                    }) {
+                        Ok(child_data) => child_data,
+                        Err(()) => {
+                            self.kill_panicked_client(
+                                client,
+                                format!("bind of {}", object.interface.name),
+                            );
+                            break;
+                        }
+                    };
                     if let Ok(client) = self.clients.get_client_mut(client.clone()) {
                         client.map.with(object.id, |obj| obj.data.user_data = child_data).unwrap();
                     }
@@ -179,6 +208,29 @@ impl<D> Handle<D> {
         Ok(dispatched)
     }
 
+    /// Invokes a dispatch callback, catching its panics if enabled
+    fn catch_panic<T>(
+        &mut self,
+        data: &mut D,
+        f: impl FnOnce(&mut Self, &mut D) -> T,
+    ) -> Result<T, ()> {
+        if self.catch_panics {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(self, data))).map_err(|_| ())
+        } else {
+            Ok(f(self, data))
+        }
+    }
+
+    /// Kills a client whose callback panicked, its objects are destroyed during the next cleanup
+    fn kill_panicked_client(&mut self, client_id: ClientId, what: String) {
+        if let Ok(client) = self.clients.get_client_mut(client_id) {
+            client.post_display_error(
+                DisplayError::Implementation,
+                CString::new(format!("Internal server error while handling {}.", what)).unwrap(),
+            );
+        }
+    }
+
     pub(crate) fn flush(&mut self, client: Option<ClientId>) -> std::io::Result<()> {
         if let Some(client) = client {
             match self.clients.get_client_mut(client) {
@@ -221,6 +273,15 @@ impl<D> Handle<D> {
         Ok(client.get_credentials())
     }
 
+    /// Returns the file descriptor of the socket of a client
+    ///
+    /// It can be monitored for readability to know when [`Backend::dispatch_client()`](super::Backend::dispatch_client)
+    /// should be invoked for this client. It should not be used for any other purpose.
+    pub fn get_client_fd(&self, id: ClientId) -> Result<RawFd, InvalidId> {
+        let client = self.clients.get_client(id)?;
+        Ok(client.socket_fd())
+    }
+
     /// Returns an iterator over all clients connected to the server.
     pub fn all_clients<'a>(&'a self) -> Box<dyn Iterator<Item = ClientId> + 'a> {
         Box::new(self.clients.all_clients_id())
@@ -323,6 +384,21 @@ impl<D> Handle<D> {
         }
     }
 
+    /// Kills the connection to a client once all its pending events have been sent.
+    ///
+    /// Contrary to [`kill_client()`](Handle::kill_client), the connection is kept open until the outgoing
+    /// buffer of the client has been flushed, and the requests it sends in the meantime are ignored. If the
+    /// reason is a protocol error, the matching `wl_display.error` is sent to the client beforehand.
+    ///
+    /// [`ClientData::disconnected()`] is invoked with the given reason once the client is actually disconnected,
+    /// during a later flush or dispatch. A client that does not read its pending events within one second is
+    /// disconnected anyway, so the server must keep flushing its clients for the disconnection to complete.
+    pub fn kill_client_after_flush(&mut self, client_id: ClientId, reason: DisconnectReason) {
+        if let Ok(client) = self.clients.get_client_mut(client_id) {
+            client.kill_after_flush(reason)
+        }
+    }
+
     /// Creates a global of the specified interface and version and then advertises it to clients.
     ///
     /// The clients which the global is advertised to is determined by the implementation of the [`GlobalHandler`].
@@ -68,6 +68,28 @@ pub trait GlobalHandler<D>: downcast_rs::DowncastSync {
     ) -> bool {
         true
     }
+    /// Version of given global advertised to given client
+    ///
+    /// If this function returns `None`, the global is hidden from the client exactly as if
+    /// [`can_view()`](GlobalHandler::can_view) returned false. Otherwise the global is advertised
+    /// with the returned version (capped to the version of the global, `global_version`), and any
+    /// attempt of the client to bind it with a higher version will result in a protocol error.
+    ///
+    /// Default implementation advertises the version of the global to all clients for which
+    /// [`can_view()`](GlobalHandler::can_view) returns true.
+    fn advertised_version(
+        &self,
+        client_id: ClientId,
+        client_data: &Arc<dyn ClientData<D>>,
+        global_id: GlobalId,
+        global_version: u32,
+    ) -> Option<u32> {
+        if self.can_view(client_id, client_data, global_id) {
+            Some(global_version)
+        } else {
+            None
+        }
+    }
     /// A global has been bound
     ///
     /// Given client bound given global, creating given object.
@@ -184,8 +206,17 @@ impl PartialEq for ObjectId {
 
 impl std::cmp::Eq for ObjectId {}
 
+impl std::hash::Hash for ObjectId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.serial.hash(state);
+        self.client_id.hash(state);
+        self.interface.name.hash(state);
+    }
+}
+
 /// An id of a client connected to the server.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ClientId {
     id: u32,
     serial: u32,
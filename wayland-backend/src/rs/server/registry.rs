@@ -25,11 +25,27 @@ struct Global<D> {
     disabled: bool,
 }
 
+impl<D> Global<D> {
+    fn advertised_version(&self, client: &Client<D>) -> Option<u32> {
+        self.handler
+            .advertised_version(client.id.clone(), &client.data, self.id.clone(), self.version)
+            .map(|version| version.min(self.version))
+            .filter(|&version| version > 0)
+    }
+}
+
+#[derive(Debug)]
+struct KnownRegistry {
+    id: ObjectId,
+    // the globals that were advertised through this registry, and must be removed from it
+    advertised: Vec<GlobalId>,
+}
+
 #[derive(Debug)]
 
 pub struct Registry<D> {
     globals: Vec<Option<Global<D>>>,
-    known_registries: Vec<ObjectId>,
+    known_registries: Vec<KnownRegistry>,
     last_serial: u32,
 }
 
@@ -75,11 +91,7 @@ impl<D> Registry<D> {
     }
 
     fn get_global(&self, id: GlobalId) -> Result<&Global<D>, InvalidId> {
-        self.globals
-            .get(id.id as usize - 1)
-            .and_then(|o| o.as_ref())
-            .filter(|o| o.id == id)
-            .ok_or(InvalidId)
+        get_global(&self.globals, id)
     }
 
     pub(crate) fn get_info(&self, id: GlobalId) -> Result<GlobalInfo, InvalidId> {
@@ -110,14 +122,8 @@ impl<D> Registry<D> {
         if target_global.interface.name.as_bytes() != interface_name.to_bytes() {
             return None;
         }
-        if target_global.version < version {
-            return None;
-        }
-        if !target_global.handler.can_view(
-            client.id.clone(),
-            &client.data,
-            target_global.id.clone(),
-        ) {
+        let advertised_version = target_global.advertised_version(client)?;
+        if advertised_version < version {
             return None;
         }
 
@@ -125,7 +131,7 @@ impl<D> Registry<D> {
     }
 
     pub(crate) fn cleanup(&mut self, dead_clients: &[ClientId]) {
-        self.known_registries.retain(|obj_id| !dead_clients.contains(&obj_id.client_id))
+        self.known_registries.retain(|registry| !dead_clients.contains(&registry.id.client_id))
     }
 
     pub(crate) fn disable_global(&mut self, id: GlobalId, clients: &mut ClientStore<D>) {
@@ -137,10 +143,15 @@ impl<D> Registry<D> {
         // Do nothing if the global is already disabled
         if !global.disabled {
             global.disabled = true;
-            // send the global_remove
-            for registry in self.known_registries.iter().cloned() {
-                if let Ok(client) = clients.get_client_mut(registry.client_id.clone()) {
-                    let _ = send_global_remove_to(client, global, registry.clone());
+            // send the global_remove to the registries that were told about this global
+            for registry in self.known_registries.iter_mut() {
+                let len = registry.advertised.len();
+                registry.advertised.retain(|advertised| *advertised != id);
+                if registry.advertised.len() == len {
+                    continue;
+                }
+                if let Ok(client) = clients.get_client_mut(registry.id.client_id.clone()) {
+                    let _ = send_global_remove_to(client, global, registry.id.clone());
                 }
             }
         }
@@ -162,8 +173,8 @@ impl<D> Registry<D> {
         registry: ObjectId,
         client: &mut Client<D>,
     ) -> Result<(), InvalidId> {
-        self.send_all_globals_to(registry.clone(), client)?;
-        self.known_registries.push(registry);
+        let advertised = self.send_all_globals_to(registry.clone(), client)?;
+        self.known_registries.push(KnownRegistry { id: registry, advertised });
         Ok(())
     }
 
@@ -171,34 +182,37 @@ impl<D> Registry<D> {
         &self,
         registry: ObjectId,
         client: &mut Client<D>,
-    ) -> Result<(), InvalidId> {
+    ) -> Result<Vec<GlobalId>, InvalidId> {
+        let mut advertised = Vec::new();
         for global in self.globals.iter().flat_map(|opt| opt.as_ref()) {
-            if !global.disabled
-                && global.handler.can_view(client.id.clone(), &client.data, global.id.clone())
-            {
+            if global.disabled {
+                continue;
+            }
+            if let Some(version) = global.advertised_version(client) {
                 // fail the whole send on error, there is no point in trying further on a failing client
-                send_global_to(client, global, registry.clone())?;
+                send_global_to(client, global, version, registry.clone())?;
+                advertised.push(global.id.clone());
             }
         }
-        Ok(())
+        Ok(advertised)
     }
 
     pub(crate) fn send_global_to_all(
-        &self,
+        &mut self,
         global_id: GlobalId,
         clients: &mut ClientStore<D>,
     ) -> Result<(), InvalidId> {
-        let global = self.get_global(global_id)?;
+        let global = get_global(&self.globals, global_id)?;
         if global.disabled {
             return Err(InvalidId);
         }
-        for registry in self.known_registries.iter().cloned() {
-            if let Ok(client) = clients.get_client_mut(registry.client_id.clone()) {
-                if !global.disabled
-                    && global.handler.can_view(client.id.clone(), &client.data, global.id.clone())
-                {
+        for registry in self.known_registries.iter_mut() {
+            if let Ok(client) = clients.get_client_mut(registry.id.client_id.clone()) {
+                if let Some(version) = global.advertised_version(client) {
                     // don't fail the whole send for a single erroring client
-                    let _ = send_global_to(client, global, registry.clone());
+                    if send_global_to(client, global, version, registry.id.clone()).is_ok() {
+                        registry.advertised.push(global.id.clone());
+                    }
                 }
             }
         }
@@ -206,10 +220,15 @@ impl<D> Registry<D> {
     }
 }
 
+fn get_global<D>(globals: &[Option<Global<D>>], id: GlobalId) -> Result<&Global<D>, InvalidId> {
+    globals.get(id.id as usize - 1).and_then(|o| o.as_ref()).filter(|o| o.id == id).ok_or(InvalidId)
+}
+
 #[inline]
 fn send_global_to<D>(
     client: &mut Client<D>,
     global: &Global<D>,
+    version: u32,
     registry: ObjectId,
 ) -> Result<(), InvalidId> {
     client.send_event(
@@ -219,7 +238,7 @@ fn send_global_to<D>(
             [
                 Argument::Uint(global.id.id),
                 Argument::Str(Box::new(CString::new(global.interface.name).unwrap())),
-                Argument::Uint(global.version),
+                Argument::Uint(version),
             ],
         ),
         // This is not a destructor event
@@ -113,10 +113,8 @@ impl Drop for Socket {
 #[derive(Debug)]
 pub struct BufferedSocket {
     socket: Socket,
-    in_data: Buffer<u32>,
-    in_fds: Buffer<RawFd>,
-    out_data: Buffer<u32>,
-    out_fds: Buffer<RawFd>,
+    incoming: IncomingBuffers,
+    outgoing: OutgoingBuffers,
 }
 
 impl BufferedSocket {
@@ -124,47 +122,122 @@ impl BufferedSocket {
     pub fn new(socket: Socket) -> BufferedSocket {
         BufferedSocket {
             socket,
-            in_data: Buffer::new(2 * MAX_BYTES_OUT / 4), // Incoming buffers are twice as big in order to be
-            in_fds: Buffer::new(2 * MAX_FDS_OUT),        // able to store leftover data if needed
-            out_data: Buffer::new(MAX_BYTES_OUT / 4),
-            out_fds: Buffer::new(MAX_FDS_OUT),
+            incoming: IncomingBuffers::new(),
+            outgoing: OutgoingBuffers::new(),
         }
     }
 
     /// Flush the contents of the outgoing buffer into the socket
     pub fn flush(&mut self) -> IoResult<()> {
+        self.outgoing.flush(&self.socket)
+    }
+
+    /// Check whether the outgoing buffer still contains data waiting to be sent
+    pub fn has_pending_output(&self) -> bool {
+        self.outgoing.has_pending_output()
+    }
+
+    /// Write a message to the outgoing buffer
+    ///
+    /// This method may flush the internal buffer if necessary (if it is full).
+    ///
+    /// If the message is too big to fit in the buffer, the error `Error::Sys(E2BIG)`
+    /// will be returned.
+    pub fn write_message(&mut self, msg: &Message<u32>) -> IoResult<()> {
+        self.outgoing.write_message(&self.socket, msg)
+    }
+
+    /// Try to fill the incoming buffers of this socket, to prepare
+    /// a new round of parsing.
+    pub fn fill_incoming_buffers(&mut self) -> IoResult<()> {
+        self.incoming.fill(&self.socket)
+    }
+
+    /// Drop the contents of the incoming buffers, closing any received file descriptor
+    pub fn discard_incoming(&mut self) {
+        self.incoming.discard()
+    }
+
+    /// Read and deserialize a single message from the incoming buffers socket
+    ///
+    /// This method requires one closure that given an object id and an opcode,
+    /// must provide the signature of the associated request/event, in the form of
+    /// a `&'static [ArgumentType]`.
+    pub fn read_one_message<F>(&mut self, signature: F) -> Result<Message<u32>, MessageParseError>
+    where
+        F: FnMut(u32, u16) -> Option<&'static [ArgumentType]>,
+    {
+        self.incoming.read_one_message(signature)
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+impl AsRawFd for BufferedSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.socket.fd
+    }
+}
+
+/*
+ * Incoming and outgoing buffers
+ */
+
+/// The outgoing half of a [`BufferedSocket`]
+///
+/// It can be used separately from the incoming half, to synchronize the sending and the
+/// receiving of messages independently.
+#[derive(Debug)]
+pub struct OutgoingBuffers {
+    data: Buffer<u32>,
+    fds: Buffer<RawFd>,
+}
+
+impl OutgoingBuffers {
+    /// Create new empty outgoing buffers
+    pub fn new() -> OutgoingBuffers {
+        OutgoingBuffers { data: Buffer::new(MAX_BYTES_OUT / 4), fds: Buffer::new(MAX_FDS_OUT) }
+    }
+
+    /// Flush the contents of the buffers into the socket
+    pub fn flush(&mut self, socket: &Socket) -> IoResult<()> {
         let written = {
-            let words = self.out_data.get_contents();
+            let words = self.data.get_contents();
             if words.is_empty() {
                 return Ok(());
             }
             let bytes = unsafe {
                 ::std::slice::from_raw_parts(words.as_ptr() as *const u8, words.len() * 4)
             };
-            let fds = self.out_fds.get_contents();
-            let written = self.socket.send_msg(bytes, fds)?;
+            let fds = self.fds.get_contents();
+            let written = socket.send_msg(bytes, fds)?;
             for &fd in fds {
                 // once the fds are sent, we can close them
                 let _ = ::nix::unistd::close(fd);
             }
             written
         };
-        self.out_data.offset(written / 4);
-        self.out_data.move_to_front();
-        self.out_fds.clear();
+        self.data.offset(written / 4);
+        self.data.move_to_front();
+        self.fds.clear();
         Ok(())
     }
 
-    pub fn blocking_flush(&mut self) -> IoResult<()> {
+    /// Check whether the buffers still contain data waiting to be sent
+    pub fn has_pending_output(&self) -> bool {
+        !self.data.get_contents().is_empty()
+    }
+
+    /// Flush the contents of the buffers into the socket, waiting for it to be writable if needed
+    pub fn blocking_flush(&mut self, socket: &Socket) -> IoResult<()> {
         loop {
-            match self.flush() {
+            match self.flush(socket) {
                 Ok(()) => return Ok(()),
                 Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
                 Err(e) => return Err(e),
             }
 
             nix::poll::poll(
-                &mut [nix::poll::PollFd::new(self.as_raw_fd(), nix::poll::PollFlags::POLLOUT)],
+                &mut [nix::poll::PollFd::new(socket.as_raw_fd(), nix::poll::PollFlags::POLLOUT)],
                 -1,
             )?;
         }
@@ -172,7 +245,7 @@ impl BufferedSocket {
 
     // internal method
     //
-    // attempts to write a message in the internal out buffers,
+    // attempts to write a message in the buffers,
     // returns true if successful
     //
     // if false is returned, it means there is not enough space
@@ -180,12 +253,12 @@ impl BufferedSocket {
     fn attempt_write_message(&mut self, msg: &Message<u32>) -> IoResult<bool> {
         match write_to_buffers(
             msg,
-            self.out_data.get_writable_storage(),
-            self.out_fds.get_writable_storage(),
+            self.data.get_writable_storage(),
+            self.fds.get_writable_storage(),
         ) {
             Ok((bytes_out, fds_out)) => {
-                self.out_data.advance(bytes_out);
-                self.out_fds.advance(fds_out);
+                self.data.advance(bytes_out);
+                self.fds.advance(fds_out);
                 Ok(true)
             }
             Err(MessageWriteError::BufferTooSmall) => Ok(false),
@@ -193,17 +266,17 @@ impl BufferedSocket {
         }
     }
 
-    /// Write a message to the outgoing buffer
+    /// Write a message to the buffers
     ///
-    /// This method may flush the internal buffer if necessary (if it is full).
+    /// This method may flush the buffers into the socket if necessary (if they are full).
     ///
     /// If the message is too big to fit in the buffer, the error `Error::Sys(E2BIG)`
     /// will be returned.
-    pub fn write_message(&mut self, msg: &Message<u32>) -> IoResult<()> {
+    pub fn write_message(&mut self, socket: &Socket, msg: &Message<u32>) -> IoResult<()> {
         if !self.attempt_write_message(msg)? {
             // the attempt failed, there is not enough space in the buffer
             // we need to flush it
-            self.blocking_flush()?;
+            self.blocking_flush(socket)?;
             if !self.attempt_write_message(msg)? {
                 // If this fails again, this means the message is too big
                 // to be transmitted at all
@@ -212,33 +285,75 @@ impl BufferedSocket {
         }
         Ok(())
     }
+}
 
-    /// Try to fill the incoming buffers of this socket, to prepare
-    /// a new round of parsing.
-    pub fn fill_incoming_buffers(&mut self) -> IoResult<()> {
+impl Default for OutgoingBuffers {
+    fn default() -> OutgoingBuffers {
+        OutgoingBuffers::new()
+    }
+}
+
+/// The incoming half of a [`BufferedSocket`]
+///
+/// It can be used separately from the outgoing half, to synchronize the sending and the
+/// receiving of messages independently.
+#[derive(Debug)]
+pub struct IncomingBuffers {
+    data: Buffer<u32>,
+    fds: Buffer<RawFd>,
+}
+
+impl IncomingBuffers {
+    /// Create new empty incoming buffers
+    pub fn new() -> IncomingBuffers {
+        IncomingBuffers {
+            data: Buffer::new(2 * MAX_BYTES_OUT / 4), // Incoming buffers are twice as big in order to be
+            fds: Buffer::new(2 * MAX_FDS_OUT),        // able to store leftover data if needed
+        }
+    }
+
+    /// Try to fill the buffers from the socket, to prepare a new round of parsing.
+    pub fn fill(&mut self, socket: &Socket) -> IoResult<()> {
         // reorganize the buffers
-        self.in_data.move_to_front();
-        self.in_fds.move_to_front();
+        self.data.move_to_front();
+        self.fds.move_to_front();
         // receive a message
         let (in_bytes, in_fds) = {
-            let words = self.in_data.get_writable_storage();
+            let words = self.data.get_writable_storage();
             let bytes = unsafe {
                 ::std::slice::from_raw_parts_mut(words.as_ptr() as *mut u8, words.len() * 4)
             };
-            let fds = self.in_fds.get_writable_storage();
-            self.socket.rcv_msg(bytes, fds)?
+            let fds = self.fds.get_writable_storage();
+            socket.rcv_msg(bytes, fds)?
         };
         if in_bytes == 0 {
             // the other end of the socket was closed
             return Err(::nix::errno::Errno::EPIPE.into());
         }
         // advance the storage
-        self.in_data.advance(in_bytes / 4 + if in_bytes % 4 > 0 { 1 } else { 0 });
-        self.in_fds.advance(in_fds);
+        self.data.advance(in_bytes / 4 + if in_bytes % 4 > 0 { 1 } else { 0 });
+        self.fds.advance(in_fds);
         Ok(())
     }
 
-    /// Read and deserialize a single message from the incoming buffers socket
+    /// Drop the contents of the buffers, closing any received file descriptor
+    pub fn discard(&mut self) {
+        for &fd in self.fds.get_contents() {
+            let _ = ::nix::unistd::close(fd);
+        }
+        self.data.clear();
+        self.fds.clear();
+    }
+
+    /// Get the object id and opcode of the next message in the buffers, if its header was received
+    pub fn peek_header(&self) -> Option<(u32, u16)> {
+        match self.data.get_contents() {
+            [object_id, word, ..] => Some((*object_id, (word & 0x0000_FFFF) as u16)),
+            _ => None,
+        }
+    }
+
+    /// Read and deserialize a single message from the buffers
     ///
     /// This method requires one closure that given an object id and an opcode,
     /// must provide the signature of the associated request/event, in the form of
@@ -251,8 +366,8 @@ impl BufferedSocket {
         F: FnMut(u32, u16) -> Option<&'static [ArgumentType]>,
     {
         let (msg, read_data, read_fd) = {
-            let data = self.in_data.get_contents();
-            let fds = self.in_fds.get_contents();
+            let data = self.data.get_contents();
+            let fds = self.fds.get_contents();
             if data.len() < 2 {
                 return Err(MessageParseError::MissingData);
             }
@@ -272,17 +387,16 @@ impl BufferedSocket {
             }
         };
 
-        self.in_data.offset(read_data);
-        self.in_fds.offset(read_fd);
+        self.data.offset(read_data);
+        self.fds.offset(read_fd);
 
         Ok(msg)
     }
 }
 
-#[cfg(not(tarpaulin_include))]
-impl AsRawFd for BufferedSocket {
-    fn as_raw_fd(&self) -> RawFd {
-        self.socket.fd
+impl Default for IncomingBuffers {
+    fn default() -> IncomingBuffers {
+        IncomingBuffers::new()
     }
 }
 
@@ -333,13 +447,9 @@ impl<T: Copy + Default> Buffer<T> {
     /// Move the unread contents of the buffer to the front, to ensure
     /// maximal write space availability
     fn move_to_front(&mut self) {
-        unsafe {
-            ::std::ptr::copy(
-                &self.storage[self.offset] as *const T,
-                &mut self.storage[0] as *mut T,
-                self.occupied - self.offset,
-            );
-        }
+        // this also handles the case of a completely read full buffer, where
+        // offset == occupied == storage.len()
+        self.storage.copy_within(self.offset..self.occupied, 0);
         self.occupied -= self.offset;
         self.offset = 0;
     }
@@ -7,15 +7,17 @@ use std::{
     os::unix::{io::RawFd, net::UnixStream, prelude::IntoRawFd},
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
+        Arc, Mutex, Weak,
     },
+    task::{Context, Poll, Waker},
 };
 
 use crate::{
     core_interfaces::WL_DISPLAY_INTERFACE,
     protocol::{
-        check_for_signature, same_interface, AllowNull, Argument, ArgumentType, Interface, Message,
-        ObjectInfo, ProtocolError, ANONYMOUS_INTERFACE,
+        check_for_signature, same_interface, warn_if_deprecated, AllowNull, Argument, ArgumentType,
+        Interface, Message, ObjectInfo, ProtocolError, ANONYMOUS_INTERFACE,
+        DISPLAY_ERROR_INVALID_METHOD, DISPLAY_ERROR_INVALID_OBJECT,
     },
 };
 use scoped_tls::scoped_thread_local;
@@ -47,7 +49,7 @@ pub trait ObjectData: downcast_rs::DowncastSync {
         msg: Message<ObjectId>,
     ) -> Option<Arc<dyn ObjectData>>;
     /// Notification that the object has been destroyed and is no longer active
-    fn destroyed(&self, object_id: ObjectId);
+    fn destroyed(self: Arc<Self>, object_id: ObjectId);
     /// Helper for forwarding a Debug implementation of your `ObjectData` type
     ///
     /// By default will just print `ObjectData { ... }`
@@ -97,6 +99,21 @@ impl std::cmp::PartialEq for ObjectId {
 
 impl std::cmp::Eq for ObjectId {}
 
+impl std::hash::Hash for ObjectId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.alive {
+            // this is an object we manage
+            Some(ref alive) => (Arc::as_ptr(alive) as usize).hash(state),
+            // this is an external (un-managed) object
+            None => {
+                (self.ptr as usize).hash(state);
+                self.id.hash(state);
+                self.interface.name.hash(state);
+            }
+        }
+    }
+}
+
 impl ObjectId {
     /// Check if this is the null ID
     pub fn is_null(&self) -> bool {
@@ -190,18 +207,37 @@ struct ProxyUserData {
     interface: &'static Interface,
 }
 
+/// The state of a connection, shared by all clones of its [`Backend`] and [`Handle`]
+///
+/// The system libwayland synchronizes the sending of requests and the reading of events by itself, only
+/// the dispatching of the events to their callbacks is serialized here.
+#[derive(Debug)]
+struct ConnectionState {
+    display: *mut wl_display,
+    evq: *mut wl_event_queue,
+    display_id: ObjectId,
+    last_error: Mutex<Option<WaylandError>>,
+    // always locked after last_error
+    error_wakers: Mutex<Vec<Waker>>,
+    dispatch_lock: Mutex<()>,
+}
+
+unsafe impl Send for ConnectionState {}
+unsafe impl Sync for ConnectionState {}
+
 /// Main handle of a backend to the Wayland protocol
 ///
 /// This type hosts most of the protocol-related functionality of the backend, and is the
 /// main entry point for manipulating Wayland objects. It can be retrieved both from
 /// the backend via [`Backend::handle()`](Backend::handle), and is given to you as argument
 /// in most event callbacks.
-#[derive(Debug)]
+///
+/// Cloning a handle is cheap, and all clones refer to the same connection. Each of them can be
+/// used from a different thread without contending with the others beyond the serialization of
+/// the messages.
+#[derive(Debug, Clone)]
 pub struct Handle {
-    display: *mut wl_display,
-    evq: *mut wl_event_queue,
-    display_id: ObjectId,
-    last_error: Option<WaylandError>,
+    state: Arc<ConnectionState>,
     pending_placeholder: Option<(&'static Interface, u32)>,
 }
 
@@ -210,13 +246,33 @@ pub struct Handle {
 /// This type hosts the plumbing functionalities for interacting with the wayland protocol,
 /// and most of the protocol-level interactions are made through the [`Handle`] type, accessed
 /// via the [`handle()`](Backend::handle) method.
-#[derive(Debug)]
+///
+/// Cloning the backend is cheap, and all clones refer to the same connection, which is closed
+/// once all of them are dropped.
+#[derive(Debug, Clone)]
 pub struct Backend {
     handle: Handle,
 }
 
-unsafe impl Send for Backend {}
-unsafe impl Sync for Backend {}
+/// A weak reference to a [`Backend`]
+///
+/// It does not keep the connection alive, and can be upgraded back into a [`Backend`] as long
+/// as the connection is.
+#[derive(Debug, Clone, Default)]
+pub struct WeakBackend {
+    state: Weak<ConnectionState>,
+}
+
+impl WeakBackend {
+    /// Try to upgrade this weak reference into a [`Backend`]
+    ///
+    /// Returns `None` if the connection has been dropped.
+    pub fn upgrade(&self) -> Option<Backend> {
+        self.state
+            .upgrade()
+            .map(|state| Backend { handle: Handle { state, pending_placeholder: None } })
+    }
+}
 
 impl Backend {
     /// Try to initialize a Wayland backend on the provided unix stream
@@ -242,27 +298,33 @@ impl Backend {
             );
         }
         let display_alive = Arc::new(AtomicBool::new(true));
-        Ok(Self {
-            handle: Handle {
-                display,
-                evq: std::ptr::null_mut(),
-                display_id: ObjectId {
-                    id: 1,
-                    ptr: display as *mut wl_proxy,
-                    alive: Some(display_alive),
-                    interface: &WL_DISPLAY_INTERFACE,
-                },
-                last_error: None,
-                pending_placeholder: None,
+        let state = ConnectionState {
+            display,
+            evq: std::ptr::null_mut(),
+            display_id: ObjectId {
+                id: 1,
+                ptr: display as *mut wl_proxy,
+                alive: Some(display_alive),
+                interface: &WL_DISPLAY_INTERFACE,
             },
-        })
+            last_error: Mutex::new(None),
+            error_wakers: Mutex::new(Vec::new()),
+            dispatch_lock: Mutex::new(()),
+        };
+        Ok(Self { handle: Handle { state: Arc::new(state), pending_placeholder: None } })
+    }
+
+    /// Create a weak reference to this backend
+    pub fn downgrade(&self) -> WeakBackend {
+        WeakBackend { state: Arc::downgrade(&self.handle.state) }
     }
 
     /// Flush all pending outgoing requests to the server
-    pub fn flush(&mut self) -> Result<(), WaylandError> {
+    pub fn flush(&self) -> Result<(), WaylandError> {
         self.handle.no_last_error()?;
-        let ret =
-            unsafe { ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_flush, self.handle.display) };
+        let ret = unsafe {
+            ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_flush, self.handle.state.display)
+        };
         if ret < 0 {
             Err(self
                 .handle
@@ -279,7 +341,7 @@ impl Backend {
     ///
     /// **Note:** this function should only be used if you know that you are the only thread
     /// reading events from the wayland socket. If this may not be the case, see [`ReadEventsGuard`]
-    pub fn dispatch_events(&mut self) -> Result<usize, WaylandError> {
+    pub fn dispatch_events(&self) -> Result<usize, WaylandError> {
         self.handle.no_last_error()?;
         self.handle.try_read()?;
         self.handle.dispatch_pending()
@@ -294,7 +356,7 @@ impl Backend {
 impl Handle {
     #[inline]
     fn no_last_error(&self) -> Result<(), WaylandError> {
-        if let Some(ref err) = self.last_error {
+        if let Some(ref err) = *self.state.last_error.lock().unwrap() {
             Err(err.clone())
         } else {
             Ok(())
@@ -302,20 +364,23 @@ impl Handle {
     }
 
     #[inline]
-    fn store_and_return_error(&mut self, err: std::io::Error) -> WaylandError {
-        // check if it was actually a protocol error
-        let err = if err.raw_os_error() == Some(nix::errno::Errno::EPROTO as i32) {
-            let mut object_id = 0;
-            let mut interface = std::ptr::null();
-            let code = unsafe {
-                ffi_dispatch!(
-                    WAYLAND_CLIENT_HANDLE,
-                    wl_display_get_protocol_error,
-                    self.display,
-                    &mut interface,
-                    &mut object_id
-                )
-            };
+    fn store_and_return_error(&self, err: std::io::Error) -> WaylandError {
+        // check if it was actually a protocol error, errors on the wl_display are reported with
+        // other error codes than EPROTO, but libwayland only records the interface of protocol errors
+        let mut object_id = 0;
+        let mut interface = std::ptr::null();
+        let code = unsafe {
+            ffi_dispatch!(
+                WAYLAND_CLIENT_HANDLE,
+                wl_display_get_protocol_error,
+                self.state.display,
+                &mut interface,
+                &mut object_id
+            )
+        };
+        let err = if !interface.is_null()
+            || err.raw_os_error() == Some(nix::errno::Errno::EPROTO as i32)
+        {
             let object_interface = unsafe {
                 if interface.is_null() {
                     String::new()
@@ -329,17 +394,49 @@ impl Handle {
                 object_id,
                 object_interface,
                 message: String::new(),
+                message_name: None,
             })
         } else {
             WaylandError::Io(err)
         };
         log::error!("{}", err);
-        self.last_error = Some(err.clone());
+        self.store_error(&mut self.state.last_error.lock().unwrap(), err.clone());
         err
     }
 
+    fn store_error(&self, last_error: &mut Option<WaylandError>, err: WaylandError) {
+        *last_error = Some(err);
+        for waker in self.state.error_wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Store a protocol violation by the server detected while processing an event, unless an
+    /// other error was already stored
+    fn store_violation(
+        &self,
+        object_id: u32,
+        interface: &'static Interface,
+        message_name: &'static str,
+        code: u32,
+        message: String,
+    ) {
+        let mut last_error = self.state.last_error.lock().unwrap();
+        if last_error.is_none() {
+            let err = WaylandError::Protocol(ProtocolError {
+                code,
+                object_id,
+                object_interface: interface.name.into(),
+                message,
+                message_name: Some(message_name),
+            });
+            log::error!("{}", err);
+            self.store_error(&mut last_error, err);
+        }
+    }
+
     #[inline]
-    fn store_if_not_wouldblock_and_return_error(&mut self, e: std::io::Error) -> WaylandError {
+    fn store_if_not_wouldblock_and_return_error(&self, e: std::io::Error) -> WaylandError {
         if e.kind() != std::io::ErrorKind::WouldBlock {
             self.store_and_return_error(e)
         } else {
@@ -347,50 +444,60 @@ impl Handle {
         }
     }
 
-    fn dispatch_pending(&mut self) -> Result<usize, WaylandError> {
-        let display = self.display;
-        let evq = self.evq;
-
-        // We erase the lifetime of the Handle to be able to store it in the tls,
-        // it's safe as it'll only last until the end of this function call anyway
-        let ret =
-            HANDLE.set(&RefCell::new(unsafe { std::mem::transmute(&mut *self) }), || unsafe {
-                if evq.is_null() {
-                    ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_dispatch_pending, display)
-                } else {
-                    ffi_dispatch!(
-                        WAYLAND_CLIENT_HANDLE,
-                        wl_display_dispatch_queue_pending,
-                        display,
-                        evq
-                    )
-                }
-            });
+    fn dispatch_pending(&self) -> Result<usize, WaylandError> {
+        let display = self.state.display;
+        let evq = self.state.evq;
+
+        // The callbacks are given their own handle, so that they do not share the pending
+        // placeholder of this one
+        let mut handle = Handle { state: self.state.clone(), pending_placeholder: None };
+        let _dispatch_guard = self.state.dispatch_lock.lock().unwrap();
+
+        // SAFETY: we erase the lifetime of the Handle to be able to store it in the tls. The
+        // reference only lives in the tls for the duration of the `HANDLE.set()` call, which
+        // ends before `handle` is dropped at the end of this function.
+        let handle_ref =
+            unsafe { std::mem::transmute::<&mut Handle, &'static mut Handle>(&mut handle) };
+        let ret = HANDLE.set(&RefCell::new(handle_ref), || unsafe {
+            if evq.is_null() {
+                ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_dispatch_pending, display)
+            } else {
+                ffi_dispatch!(
+                    WAYLAND_CLIENT_HANDLE,
+                    wl_display_dispatch_queue_pending,
+                    display,
+                    evq
+                )
+            }
+        });
         if ret < 0 {
             Err(self.store_if_not_wouldblock_and_return_error(std::io::Error::last_os_error()))
         } else {
+            // a callback may have detected a protocol violation
+            self.no_last_error()?;
             Ok(ret as usize)
         }
     }
 
-    fn try_read(&mut self) -> Result<(), WaylandError> {
+    fn try_read(&self) -> Result<(), WaylandError> {
         let ret = unsafe {
-            if self.evq.is_null() {
-                ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_prepare_read, self.display)
+            if self.state.evq.is_null() {
+                ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_prepare_read, self.state.display)
             } else {
                 ffi_dispatch!(
                     WAYLAND_CLIENT_HANDLE,
                     wl_display_prepare_read_queue,
-                    self.display,
-                    self.evq
+                    self.state.display,
+                    self.state.evq
                 )
             }
         };
         if ret < 0 {
             return Ok(());
         }
-        let ret =
-            unsafe { ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_read_events, self.display) };
+        let ret = unsafe {
+            ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_read_events, self.state.display)
+        };
         if ret < 0 {
             Err(self.store_if_not_wouldblock_and_return_error(std::io::Error::last_os_error()))
         } else {
@@ -415,9 +522,11 @@ impl Handle {
 ///
 /// This synchronization is compatible with the "prepare_read" mechanism of the system libwayland,
 /// and will correctly synchronize with other C libraries using the same Wayland socket.
+///
+/// Holding a guard, or reading events, does not prevent other threads from sending requests.
 #[derive(Debug)]
 pub struct ReadEventsGuard {
-    backend: Arc<Mutex<Backend>>,
+    backend: Backend,
     display: *mut wl_display,
     done: bool,
 }
@@ -427,10 +536,9 @@ impl ReadEventsGuard {
     ///
     /// This call will not block, but event callbacks may be invoked in the process
     /// of preparing the guard.
-    pub fn try_new(backend: Arc<Mutex<Backend>>) -> Result<Self, WaylandError> {
-        let mut backend_guard = backend.lock().unwrap();
-        let display = backend_guard.handle.display;
-        let evq = backend_guard.handle.evq;
+    pub fn try_new(backend: Backend) -> Result<Self, WaylandError> {
+        let display = backend.handle.state.display;
+        let evq = backend.handle.state.evq;
 
         // do the prepare_read() and dispatch as necessary
         loop {
@@ -447,14 +555,12 @@ impl ReadEventsGuard {
                 }
             };
             if ret < 0 {
-                backend_guard.handle.dispatch_pending()?;
+                backend.handle.dispatch_pending()?;
             } else {
                 break;
             }
         }
 
-        std::mem::drop(backend_guard);
-
         // prepare_read is done, we are ready
         Ok(ReadEventsGuard { backend, display, done: false })
     }
@@ -481,13 +587,11 @@ impl ReadEventsGuard {
             // we have done the reading, and there is an error
             Err(self
                 .backend
-                .lock()
-                .unwrap()
                 .handle
                 .store_if_not_wouldblock_and_return_error(std::io::Error::last_os_error()))
         } else {
             // the read occured, dispatch pending events
-            self.backend.lock().unwrap().handle.dispatch_pending()
+            self.backend.handle.dispatch_pending()
         }
     }
 }
@@ -505,14 +609,30 @@ impl Drop for ReadEventsGuard {
 impl Handle {
     /// Get the object ID for the `wl_display`
     pub fn display_id(&self) -> ObjectId {
-        self.display_id.clone()
+        self.state.display_id.clone()
     }
 
     /// Get the last error that occurred on this backend
     ///
     /// If this returns an error, your Wayland connection is already dead.
     pub fn last_error(&self) -> Option<WaylandError> {
-        self.last_error.clone()
+        self.state.last_error.lock().unwrap().clone()
+    }
+
+    /// Poll for the error that kills this connection
+    ///
+    /// This returns the last error if there is one, and otherwise registers the waker of the
+    /// provided context to be woken up once an error is stored.
+    pub fn poll_error(&self, cx: &mut Context<'_>) -> Poll<WaylandError> {
+        let last_error = self.state.last_error.lock().unwrap();
+        if let Some(ref err) = *last_error {
+            return Poll::Ready(err.clone());
+        }
+        let mut wakers = self.state.error_wakers.lock().unwrap();
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
     }
 
     /// Get the detailed information about a wayland object
@@ -550,6 +670,9 @@ impl Handle {
     /// If a specification is provided it'll be checked against what can be deduced from the
     /// protocol specification, and [`send_request`](Handle::send_request) will panic if they
     /// do not match.
+    ///
+    /// The placeholder is specific to this `Handle`, and is consumed by its next call to
+    /// [`send_request`](Handle::send_request).
     pub fn placeholder_id(&mut self, spec: Option<(&'static Interface, u32)>) -> ObjectId {
         self.pending_placeholder = spec;
         ObjectId {
@@ -604,6 +727,7 @@ impl Handle {
                 id.interface.name, id.id, message_desc.name, message_desc.signature, args
             );
         }
+        warn_if_deprecated(id.interface, id.id, parent_version, message_desc);
 
         // Prepare the child object data
         let child_spec = if message_desc
@@ -843,7 +967,15 @@ unsafe extern "C" fn dispatcher_func(
     let message_desc = match interface.events.get(opcode as usize) {
         Some(desc) => desc,
         None => {
-            log::error!("Unknown event opcode {} for interface {}.", opcode, interface.name);
+            HANDLE.with(|handle| {
+                handle.borrow().store_violation(
+                    ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_proxy_get_id, proxy),
+                    interface,
+                    "<unknown>",
+                    DISPLAY_ERROR_INVALID_METHOD,
+                    format!("Unknown opcode {} for interface {}.", opcode, interface.name),
+                )
+            });
             return -1;
         }
     };
@@ -881,14 +1013,18 @@ unsafe extern "C" fn dispatcher_func(
                             &*(ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_proxy_get_user_data, obj)
                                 as *mut ProxyUserData);
                         if !same_interface(next_interface, obj_udata.interface) {
-                            log::error!(
-                                "Received object {}@{} in {}.{} but expected interface {}.",
-                                obj_udata.interface.name,
-                                obj_id,
-                                interface.name,
-                                message_desc.name,
-                                next_interface.name,
-                            );
+                            HANDLE.with(|handle| {
+                                handle.borrow().store_violation(
+                                    ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_proxy_get_id, proxy),
+                                    interface,
+                                    message_desc.name,
+                                    DISPLAY_ERROR_INVALID_OBJECT,
+                                    format!(
+                                        "Server sent object {} for interface {}, but it has interface {}.",
+                                        obj_id, next_interface.name, obj_udata.interface.name
+                                    ),
+                                )
+                            });
                             return -1;
                         }
                         parsed_args.push(Argument::Object(ObjectId {
@@ -1004,13 +1140,11 @@ extern "C" {
     fn wl_log_trampoline_to_rust_client(fmt: *const c_char, list: *const c_void);
 }
 
-impl Drop for Backend {
+impl Drop for ConnectionState {
     fn drop(&mut self) {
-        if self.handle.evq.is_null() {
+        if self.evq.is_null() {
             // we are own the connection, clone it
-            unsafe {
-                ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_disconnect, self.handle.display)
-            }
+            unsafe { ffi_dispatch!(WAYLAND_CLIENT_HANDLE, wl_display_disconnect, self.display) }
         }
     }
 }
@@ -1026,7 +1160,7 @@ impl ObjectData for DumbObjectData {
         unreachable!()
     }
 
-    fn destroyed(&self, _object_id: ObjectId) {
+    fn destroyed(self: Arc<Self>, _object_id: ObjectId) {
         unreachable!()
     }
 }
@@ -1042,7 +1176,7 @@ impl ObjectData for UninitObjectData {
         panic!("Received a message on an uninitialized object: {:?}", msg);
     }
 
-    fn destroyed(&self, _object_id: ObjectId) {}
+    fn destroyed(self: Arc<Self>, _object_id: ObjectId) {}
 
     fn debug(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UninitObjectData").finish()
@@ -11,11 +11,12 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 use crate::protocol::{
-    check_for_signature, same_interface, AllowNull, Argument, ArgumentType, Interface, Message,
-    ObjectInfo, ANONYMOUS_INTERFACE,
+    check_for_signature, same_interface, warn_if_deprecated, AllowNull, Argument, ArgumentType,
+    Interface, Message, ObjectInfo, ANONYMOUS_INTERFACE,
 };
 use scoped_tls::scoped_thread_local;
 use smallvec::SmallVec;
@@ -24,6 +25,7 @@ use wayland_sys::{common::*, ffi_dispatch, server::*};
 
 use super::{free_arrays, RUST_MANAGED};
 
+use crate::types::server::CLOSING_TIMEOUT;
 pub use crate::types::server::{Credentials, DisconnectReason, GlobalInfo, InitError, InvalidId};
 
 // First pointer is &mut Handle<D>, and second pointer is &mut D
@@ -89,6 +91,35 @@ pub trait GlobalHandler<D>: downcast_rs::DowncastSync {
     ) -> bool {
         true
     }
+    /// Version of given global advertised to given client
+    ///
+    /// If this function returns `None`, the global is hidden from the client exactly as if
+    /// [`can_view()`](GlobalHandler::can_view) returned false. Otherwise the global is advertised
+    /// with the returned version (capped to the version of the global, `global_version`), and any
+    /// attempt of the client to bind it with a higher version will result in a protocol error.
+    ///
+    /// # Backend specific
+    ///
+    /// `libwayland-server` always advertises a global with the version it was created with, so advertising
+    /// a per-client version is only supported by the `rs` backend. The `sys` backend only uses this method to
+    /// hide the global when it returns `None`, and clients may bind the global with the version it was created
+    /// with.
+    ///
+    /// Default implementation advertises the version of the global to all clients for which
+    /// [`can_view()`](GlobalHandler::can_view) returns true.
+    fn advertised_version(
+        &self,
+        client_id: ClientId,
+        client_data: &Arc<dyn ClientData<D>>,
+        global_id: GlobalId,
+        global_version: u32,
+    ) -> Option<u32> {
+        if self.can_view(client_id, client_data, global_id) {
+            Some(global_version)
+        } else {
+            None
+        }
+    }
     /// A global has been bound
     ///
     /// Given client bound given global, creating given object.
@@ -175,6 +206,21 @@ impl std::cmp::PartialEq for ObjectId {
 
 impl std::cmp::Eq for ObjectId {}
 
+impl std::hash::Hash for ObjectId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.alive {
+            // this is an object we manage
+            Some(ref alive) => (Arc::as_ptr(alive) as usize).hash(state),
+            // this is an external object
+            None => {
+                (self.ptr as usize).hash(state);
+                self.id.hash(state);
+                self.interface.name.hash(state);
+            }
+        }
+    }
+}
+
 #[cfg(not(tarpaulin_include))]
 impl std::fmt::Display for ObjectId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -308,6 +354,12 @@ impl std::cmp::PartialEq for ClientId {
 
 impl std::cmp::Eq for ClientId {}
 
+impl std::hash::Hash for ClientId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.alive) as usize).hash(state)
+    }
+}
+
 /// The ID of a global
 #[derive(Debug, Clone)]
 pub struct GlobalId {
@@ -336,6 +388,8 @@ struct ResourceUserData<D> {
 struct ClientUserData<D> {
     data: Arc<dyn ClientData<D>>,
     alive: Arc<AtomicBool>,
+    // reason of the disconnection, if the client is waiting for its pending events to be sent
+    closing: Option<DisconnectReason>,
 }
 
 struct GlobalUserData<D> {
@@ -357,6 +411,8 @@ struct GlobalUserData<D> {
 pub struct Handle<D> {
     display: *mut wl_display,
     pending_destructors: Vec<PendingDestructor<D>>,
+    closing_clients: Vec<(ClientId, Instant)>,
+    catch_panics: bool,
     _data: std::marker::PhantomData<fn(&mut D)>,
 }
 
@@ -406,11 +462,26 @@ impl<D> Backend<D> {
             handle: Handle {
                 display,
                 pending_destructors: Vec::new(),
+                closing_clients: Vec::new(),
+                catch_panics: false,
                 _data: std::marker::PhantomData,
             },
         })
     }
 
+    /// Initialize a new Wayland backend, without monitoring the clients with an internal poll
+    ///
+    /// The sockets of the clients should then be monitored directly, using the file descriptors given by
+    /// [`Handle::get_client_fd()`], and dispatched individually with [`Backend::dispatch_client()`].
+    ///
+    /// # Backend specific
+    ///
+    /// libwayland always monitors the clients with its own event loop, so this is identical to
+    /// [`Backend::new()`] for the `sys` backend.
+    pub fn new_without_poll() -> Result<Self, InitError> {
+        Self::new()
+    }
+
     /// Initializes a connection to a client.
     ///
     /// The `data` parameter contains data that will be associated with the client.
@@ -439,26 +510,125 @@ impl<D> Backend<D> {
     ///
     /// If no client is specified, all pending events are flushed to all clients.
     pub fn flush(&mut self, client: Option<ClientId>) -> std::io::Result<()> {
-        if let Some(client_id) = client {
-            if client_id.alive.load(Ordering::Acquire) {
-                unsafe { ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_flush, client_id.ptr) }
+        let handle = &mut self.handle;
+        // wl_display_flush_clients and the disconnection of closing clients might invoke destructors
+        PENDING_DESTRUCTORS.set(&(&mut handle.pending_destructors as *mut _ as *mut _), || {
+            if let Some(client_id) = client {
+                if client_id.alive.load(Ordering::Acquire) {
+                    unsafe { ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_flush, client_id.ptr) }
+                }
+            } else {
+                unsafe {
+                    ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_display_flush_clients, handle.display);
+                }
             }
-        } else {
-            // wl_display_flush_clients might invoke destructors
-            PENDING_DESTRUCTORS.set(
-                &(&mut self.handle.pending_destructors as *mut _ as *mut _),
-                || unsafe {
-                    ffi_dispatch!(
-                        WAYLAND_SERVER_HANDLE,
-                        wl_display_flush_clients,
-                        self.handle.display
-                    );
-                },
-            );
-        }
+            handle.finish_closing_clients();
+        });
         Ok(())
     }
 
+    /// Gracefully disconnects all clients.
+    ///
+    /// A `wl_display.error` with the `implementation` code and given message is posted to all clients, and this
+    /// method then blocks until it has been sent to all of them, or until `timeout` has elapsed. Once this method
+    /// returns, all clients are disconnected with [`DisconnectReason::ServerKill`] and their objects are destroyed.
+    ///
+    /// Returns an error of kind `TimedOut` if some clients could not be flushed in time.
+    ///
+    /// # Backend specific
+    ///
+    /// libwayland does not expose the state of the outgoing buffer of a client, so the `sys` backend considers
+    /// a client flushed once its socket is still writable right after a flush.
+    pub fn shutdown(
+        &mut self,
+        data: &mut D,
+        message: CString,
+        timeout: Duration,
+    ) -> std::io::Result<()> {
+        let deadline = Instant::now() + timeout;
+        let clients = unsafe { client_list(self.handle.display) }
+            .into_iter()
+            .filter_map(|ptr| unsafe { client_id_from_ptr::<D>(ptr) })
+            .filter(|id| id.alive.load(Ordering::Acquire))
+            .collect::<Vec<_>>();
+
+        for client in &clients {
+            unsafe {
+                let display =
+                    ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_get_object, client.ptr, 1);
+                ffi_dispatch!(
+                    WAYLAND_SERVER_HANDLE,
+                    wl_resource_post_error,
+                    display,
+                    3, // wl_display.error.implementation
+                    b"%s\0".as_ptr() as *const c_char,
+                    message.as_ptr()
+                );
+            }
+        }
+
+        let mut pending = clients.clone();
+        let ret = loop {
+            let mut fds = pending
+                .iter()
+                .map(|client| unsafe {
+                    ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_flush, client.ptr);
+                    let fd = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_get_fd, client.ptr);
+                    nix::poll::PollFd::new(fd, nix::poll::PollFlags::POLLOUT)
+                })
+                .collect::<Vec<_>>();
+            // if the socket of a client is writable right after a flush, it no longer has pending data
+            if let Err(e) = nix::poll::poll(&mut fds, 0) {
+                if e != nix::errno::Errno::EINTR {
+                    break Err(e.into());
+                }
+            }
+            let mut ready = fds.iter().map(|fd| matches!(fd.revents(), Some(r) if !r.is_empty()));
+            pending.retain(|_| !ready.next().unwrap());
+            if pending.is_empty() {
+                break Ok(());
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break Err(std::io::ErrorKind::TimedOut.into());
+            }
+            let mut fds = fds
+                .into_iter()
+                .filter(|fd| !matches!(fd.revents(), Some(r) if !r.is_empty()))
+                .collect::<Vec<_>>();
+            let timeout = (deadline - now).as_millis().min(i32::MAX as u128) as i32;
+            match nix::poll::poll(&mut fds, timeout) {
+                Ok(_) | Err(nix::errno::Errno::EINTR) => {}
+                Err(e) => break Err(e.into()),
+            }
+        };
+
+        // wl_client_destroy may result in the destruction of some wayland objects
+        PENDING_DESTRUCTORS.set(
+            &(&mut self.handle.pending_destructors as *mut _ as *mut _),
+            || {
+                for client in clients {
+                    self.handle.kill_client(client, DisconnectReason::ServerKill);
+                }
+            },
+        );
+        for (object, client_id, object_id) in self.handle.pending_destructors.drain(..) {
+            object.destroyed(data, client_id, object_id);
+        }
+
+        ret
+    }
+
+    /// Sets whether panics occurring in request and bind callbacks should be caught.
+    ///
+    /// When enabled, a panic in the handler of a request only disconnects the client that sent it, with
+    /// a `wl_display.error` of code `implementation`, and the destructors of all its objects are invoked.
+    /// The panic is still reported by the panic hook, and the server-wide data may have been left in an
+    /// inconsistent state by the panicking callback. This is disabled by default.
+    pub fn set_catch_panics(&mut self, enabled: bool) {
+        self.handle.catch_panics = enabled;
+    }
+
     /// Returns a handle which represents the server side state of the backend.
     ///
     /// The handle provides a variety of functionality, such as querying information about wayland objects,
@@ -519,7 +689,9 @@ impl<D> Backend<D> {
         let pointers = (&mut self.handle as *mut _ as *mut c_void, data as *mut _ as *mut c_void);
         let ret = HANDLE.set(&pointers, || unsafe {
             let evl_ptr = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_display_get_event_loop, display);
-            ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_event_loop_dispatch, evl_ptr, 0)
+            let ret = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_event_loop_dispatch, evl_ptr, 0);
+            (*(pointers.0 as *mut Handle<D>)).finish_closing_clients();
+            ret
         });
 
         for (object, client_id, object_id) in self.handle.pending_destructors.drain(..) {
@@ -624,31 +796,24 @@ impl<D> Handle<D> {
         Ok(creds)
     }
 
-    /// Returns an iterator over all clients connected to the server.
-    pub fn all_clients<'a>(&'a self) -> Box<dyn Iterator<Item = ClientId> + 'a> {
-        let mut client_list = unsafe {
-            ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_display_get_client_list, self.display)
-        };
-        Box::new(std::iter::from_fn(move || {
-            if client_list.is_null() {
-                None
-            } else {
-                unsafe {
-                    let client =
-                        ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_from_link, client_list);
-                    let id = client_id_from_ptr::<D>(client);
+    /// Returns the file descriptor of the socket of a client
+    ///
+    /// It can be monitored for readability to know when [`Backend::dispatch_client()`] should be invoked
+    /// for this client. It should not be used for any other purpose.
+    pub fn get_client_fd(&self, id: ClientId) -> Result<RawFd, InvalidId> {
+        if !id.alive.load(Ordering::Acquire) {
+            return Err(InvalidId);
+        }
 
-                    let next = (*client_list).next;
-                    if client_list == next {
-                        client_list = std::ptr::null_mut();
-                    } else {
-                        client_list = next;
-                    }
+        Ok(unsafe { ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_get_fd, id.ptr) })
+    }
 
-                    id
-                }
-            }
-        }))
+    /// Returns an iterator over all clients connected to the server.
+    pub fn all_clients<'a>(&'a self) -> Box<dyn Iterator<Item = ClientId> + 'a> {
+        let clients = unsafe { client_list(self.display) };
+        Box::new(
+            clients.into_iter().filter_map(|client| unsafe { client_id_from_ptr::<D>(client) }),
+        )
     }
 
     /// Returns an iterator over all objects owned by a client.
@@ -749,6 +914,12 @@ impl<D> Handle<D> {
                 id.interface.name, id.id, message_desc.name, message_desc.signature, args
             );
         }
+        if message_desc.deprecated_since.is_some() {
+            let version = unsafe {
+                ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_get_version, id.ptr) as u32
+            };
+            warn_if_deprecated(id.interface, id.id, version, message_desc);
+        }
 
         let mut argument_list = SmallVec::<[wl_argument; 4]>::with_capacity(args.len());
         let mut arg_interfaces = message_desc.arg_interfaces.iter();
@@ -923,6 +1094,97 @@ impl<D> Handle<D> {
         }
     }
 
+    /// Kills the connection to a client once all its pending events have been sent.
+    ///
+    /// Contrary to [`kill_client()`](Handle::kill_client), the connection is kept open until the outgoing
+    /// buffer of the client has been flushed, and the requests it sends in the meantime are ignored. If the
+    /// reason is a protocol error, the matching `wl_display.error` is sent to the client beforehand.
+    ///
+    /// [`ClientData::disconnected()`] is invoked with the given reason once the client is actually disconnected,
+    /// during a later flush or dispatch. A client that does not read its pending events within one second is
+    /// disconnected anyway, so the server must keep flushing its clients for the disconnection to complete.
+    ///
+    /// # Backend specific
+    ///
+    /// libwayland does not expose the state of the outgoing buffer of a client, so the `sys` backend considers
+    /// a client flushed once its socket is still writable right after a flush.
+    pub fn kill_client_after_flush(&mut self, client_id: ClientId, reason: DisconnectReason) {
+        if !client_id.alive.load(Ordering::Acquire) {
+            return;
+        }
+        let udata = match unsafe { client_user_data::<D>(client_id.ptr) } {
+            Some(udata) => unsafe { &mut *udata },
+            None => return,
+        };
+        if udata.closing.is_some() {
+            return;
+        }
+        if let DisconnectReason::ProtocolError(ref error) = reason {
+            let message = CString::new(error.message.clone()).unwrap_or_default();
+            unsafe {
+                let mut resource = ffi_dispatch!(
+                    WAYLAND_SERVER_HANDLE,
+                    wl_client_get_object,
+                    client_id.ptr,
+                    error.object_id
+                );
+                if resource.is_null() {
+                    resource = ffi_dispatch!(
+                        WAYLAND_SERVER_HANDLE,
+                        wl_client_get_object,
+                        client_id.ptr,
+                        1
+                    );
+                }
+                ffi_dispatch!(
+                    WAYLAND_SERVER_HANDLE,
+                    wl_resource_post_error,
+                    resource,
+                    error.code,
+                    b"%s\0".as_ptr() as *const c_char,
+                    message.as_ptr()
+                );
+            }
+        }
+        udata.closing = Some(reason);
+        self.closing_clients.push((client_id.clone(), Instant::now() + CLOSING_TIMEOUT));
+        // the client is disconnected by a later flush or dispatch, as destroying it from a callback is not safe
+        unsafe {
+            ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_flush, client_id.ptr);
+        }
+    }
+
+    /// Disconnects the closing clients whose outgoing buffer has been flushed, or which took too long to read it
+    fn finish_closing_clients(&mut self) {
+        let now = Instant::now();
+        let mut finished = Vec::new();
+        self.closing_clients.retain(|(client_id, deadline)| {
+            if !client_id.alive.load(Ordering::Acquire) {
+                // the client was already destroyed, by libwayland or by kill_client()
+                return false;
+            }
+            // if the socket of the client is writable right after a flush, it no longer has pending data
+            let flushed = unsafe {
+                ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_flush, client_id.ptr);
+                let fd = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_get_fd, client_id.ptr);
+                let mut fds = [nix::poll::PollFd::new(fd, nix::poll::PollFlags::POLLOUT)];
+                !matches!(nix::poll::poll(&mut fds, 0), Ok(0))
+            };
+            if flushed || now >= *deadline {
+                finished.push(client_id.clone());
+                false
+            } else {
+                true
+            }
+        });
+        for client_id in finished {
+            let reason = unsafe { client_user_data::<D>(client_id.ptr) }
+                .and_then(|udata| unsafe { (*udata).closing.take() })
+                .unwrap_or(DisconnectReason::ConnectionClosed);
+            self.kill_client(client_id, reason);
+        }
+    }
+
     /// Creates a global of the specified interface and version and then advertises it to clients.
     ///
     /// The clients which the global is advertised to is determined by the implementation of the [`GlobalHandler`].
@@ -1049,7 +1311,8 @@ impl<D> Handle<D> {
 
 unsafe fn init_client<D>(client: *mut wl_client, data: Arc<dyn ClientData<D>>) -> ClientId {
     let alive = Arc::new(AtomicBool::new(true));
-    let client_data = Box::into_raw(Box::new(ClientUserData { alive: alive.clone(), data }));
+    let client_data =
+        Box::into_raw(Box::new(ClientUserData { alive: alive.clone(), data, closing: None }));
 
     let listener = signal::rust_listener_create(client_destroy_notify::<D>);
     signal::rust_listener_set_user_data(listener, client_data as *mut c_void);
@@ -1059,6 +1322,17 @@ unsafe fn init_client<D>(client: *mut wl_client, data: Arc<dyn ClientData<D>>) -
     ClientId { ptr: client, alive }
 }
 
+unsafe fn client_list(display: *mut wl_display) -> Vec<*mut wl_client> {
+    let head = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_display_get_client_list, display);
+    let mut clients = Vec::new();
+    let mut link = (*head).next;
+    while link != head {
+        clients.push(ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_from_link, link));
+        link = (*link).next;
+    }
+    clients
+}
+
 unsafe fn client_id_from_ptr<D>(client: *mut wl_client) -> Option<ClientId> {
     client_user_data::<D>(client)
         .map(|udata| ClientId { ptr: client, alive: (*udata).alive.clone() })
@@ -1090,7 +1364,7 @@ unsafe extern "C" fn client_destroy_notify<D>(listener: *mut wl_listener, client
         data.alive.store(false, Ordering::Release);
         data.data.disconnected(
             ClientId { ptr: client_ptr as *mut wl_client, alive: data.alive.clone() },
-            DisconnectReason::ConnectionClosed,
+            data.closing.unwrap_or(DisconnectReason::ConnectionClosed),
         );
     }
 }
@@ -1126,9 +1400,16 @@ unsafe extern "C" fn global_bind<D>(
             id
         );
         let (object_id, udata) = init_resource(resource, global_udata.interface, None);
-        let obj_data =
-            global_udata.handler.clone().bind(handle, data, client_id, global_id, object_id);
-        (*udata).data = obj_data;
+        let catch_panics = handle.catch_panics;
+        let bind =
+            || global_udata.handler.clone().bind(handle, data, client_id, global_id, object_id);
+        match catch_panic(catch_panics, bind) {
+            Ok(obj_data) => (*udata).data = obj_data,
+            Err(()) => post_panic_error(
+                client,
+                &format!("bind of {} version {}", global_udata.interface.name, version),
+            ),
+        }
     })
 }
 
@@ -1149,7 +1430,11 @@ unsafe extern "C" fn global_filter<D>(
 
     let global_id = GlobalId { ptr: global as *mut wl_global, alive: global_udata.alive.clone() };
 
-    global_udata.handler.can_view(client_id, &client_udata.data, global_id)
+    global_udata
+        .handler
+        .advertised_version(client_id, &client_udata.data, global_id, global_udata.version)
+        .map(|version| version > 0)
+        .unwrap_or(false)
 }
 
 unsafe fn init_resource<D>(
@@ -1190,6 +1475,10 @@ unsafe extern "C" fn resource_dispatcher<D>(
         as *mut ResourceUserData<D>;
     let udata = &mut *udata_ptr;
     let client = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_get_client, resource);
+    if matches!(client_user_data::<D>(client), Some(cudata) if (*cudata).closing.is_some()) {
+        // requests of a client being disconnected are no longer processed
+        return 0;
+    }
     let resource_id = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_get_id, resource);
     let version = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_get_version, resource);
     let interface = udata.interface;
@@ -1314,14 +1603,30 @@ unsafe extern "C" fn resource_dispatcher<D>(
     let ret = HANDLE.with(|&(handle_ptr, data_ptr)| {
         let handle = &mut *(handle_ptr as *mut Handle<D>);
         let data = &mut *(data_ptr as *mut D);
-        udata.data.clone().request(
-            handle,
-            data,
-            client_id.clone(),
-            Message { sender_id: object_id.clone(), opcode: opcode as u16, args: parsed_args },
-        )
+        let catch_panics = handle.catch_panics;
+        let request = || {
+            udata.data.clone().request(
+                handle,
+                data,
+                client_id.clone(),
+                Message { sender_id: object_id.clone(), opcode: opcode as u16, args: parsed_args },
+            )
+        };
+        catch_panic(catch_panics, request)
     });
 
+    let ret = match ret {
+        Ok(ret) => ret,
+        Err(()) => {
+            // the client will be destroyed by libwayland, along with all its resources
+            post_panic_error(
+                client,
+                &format!("request {}@{}.{}", udata.interface.name, resource_id, message_desc.name),
+            );
+            return 0;
+        }
+    };
+
     if message_desc.is_destructor {
         ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_destroy, resource);
     }
@@ -1342,6 +1647,27 @@ unsafe extern "C" fn resource_dispatcher<D>(
     0
 }
 
+fn catch_panic<T>(enabled: bool, f: impl FnOnce() -> T) -> Result<T, ()> {
+    if enabled {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).map_err(|_| ())
+    } else {
+        Ok(f())
+    }
+}
+
+unsafe fn post_panic_error(client: *mut wl_client, what: &str) {
+    let display = ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_client_get_object, client, 1);
+    let msg = CString::new(format!("Internal server error while handling {}.", what)).unwrap();
+    ffi_dispatch!(
+        WAYLAND_SERVER_HANDLE,
+        wl_resource_post_error,
+        display,
+        3, // wl_display.error.implementation
+        b"%s\0".as_ptr() as *const c_char,
+        msg.as_ptr()
+    );
+}
+
 unsafe extern "C" fn resource_destructor<D>(resource: *mut wl_resource) {
     let udata =
         Box::from_raw(ffi_dispatch!(WAYLAND_SERVER_HANDLE, wl_resource_get_user_data, resource)
@@ -63,7 +63,7 @@ macro_rules! impl_client_objectdata {
             ) -> Option<Arc<dyn $client_backend::ObjectData>> {
                 None
             }
-            fn destroyed(&self, _object_id: $client_backend::ObjectId) {
+            fn destroyed(self: Arc<Self>, _object_id: $client_backend::ObjectId) {
                 self.0.store(true, Ordering::Release);
             }
         }
@@ -97,7 +97,7 @@ macro_rules! clientdata_impls {
                 self.0.store(true, Ordering::SeqCst);
                 None
             }
-            fn destroyed(&self, _object_id: $client_backend::ObjectId) {}
+            fn destroyed(self: Arc<Self>, _object_id: $client_backend::ObjectId) {}
         }
     }
 }
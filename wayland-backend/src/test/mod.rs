@@ -57,6 +57,7 @@ mod interfaces {
 mod destructors;
 mod many_args;
 mod object_args;
+mod panics;
 mod protocol_error;
 mod server_created_objects;
 mod sync;
@@ -181,7 +182,7 @@ impl client_rs::ObjectData for DoNothingData {
         None
     }
 
-    fn destroyed(&self, _: client_rs::ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: client_rs::ObjectId) {}
 }
 
 impl client_sys::ObjectData for DoNothingData {
@@ -193,5 +194,5 @@ impl client_sys::ObjectData for DoNothingData {
         None
     }
 
-    fn destroyed(&self, _: client_sys::ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: client_sys::ObjectId) {}
 }
@@ -101,7 +101,7 @@ macro_rules! impl_client_objectdata {
                 self.0.store(true, Ordering::SeqCst);
                 None
             }
-            fn destroyed(&self, _object_id: $client_backend::ObjectId) {}
+            fn destroyed(self: Arc<Self>, _object_id: $client_backend::ObjectId) {}
         }
     };
 }
@@ -0,0 +1,123 @@
+use std::{
+    ffi::CString,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use super::*;
+
+struct PanickingData(AtomicUsize);
+
+macro_rules! impl_server_objectdata {
+    ($server_backend:tt) => {
+        impl $server_backend::ObjectData<()> for PanickingData {
+            fn request(
+                self: Arc<Self>,
+                _: &mut $server_backend::Handle<()>,
+                _: &mut (),
+                _: $server_backend::ClientId,
+                _: Message<$server_backend::ObjectId>,
+            ) -> Option<Arc<dyn $server_backend::ObjectData<()>>> {
+                panic!("Oops!");
+            }
+
+            fn destroyed(
+                &self,
+                _: &mut (),
+                _: $server_backend::ClientId,
+                _: $server_backend::ObjectId,
+            ) {
+                self.0.fetch_add(1, Ordering::AcqRel);
+            }
+        }
+
+        impl $server_backend::GlobalHandler<()> for PanickingData {
+            fn bind(
+                self: Arc<Self>,
+                _: &mut $server_backend::Handle<()>,
+                _: &mut (),
+                _: $server_backend::ClientId,
+                _: $server_backend::GlobalId,
+                _: $server_backend::ObjectId,
+            ) -> Arc<dyn $server_backend::ObjectData<()>> {
+                self
+            }
+        }
+    };
+}
+
+impl_server_objectdata!(server_rs);
+impl_server_objectdata!(server_sys);
+
+expand_test!(panic_in_request, {
+    let (tx, rx) = std::os::unix::net::UnixStream::pair().unwrap();
+    let mut server = server_backend::Backend::new().unwrap();
+    server.set_catch_panics(true);
+    let _client_id = server.insert_client(rx, Arc::new(DoNothingData)).unwrap();
+    let mut client = client_backend::Backend::connect(tx).unwrap();
+
+    let server_data = Arc::new(PanickingData(AtomicUsize::new(0)));
+
+    server.handle().create_global(&interfaces::TEST_GLOBAL_INTERFACE, 3, server_data.clone());
+
+    // get the registry client-side
+    let client_display = client.handle().display_id();
+    let placeholder = client.handle().placeholder_id(Some((&interfaces::WL_REGISTRY_INTERFACE, 1)));
+    let registry_id = client
+        .handle()
+        .send_request(
+            message!(client_display, 1, [Argument::NewId(placeholder)],),
+            Some(Arc::new(DoNothingData)),
+        )
+        .unwrap();
+    // create the test global
+    let placeholder = client.handle().placeholder_id(Some((&interfaces::TEST_GLOBAL_INTERFACE, 3)));
+    let test_global_id = client
+        .handle()
+        .send_request(
+            message!(
+                registry_id,
+                0,
+                [
+                    Argument::Uint(1),
+                    Argument::Str(Box::new(
+                        CString::new(interfaces::TEST_GLOBAL_INTERFACE.name.as_bytes()).unwrap(),
+                    )),
+                    Argument::Uint(3),
+                    Argument::NewId(placeholder),
+                ],
+            ),
+            Some(Arc::new(DoNothingData)),
+        )
+        .unwrap();
+
+    // create a secondary, the server handler of this request panics
+    let placeholder = client.handle().placeholder_id(Some((&interfaces::SECONDARY_INTERFACE, 3)));
+    client
+        .handle()
+        .send_request(
+            message!(test_global_id, 1, [Argument::NewId(placeholder)],),
+            Some(Arc::new(DoNothingData)),
+        )
+        .unwrap();
+
+    client.flush().unwrap();
+
+    // the panic must not reach us
+    server.dispatch_all_clients(&mut ()).unwrap();
+    server.flush(None).unwrap();
+
+    // the object of the killed client has been destroyed exactly once
+    assert_eq!(server_data.0.load(Ordering::Acquire), 1);
+
+    match client.dispatch_events() {
+        Err(client_backend::WaylandError::Protocol(err)) => {
+            assert_eq!(err.code, 3);
+            assert_eq!(err.object_id, 1);
+            assert_eq!(err.object_interface, "wl_display");
+        }
+        e => panic!("Invalid result: {:?}", e),
+    }
+});
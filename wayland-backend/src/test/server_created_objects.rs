@@ -95,7 +95,7 @@ macro_rules! impl_client_objectdata {
                 }
                 Some(self)
             }
-            fn destroyed(&self, _object_id: $client_backend::ObjectId) {}
+            fn destroyed(self: Arc<Self>, _object_id: $client_backend::ObjectId) {}
         }
     };
 }
@@ -53,6 +53,9 @@ impl std::fmt::Display for InvalidId {
     }
 }
 
+/// How long a client disconnected after a flush is given to read its pending events
+pub(crate) const CLOSING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// Describes why a client has been disconnected from the server.
 #[derive(Debug)]
 pub enum DisconnectReason {
@@ -60,6 +63,47 @@ pub enum DisconnectReason {
     ConnectionClosed,
     /// The server has sent the client a protocol error, terminating the connection.
     ProtocolError(crate::protocol::ProtocolError),
+    /// The server has terminated the connection, for example because it is shutting down.
+    ServerKill,
+    /// The connection was terminated because of an I/O error on the socket.
+    Io(std::io::Error),
+}
+
+impl DisconnectReason {
+    /// Builds the reason for an I/O error on a client socket.
+    ///
+    /// Errors meaning that the other end of the socket was closed map to
+    /// [`DisconnectReason::ConnectionClosed`].
+    pub(crate) fn from_io_error(err: std::io::Error) -> DisconnectReason {
+        match err.raw_os_error().map(nix::errno::Errno::from_i32) {
+            Some(nix::errno::Errno::EPIPE) | Some(nix::errno::Errno::ECONNRESET) => {
+                DisconnectReason::ConnectionClosed
+            }
+            _ => DisconnectReason::Io(err),
+        }
+    }
+}
+
+impl std::error::Error for DisconnectReason {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DisconnectReason::ProtocolError(ref err) => Some(err),
+            DisconnectReason::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+impl std::fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+        match self {
+            DisconnectReason::ConnectionClosed => f.write_str("connection closed"),
+            DisconnectReason::ProtocolError(ref err) => std::fmt::Display::fmt(err, f),
+            DisconnectReason::ServerKill => f.write_str("connection terminated by the server"),
+            DisconnectReason::Io(ref err) => write!(f, "I/O error: {}", err),
+        }
+    }
 }
 
 /// Holds the client credentials
@@ -128,11 +128,20 @@ mod server {
             Sync,
             PartialEq,
             Eq,
+            std::hash::Hash,
             Clone
         );
 
         // ClientId
-        assert_impl!(server::ClientId: std::fmt::Debug, Clone, Send, Sync, PartialEq, Eq);
+        assert_impl!(
+            server::ClientId: std::fmt::Debug,
+            Clone,
+            Send,
+            Sync,
+            PartialEq,
+            Eq,
+            std::hash::Hash
+        );
 
         // GlobalId
         assert_impl!(server::GlobalId: std::fmt::Debug, Clone, Send, Sync, PartialEq, Eq);
@@ -161,7 +170,8 @@ mod client {
             Send,
             Sync,
             PartialEq,
-            Eq
+            Eq,
+            std::hash::Hash
         );
 
         // Handle
@@ -2,14 +2,12 @@ use wayland_client::{protocol::wl_registry, Connection, ConnectionHandle, Dispat
 
 struct AppData;
 
-impl Dispatch<wl_registry::WlRegistry> for AppData {
-    type UserData = ();
-
+impl Dispatch<wl_registry::WlRegistry, ()> for AppData {
     fn event(
         &mut self,
         _: &wl_registry::WlRegistry,
         event: wl_registry::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<AppData>,
     ) {
@@ -27,7 +25,7 @@ fn main() {
     let mut event_queue = conn.new_event_queue();
     let qh = event_queue.handle();
 
-    let _registry = display.get_registry(&mut conn.handle(), &qh, ()).unwrap();
+    let _registry = display.get_registry(&qh, ()).unwrap();
 
     println!("Advertized globals:");
 
@@ -17,7 +17,7 @@ fn main() {
     let qhandle = event_queue.handle();
 
     let display = conn.handle().display();
-    display.get_registry(&mut conn.handle(), &qhandle, ()).unwrap();
+    display.get_registry(&qhandle, ()).unwrap();
 
     let mut state = State {
         running: true,
@@ -44,43 +44,40 @@ struct State {
     configured: bool,
 }
 
-impl Dispatch<wl_registry::WlRegistry> for State {
-    type UserData = ();
-
+impl Dispatch<wl_registry::WlRegistry, ()> for State {
     fn event(
         &mut self,
         registry: &wl_registry::WlRegistry,
         event: wl_registry::Event,
         _: &(),
-        conn: &mut ConnectionHandle,
+        _: &mut ConnectionHandle,
         qh: &QueueHandle<Self>,
     ) {
         if let wl_registry::Event::Global { name, interface, .. } = event {
             match &interface[..] {
                 "wl_compositor" => {
                     let compositor = registry
-                        .bind::<wl_compositor::WlCompositor, _>(conn, name, 1, qh, ())
+                        .bind::<wl_compositor::WlCompositor, _, _>(name, 1, qh, ())
                         .unwrap();
-                    let surface = compositor.create_surface(conn, qh, ()).unwrap();
+                    let surface = compositor.create_surface(qh, ()).unwrap();
                     self.base_surface = Some(surface);
 
                     if self.wm_base.is_some() && self.xdg_surface.is_none() {
-                        self.init_xdg_surface(conn, qh);
+                        self.init_xdg_surface(qh);
                     }
                 }
                 "wl_shm" => {
-                    let shm = registry.bind::<wl_shm::WlShm, _>(conn, name, 1, qh, ()).unwrap();
+                    let shm = registry.bind::<wl_shm::WlShm, _, _>(name, 1, qh, ()).unwrap();
 
                     let (init_w, init_h) = (320, 240);
 
                     let mut file = tempfile::tempfile().unwrap();
                     draw(&mut file, (init_w, init_h));
                     let pool = shm
-                        .create_pool(conn, file.as_raw_fd(), (init_w * init_h * 4) as i32, qh, ())
+                        .create_pool(file.as_raw_fd(), (init_w * init_h * 4) as i32, qh, ())
                         .unwrap();
                     let buffer = pool
                         .create_buffer(
-                            conn,
                             0,
                             init_w as i32,
                             init_h as i32,
@@ -94,20 +91,20 @@ impl Dispatch<wl_registry::WlRegistry> for State {
 
                     if self.configured {
                         let surface = self.base_surface.as_ref().unwrap();
-                        surface.attach(conn, Some(&buffer), 0, 0);
-                        surface.commit(conn);
+                        surface.attach(Some(&buffer), 0, 0).unwrap();
+                        surface.commit().unwrap();
                     }
                 }
                 "wl_seat" => {
-                    registry.bind::<wl_seat::WlSeat, _>(conn, name, 1, qh, ()).unwrap();
+                    registry.bind::<wl_seat::WlSeat, _, _>(name, 1, qh, ()).unwrap();
                 }
                 "xdg_wm_base" => {
                     let wm_base =
-                        registry.bind::<xdg_wm_base::XdgWmBase, _>(conn, name, 1, qh, ()).unwrap();
+                        registry.bind::<xdg_wm_base::XdgWmBase, _, _>(name, 1, qh, ()).unwrap();
                     self.wm_base = Some(wm_base);
 
                     if self.base_surface.is_some() && self.xdg_surface.is_none() {
-                        self.init_xdg_surface(conn, qh);
+                        self.init_xdg_surface(qh);
                     }
                 }
                 _ => {}
@@ -116,14 +113,12 @@ impl Dispatch<wl_registry::WlRegistry> for State {
     }
 }
 
-impl Dispatch<wl_compositor::WlCompositor> for State {
-    type UserData = ();
-
+impl Dispatch<wl_compositor::WlCompositor, ()> for State {
     fn event(
         &mut self,
         _: &wl_compositor::WlCompositor,
         _: wl_compositor::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
@@ -131,14 +126,12 @@ impl Dispatch<wl_compositor::WlCompositor> for State {
     }
 }
 
-impl Dispatch<wl_surface::WlSurface> for State {
-    type UserData = ();
-
+impl Dispatch<wl_surface::WlSurface, ()> for State {
     fn event(
         &mut self,
         _: &wl_surface::WlSurface,
         _: wl_surface::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
@@ -146,14 +139,12 @@ impl Dispatch<wl_surface::WlSurface> for State {
     }
 }
 
-impl Dispatch<wl_shm::WlShm> for State {
-    type UserData = ();
-
+impl Dispatch<wl_shm::WlShm, ()> for State {
     fn event(
         &mut self,
         _: &wl_shm::WlShm,
         _: wl_shm::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
@@ -161,14 +152,12 @@ impl Dispatch<wl_shm::WlShm> for State {
     }
 }
 
-impl Dispatch<wl_shm_pool::WlShmPool> for State {
-    type UserData = ();
-
+impl Dispatch<wl_shm_pool::WlShmPool, ()> for State {
     fn event(
         &mut self,
         _: &wl_shm_pool::WlShmPool,
         _: wl_shm_pool::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
@@ -176,14 +165,12 @@ impl Dispatch<wl_shm_pool::WlShmPool> for State {
     }
 }
 
-impl Dispatch<wl_buffer::WlBuffer> for State {
-    type UserData = ();
-
+impl Dispatch<wl_buffer::WlBuffer, ()> for State {
     fn event(
         &mut self,
         _: &wl_buffer::WlBuffer,
         _: wl_buffer::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
@@ -209,63 +196,57 @@ fn draw(tmp: &mut File, (buf_x, buf_y): (u32, u32)) {
 }
 
 impl State {
-    fn init_xdg_surface(&mut self, conn: &mut ConnectionHandle<'_>, qh: &QueueHandle<State>) {
+    fn init_xdg_surface(&mut self, qh: &QueueHandle<State>) {
         let wm_base = self.wm_base.as_ref().unwrap();
         let base_surface = self.base_surface.as_ref().unwrap();
 
-        let xdg_surface = wm_base.get_xdg_surface(conn, base_surface, qh, ()).unwrap();
-        let toplevel = xdg_surface.get_toplevel(conn, qh, ()).unwrap();
-        toplevel.set_title(conn, "A fantastic window!".into());
+        let xdg_surface = wm_base.get_xdg_surface(base_surface, qh, ()).unwrap();
+        let toplevel = xdg_surface.get_toplevel(qh, ()).unwrap();
+        toplevel.set_title("A fantastic window!".into()).unwrap();
 
-        base_surface.commit(conn);
+        base_surface.commit().unwrap();
 
         self.xdg_surface = Some((xdg_surface, toplevel));
     }
 }
 
-impl Dispatch<xdg_wm_base::XdgWmBase> for State {
-    type UserData = ();
-
+impl Dispatch<xdg_wm_base::XdgWmBase, ()> for State {
     fn event(
         &mut self,
         wm_base: &xdg_wm_base::XdgWmBase,
         event: xdg_wm_base::Event,
         _: &(),
-        conn: &mut ConnectionHandle,
+        _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
         if let xdg_wm_base::Event::Ping { serial } = event {
-            wm_base.pong(conn, serial);
+            wm_base.pong(serial).unwrap();
         }
     }
 }
 
-impl Dispatch<xdg_surface::XdgSurface> for State {
-    type UserData = ();
-
+impl Dispatch<xdg_surface::XdgSurface, ()> for State {
     fn event(
         &mut self,
         xdg_surface: &xdg_surface::XdgSurface,
         event: xdg_surface::Event,
         _: &(),
-        conn: &mut ConnectionHandle,
+        _: &mut ConnectionHandle,
         _: &QueueHandle<Self>,
     ) {
         if let xdg_surface::Event::Configure { serial, .. } = event {
-            xdg_surface.ack_configure(conn, serial);
+            xdg_surface.ack_configure(serial).unwrap();
             self.configured = true;
             let surface = self.base_surface.as_ref().unwrap();
             if let Some(ref buffer) = self.buffer {
-                surface.attach(conn, Some(buffer), 0, 0);
-                surface.commit(conn);
+                surface.attach(Some(buffer), 0, 0).unwrap();
+                surface.commit().unwrap();
             }
         }
     }
 }
 
-impl Dispatch<xdg_toplevel::XdgToplevel> for State {
-    type UserData = ();
-
+impl Dispatch<xdg_toplevel::XdgToplevel, ()> for State {
     fn event(
         &mut self,
         _: &xdg_toplevel::XdgToplevel,
@@ -280,28 +261,24 @@ impl Dispatch<xdg_toplevel::XdgToplevel> for State {
     }
 }
 
-impl Dispatch<wl_seat::WlSeat> for State {
-    type UserData = ();
-
+impl Dispatch<wl_seat::WlSeat, ()> for State {
     fn event(
         &mut self,
         seat: &wl_seat::WlSeat,
         event: wl_seat::Event,
         _: &(),
-        conn: &mut ConnectionHandle,
+        _: &mut ConnectionHandle,
         qh: &QueueHandle<Self>,
     ) {
         if let wl_seat::Event::Capabilities { capabilities: WEnum::Value(capabilities) } = event {
             if capabilities.contains(wl_seat::Capability::Keyboard) {
-                seat.get_keyboard(conn, qh, ()).unwrap();
+                seat.get_keyboard(qh, ()).unwrap();
             }
         }
     }
 }
 
-impl Dispatch<wl_keyboard::WlKeyboard> for State {
-    type UserData = ();
-
+impl Dispatch<wl_keyboard::WlKeyboard, ()> for State {
     fn event(
         &mut self,
         _: &wl_keyboard::WlKeyboard,
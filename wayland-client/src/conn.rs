@@ -1,38 +1,55 @@
 use std::{
     env,
+    future::Future,
     io::ErrorKind,
+    os::unix::io::RawFd,
     os::unix::net::UnixStream,
     os::unix::prelude::FromRawFd,
     path::PathBuf,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, Mutex, MutexGuard,
+        Arc, Mutex,
     },
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
 };
 
 use wayland_backend::{
-    client::{Backend, Handle, InvalidId, ObjectData, ObjectId, ReadEventsGuard, WaylandError},
+    client::{
+        Backend, Handle, InvalidId, ObjectData, ObjectId, ReadEventsGuard, WaylandError,
+        WeakBackend,
+    },
     protocol::{Interface, ObjectInfo, ProtocolError},
 };
 
 use nix::{fcntl, Error};
 
-use crate::{EventQueue, Proxy};
+use crate::{DispatchError, EventQueue, InterfaceError, Proxy, RequestError};
 
 /// The Wayland connection
 ///
 /// This is the main type representing your connection to the Wayland server. Most operations require
 /// access to either this type or the [`ConnectionHandle`], which can be accessed through the
 /// [`handle()`](Connection::handle) method, and is given to you in most callbacks.
+///
+/// The connection can be cloned and shared between threads. Sending requests, reading events and dispatching
+/// them are synchronized independently, so that threads sending requests do not wait for the one reading
+/// events.
 #[derive(Debug, Clone)]
 pub struct Connection {
-    backend: Arc<Mutex<Backend>>,
+    backend: Backend,
 }
 
 impl Connection {
     /// Access the connection handle
     pub fn handle(&self) -> ConnectionHandle {
-        ConnectionHandle { inner: HandleInner::Guard(self.backend.lock().unwrap()) }
+        ConnectionHandle::new(&self.backend)
+    }
+
+    /// Create a weak reference to this connection
+    pub fn downgrade(&self) -> WeakConnection {
+        WeakConnection { backend: self.backend.downgrade() }
     }
 
     /// Try to connect to the Wayland server following the environment
@@ -70,22 +87,22 @@ impl Connection {
         };
 
         let backend = Backend::connect(stream).map_err(|_| ConnectError::NoWaylandLib)?;
-        Ok(Connection { backend: Arc::new(Mutex::new(backend)) })
+        Ok(Connection { backend })
     }
 
     /// Initialize a Wayland connection from an already existing Unix stream
     pub fn from_socket(stream: UnixStream) -> Result<Connection, ConnectError> {
         let backend = Backend::connect(stream).map_err(|_| ConnectError::NoWaylandLib)?;
-        Ok(Connection { backend: Arc::new(Mutex::new(backend)) })
+        Ok(Connection { backend })
     }
 
     /// Wrap an existing [`Backend`] into a Connection
-    pub fn from_backend(backend: Arc<Mutex<Backend>>) -> Connection {
+    pub fn from_backend(backend: Backend) -> Connection {
         Connection { backend }
     }
 
     /// Get the [`Backend`] underlying this Connection
-    pub fn backend(&self) -> Arc<Mutex<Backend>> {
+    pub fn backend(&self) -> Backend {
         self.backend.clone()
     }
 
@@ -93,7 +110,7 @@ impl Connection {
     ///
     /// This needs to be done regularly to ensure the server receives all your requests.
     pub fn flush(&self) -> Result<(), WaylandError> {
-        self.backend.lock().unwrap().flush()
+        self.backend.flush()
     }
 
     /// Start a synchronized read from the socket
@@ -117,7 +134,16 @@ impl Connection {
     /// their respective event queues. Alternatively,
     /// [`EventQueue::blocking_dispatch()`](EventQueue::blocking_dispatch) does both.
     pub fn blocking_dispatch(&self) -> Result<usize, WaylandError> {
-        blocking_dispatch_impl(self.backend.clone())
+        blocking_dispatch_impl(self.backend.clone(), None).map(|n| n.unwrap_or(0))
+    }
+
+    /// Block until events are received from the server, with a timeout
+    ///
+    /// This is similar to [`blocking_dispatch()`](Connection::blocking_dispatch), but returns
+    /// [`DispatchError::Timeout`] if no event was received before the timeout expired.
+    pub fn blocking_dispatch_timeout(&self, timeout: Duration) -> Result<usize, DispatchError> {
+        blocking_dispatch_impl(self.backend.clone(), Some(Instant::now() + timeout))?
+            .ok_or(DispatchError::Timeout)
     }
 
     /// Do a roundtrip to the server
@@ -126,29 +152,58 @@ impl Connection {
     /// preceding requests. This is notably useful during the initial setup of an app, to wait for
     /// the initial state from the server.
     pub fn roundtrip(&self) -> Result<usize, WaylandError> {
-        let done = Arc::new(AtomicBool::new(false));
-        {
-            let mut backend = self.backend.lock().unwrap();
-            let mut handle = ConnectionHandle::from_handle(backend.handle());
-            let display = handle.display();
-            let cb_done = done.clone();
-            let sync_data = Arc::new(SyncData { done: cb_done });
-            handle
-                .send_request(
-                    &display,
-                    crate::protocol::wl_display::Request::Sync {},
-                    Some(sync_data),
-                )
-                .map_err(|_| WaylandError::Io(Error::EPIPE.into()))?;
-        }
+        self.roundtrip_impl(None).map(|n| n.unwrap_or(0))
+    }
+
+    /// Do a roundtrip to the server, with a timeout
+    ///
+    /// This is similar to [`roundtrip()`](Connection::roundtrip), but returns
+    /// [`DispatchError::Timeout`] if the server did not answer before the timeout expired.
+    pub fn roundtrip_timeout(&self, timeout: Duration) -> Result<usize, DispatchError> {
+        self.roundtrip_impl(Some(Instant::now() + timeout))?.ok_or(DispatchError::Timeout)
+    }
+
+    fn roundtrip_impl(&self, deadline: Option<Instant>) -> Result<Option<usize>, WaylandError> {
+        let sync = self.sync()?;
 
         let mut dispatched = 0;
 
-        while !done.load(Ordering::Acquire) {
-            dispatched += blocking_dispatch_impl(self.backend.clone())?;
+        while !sync.is_done() {
+            match blocking_dispatch_impl(self.backend.clone(), deadline)? {
+                Some(n) => dispatched += n,
+                None => return Ok(None),
+            }
         }
 
-        Ok(dispatched)
+        Ok(Some(dispatched))
+    }
+
+    /// Send a `wl_display.sync` request without waiting for its answer
+    ///
+    /// The returned [`SyncHandle`] completes once the server has processed and answered all your preceding
+    /// requests. It does not read the socket itself, so this only happens when events are read, for example
+    /// by [`blocking_dispatch()`](Connection::blocking_dispatch) or by your event loop. It can be checked
+    /// with [`SyncHandle::is_done()`], or awaited as it implements [`Future`], resolving to an error if the
+    /// connection dies before the answer.
+    ///
+    /// If the connection is already dead, the error that killed it is returned instead.
+    pub fn sync(&self) -> Result<SyncHandle, WaylandError> {
+        let last_error = || self.backend.clone().handle().last_error();
+        // the request would never be answered
+        if let Some(err) = last_error() {
+            return Err(err);
+        }
+        let state = Arc::new(SyncState::default());
+        let mut handle = ConnectionHandle::new(&self.backend);
+        let display = handle.display();
+        handle
+            .send_request(
+                &display,
+                crate::protocol::wl_display::Request::Sync {},
+                Some(Arc::new(SyncData { state: state.clone() })),
+            )
+            .map_err(|_| last_error().unwrap_or_else(|| WaylandError::Io(Error::EPIPE.into())))?;
+        Ok(SyncHandle { state, backend: self.backend.clone() })
     }
 
     /// Create a new event queue
@@ -158,67 +213,115 @@ impl Connection {
 
     /// Retrive the protocol error that occured on the socket (if any)
     pub fn protocol_error(&self) -> Option<ProtocolError> {
-        match dbg!(self.backend.lock().unwrap().handle().last_error())? {
+        match self.backend.clone().handle().last_error()? {
             WaylandError::Protocol(err) => Some(err),
             WaylandError::Io(_) => None,
         }
     }
+
+    /// Retrieve the protocol error that occured on the socket (if any) as the `Error` enum of its interface
+    ///
+    /// Returns `None` if no protocol error occured, or if it was raised by an object of an other interface
+    /// than the one of `E`. The protocol violations detected by the client itself are reported as errors
+    /// of the `wl_display` interface.
+    pub fn protocol_error_as<E: InterfaceError>(&self) -> Option<E> {
+        let err = self.protocol_error()?;
+        let interface =
+            if err.message_name.is_some() { "wl_display" } else { err.object_interface.as_str() };
+        if E::interface().name == interface {
+            E::from_code(err.code)
+        } else {
+            None
+        }
+    }
 }
 
-pub(crate) fn blocking_dispatch_impl(backend: Arc<Mutex<Backend>>) -> Result<usize, WaylandError> {
-    backend.lock().unwrap().flush()?;
+/// Read events from the socket, returns `None` if the deadline expired before any event was received
+pub(crate) fn blocking_dispatch_impl(
+    backend: Backend,
+    deadline: Option<Instant>,
+) -> Result<Option<usize>, WaylandError> {
+    backend.flush()?;
 
     // first, prepare the read
     let guard = ReadEventsGuard::try_new(backend)?;
 
     // there is nothing to dispatch, wait for readiness
-    loop {
-        let mut fds = [nix::poll::PollFd::new(
-            guard.connection_fd(),
-            nix::poll::PollFlags::POLLIN | nix::poll::PollFlags::POLLERR,
-        )];
-        match nix::poll::poll(&mut fds, -1) {
-            Ok(_) => break,
-            Err(nix::errno::Errno::EINTR) => continue,
-            Err(e) => return Err(WaylandError::Io(e.into())),
-        }
+    if !wait_readable(guard.connection_fd(), deadline).map_err(WaylandError::Io)? {
+        // dropping the guard cancels the read
+        return Ok(None);
     }
 
     // at this point the fd is ready
     match guard.read() {
-        Ok(n) => Ok(n),
+        Ok(n) => Ok(Some(n)),
         // if we are still "wouldblock", that means that there was a dispatch from an other
         // thread with the C-based backend, spuriously return 0.
-        Err(WaylandError::Io(e)) if e.kind() == ErrorKind::WouldBlock => Ok(0),
+        Err(WaylandError::Io(e)) if e.kind() == ErrorKind::WouldBlock => Ok(Some(0)),
         Err(e) => Err(e),
     }
 }
 
-/// A handle to the Wayland connection
-#[derive(Debug)]
-pub struct ConnectionHandle<'a> {
-    pub(crate) inner: HandleInner<'a>,
+/// Wait for a fd to be readable, returns `false` if the deadline expired first
+pub(crate) fn wait_readable(fd: RawFd, deadline: Option<Instant>) -> std::io::Result<bool> {
+    loop {
+        let timeout = match deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                // round up, to not wake up right before the deadline
+                let millis = (remaining + Duration::from_nanos(999_999)).as_millis();
+                millis.min(i32::MAX as u128) as i32
+            }
+            None => -1,
+        };
+        let mut fds = [nix::poll::PollFd::new(
+            fd,
+            nix::poll::PollFlags::POLLIN | nix::poll::PollFlags::POLLERR,
+        )];
+        match nix::poll::poll(&mut fds, timeout) {
+            Ok(0) if deadline.is_some() => return Ok(false),
+            Ok(_) => return Ok(true),
+            Err(nix::errno::Errno::EINTR) => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
 }
 
-#[derive(Debug)]
-pub(crate) enum HandleInner<'a> {
-    Handle(&'a mut Handle),
-    Guard(MutexGuard<'a, Backend>),
+/// A weak reference to a Wayland connection
+///
+/// This is notably held by proxies, allowing them to send requests without a [`ConnectionHandle`], see
+/// [`Proxy::send_request()`](crate::Proxy::send_request).
+#[derive(Debug, Clone, Default)]
+pub struct WeakConnection {
+    backend: WeakBackend,
 }
 
-impl<'a> HandleInner<'a> {
-    #[inline]
-    pub(crate) fn handle(&mut self) -> &mut Handle {
-        match self {
-            HandleInner::Handle(handle) => handle,
-            HandleInner::Guard(guard) => guard.handle(),
-        }
+impl WeakConnection {
+    /// Try to access the connection
+    ///
+    /// Returns `None` if the connection has been dropped.
+    pub fn upgrade(&self) -> Option<Connection> {
+        self.backend.upgrade().map(|backend| Connection { backend })
     }
 }
 
-impl<'a> ConnectionHandle<'a> {
-    pub(crate) fn from_handle(handle: &'a mut Handle) -> ConnectionHandle<'a> {
-        ConnectionHandle { inner: HandleInner::Handle(handle) }
+/// A handle to the Wayland connection
+///
+/// It does not lock the connection: several handles to the same connection can coexist, on the same or on
+/// different threads.
+#[derive(Debug)]
+pub struct ConnectionHandle {
+    pub(crate) inner: Backend,
+}
+
+impl ConnectionHandle {
+    pub(crate) fn new(backend: &Backend) -> ConnectionHandle {
+        ConnectionHandle { inner: backend.clone() }
+    }
+
+    /// Create a weak reference to this connection
+    pub fn downgrade(&self) -> WeakConnection {
+        WeakConnection { backend: self.inner.downgrade() }
     }
 
     /// Get the `WlDisplay` associated with this connection
@@ -236,9 +339,9 @@ impl<'a> ConnectionHandle<'a> {
         proxy: &I,
         request: I::Request,
         data: Option<Arc<dyn ObjectData>>,
-    ) -> Result<ObjectId, InvalidId> {
+    ) -> Result<ObjectId, RequestError> {
         let msg = proxy.write_request(self, request)?;
-        self.inner.handle().send_request(msg, data)
+        Ok(self.inner.handle().send_request(msg, data)?)
     }
 
     /// Create a placeholder id for request serialization
@@ -287,12 +390,51 @@ pub enum ConnectError {
     InvalidFd,
 }
 
+/// A pending `wl_display.sync` request
+///
+/// Created by [`Connection::sync()`], it completes once the server has answered the request. As a [`Future`],
+/// it is woken up by the thread reading the events, and resolves to the error of the connection if it dies
+/// before the answer. It keeps the connection alive until it is dropped.
+#[derive(Debug, Clone)]
+pub struct SyncHandle {
+    state: Arc<SyncState>,
+    backend: Backend,
+}
+
+impl SyncHandle {
+    /// Check whether the server has answered the request
+    pub fn is_done(&self) -> bool {
+        self.state.done.load(Ordering::Acquire)
+    }
+}
+
+impl Future for SyncHandle {
+    type Output = Result<(), WaylandError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), WaylandError>> {
+        let this = self.get_mut();
+        // register the waker before checking, so that an answer in between is not missed
+        *this.state.waker.lock().unwrap() = Some(cx.waker().clone());
+        if this.is_done() {
+            return Poll::Ready(Ok(()));
+        }
+        // the answer will never come if the connection is dead
+        this.backend.handle().poll_error(cx).map(Err)
+    }
+}
+
+#[derive(Debug, Default)]
+struct SyncState {
+    done: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
 /*
     wl_callback object data for wl_display.sync
 */
 
 struct SyncData {
-    done: Arc<AtomicBool>,
+    state: Arc<SyncState>,
 }
 
 impl ObjectData for SyncData {
@@ -301,9 +443,12 @@ impl ObjectData for SyncData {
         _handle: &mut Handle,
         _msg: wayland_backend::protocol::Message<ObjectId>,
     ) -> Option<Arc<dyn ObjectData>> {
-        self.done.store(true, Ordering::Release);
+        self.state.done.store(true, Ordering::Release);
+        if let Some(waker) = self.state.waker.lock().unwrap().take() {
+            waker.wake();
+        }
         None
     }
 
-    fn destroyed(&self, _: ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: ObjectId) {}
 }
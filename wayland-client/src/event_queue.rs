@@ -1,4 +1,9 @@
-use std::sync::{Arc, Mutex};
+use std::os::unix::io::RawFd;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+use std::time::{Duration, Instant};
 
 use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use wayland_backend::{
@@ -10,10 +15,12 @@ use crate::{ConnectionHandle, DispatchError, Proxy};
 
 /// A trait which provides an implementation for handling events from the server on a proxy with some type of
 /// associated user data.
-pub trait Dispatch<I: Proxy>: Sized {
-    /// The user data associated with the type of proxy.
-    type UserData: Send + Sync + 'static;
-
+///
+/// The handler is selected by both the interface `I` of the proxy and the type `U` of the user data it
+/// was created with, so a single state can handle for example some `wl_surface`s carrying window data and
+/// others carrying cursor data, by implementing both `Dispatch<WlSurface, WindowData>` and
+/// `Dispatch<WlSurface, CursorData>`.
+pub trait Dispatch<I: Proxy, U>: Sized {
     /// Called when an event from the server is processed.
     ///
     /// The implementation of this function may vary depending on protocol requirements. Typically the client
@@ -22,90 +29,77 @@ pub trait Dispatch<I: Proxy>: Sized {
         &mut self,
         proxy: &I,
         event: I::Event,
-        data: &Self::UserData,
+        data: &U,
         connhandle: &mut ConnectionHandle,
         qhandle: &QueueHandle<Self>,
     );
 
-    /// Method used to initialize the user-data of objects created by events
+    /// Called when the object this user data is associated with has been destroyed.
+    ///
+    /// This happens either when a destructor request was sent for this object, or when the server
+    /// sent a destructor event (such as `wl_callback.done`). This notification is delivered through
+    /// the event queue of the object, in order with the other events, and is always the last call
+    /// made for this object.
+    ///
+    /// You are given the [`ObjectId`] of the destroyed object for cleanup convenience.
     ///
-    /// If the interface does not have any such event, you can ignore it. If not, the
-    /// [`event_created_child!`](event_created_child!) macro is provided for overriding it.
-    fn event_created_child(opcode: u16, _qhandle: &QueueHandle<Self>) -> Arc<dyn ObjectData> {
-        panic!(
-            "Missing event_created_child specialization for event opcode {} of {}",
-            opcode,
-            I::interface().name
-        );
+    /// By default this method does nothing.
+    fn destroyed(
+        &mut self,
+        _object: ObjectId,
+        _data: &U,
+        _connhandle: &mut ConnectionHandle,
+        _qhandle: &QueueHandle<Self>,
+    ) {
     }
 }
 
-/// Macro used to override [`Dispatch::event_created_child()`](Dispatch::event_created_child)
-///
-/// Use this macro inside the [`Dispatch`] implementation to override this method, to implement the
-/// initialization of the user data for event-created objects. The usage syntax is as follow:
-///
-/// ```ignore
-/// impl Dispatch<WlFoo> for MyState {
-///     type UserData = FooUserData;
+/// Initialization of the objects created by the events of an interface
 ///
-///     fn event(
-///         &mut self,
-///         proxy: &WlFoo,
-///         event: FooEvent,
-///         data: &FooUserData,
-///         connhandle: &mut ConnectionHandle,
-///         qhandle: &QueueHandle<MyState>
-///     ) {
-///         /* ... */
-///     }
-///
-///     event_created_child!(MyState, WlFoo, [
-///     // there can be multiple lines if this interface has multiple object-creating event
-///         2 => (WlBar, BarUserData::new()),
-///     //  ~     ~~~~~  ~~~~~~~~~~~~~~~~~~
-///     //  |       |       |
-///     //  |       |       +-- an expression whose evaluation produces the user data value
-///     //  |       +-- the type of the newly created objecy
-///     //  +-- the opcode of the event that creates a new object
-///     ]);
-/// }
-/// ```
-#[macro_export]
-macro_rules! event_created_child {
-    ($selftype:ty, $iface:ty, [$($opcode:expr => ($child_iface:ty, $child_udata:expr)),* $(,)?]) => {
-        fn event_created_child(
-            opcode: u16,
-            qhandle: &$crate::QueueHandle<$selftype>
-        ) -> std::sync::Arc<dyn $crate::backend::ObjectData> {
-            match opcode {
-                $(
-                    $opcode => {
-                        qhandle.make_data::<$child_iface>({$child_udata})
-                    },
-                )*
-                _ => {
-                    panic!("Missing event_created_child specialization for event opcode {} of {}", opcode, <$iface as $crate::Proxy>::interface().name);
-                },
-            }
-        }
-    }
+/// This trait is implemented by `wayland-scanner` for all generated proxy types. For interfaces without any
+/// object-creating event it is implemented for any state and user data. For the others, the user data `U`
+/// must implement the `ChildrenData` trait generated in the module of the interface, which provides a typed
+/// method initializing the user data of the objects created by each of these events. Forgetting to handle
+/// one of them is thus a compile-time error when creating the parent object, rather than a panic when the
+/// server creates the child.
+pub trait EventChildren<D, U>: Proxy {
+    /// Create the object data of the object created by the event of given opcode
+    ///
+    /// Returns `None` if this event does not create any object.
+    fn event_created_child(
+        opcode: u16,
+        parent_data: &U,
+        qhandle: &QueueHandle<D>,
+    ) -> Option<Arc<dyn ObjectData>>;
 }
 
 type QueueCallback<D> = fn(
-    &mut ConnectionHandle<'_>,
+    &mut ConnectionHandle,
     Message<ObjectId>,
     &mut D,
     Arc<dyn ObjectData>,
     &QueueHandle<D>,
 ) -> Result<(), DispatchError>;
 
-struct QueueEvent<D>(QueueCallback<D>, Message<ObjectId>, Arc<dyn ObjectData>);
+type DestroyedCallback<D> =
+    fn(&mut ConnectionHandle, ObjectId, &mut D, Arc<dyn ObjectData>, &QueueHandle<D>);
+
+enum QueueEvent<D> {
+    Event(QueueCallback<D>, Message<ObjectId>, Arc<dyn ObjectData>),
+    Destroyed(DestroyedCallback<D>, ObjectId, Arc<dyn ObjectData>),
+}
 
 #[cfg(not(tarpaulin_include))]
 impl<D> std::fmt::Debug for QueueEvent<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("QueueEvent").field("msg", &self.1).finish_non_exhaustive()
+        match self {
+            QueueEvent::Event(_, msg, _) => {
+                f.debug_struct("QueueEvent").field("msg", msg).finish_non_exhaustive()
+            }
+            QueueEvent::Destroyed(_, id, _) => {
+                f.debug_struct("QueueEvent").field("destroyed", id).finish_non_exhaustive()
+            }
+        }
     }
 }
 
@@ -119,10 +113,30 @@ impl<D> std::fmt::Debug for QueueEvent<D> {
 /// as argument to the method creating it. All event received by that object will be processed by that event
 /// queue, when [`dispatch_pending()`](EventQueue::dispatch_pending) or
 /// [`blocking_dispatch()`](EventQueue::blocking_dispatch) is invoked.
+///
+/// Each event queue can also provide its own file descriptor, which becomes readable when events are queued
+/// in it, see [`queue_fd()`](EventQueue::queue_fd). This allows several threads to each wait on their own
+/// event queue while a single one reads the Wayland socket, see [`ReadingMode`].
 pub struct EventQueue<D> {
     rx: UnboundedReceiver<QueueEvent<D>>,
     handle: QueueHandle<D>,
-    backend: Arc<Mutex<Backend>>,
+    backend: Backend,
+    mode: ReadingMode,
+}
+
+/// How the blocking methods of an [`EventQueue`] wait for events
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReadingMode {
+    /// Read events from the Wayland socket, synchronizing with the other threads doing so
+    ///
+    /// This is the default.
+    Socket,
+    /// Wait on the [queue fd](EventQueue::queue_fd) for events read by an other thread
+    ///
+    /// Another thread is then responsible for reading the Wayland socket, for example by
+    /// calling [`Connection::blocking_dispatch()`](crate::Connection::blocking_dispatch) in a loop, the
+    /// blocking methods of this queue will otherwise wait forever.
+    External,
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -131,14 +145,16 @@ impl<D> std::fmt::Debug for EventQueue<D> {
         f.debug_struct("EventQueue")
             .field("rx", &self.rx)
             .field("handle", &self.handle)
+            .field("mode", &self.mode)
             .finish_non_exhaustive()
     }
 }
 
 impl<D> EventQueue<D> {
-    pub(crate) fn new(backend: Arc<Mutex<Backend>>) -> Self {
+    pub(crate) fn new(backend: Backend) -> Self {
         let (tx, rx) = unbounded();
-        EventQueue { rx, handle: QueueHandle { tx }, backend }
+        let notifier = Arc::new(QueueNotifier::default());
+        EventQueue { rx, handle: QueueHandle { tx, notifier }, backend, mode: ReadingMode::Socket }
     }
 
     /// Get a [`QueueHandle`] for this event queue
@@ -153,7 +169,7 @@ impl<D> EventQueue<D> {
     /// This method will dispatch all such pending events by sequentially invoking their associated handlers:
     /// the [`Dispatch`](crate::Dispatch) implementations on the provided `&mut D`.
     pub fn dispatch_pending(&mut self, data: &mut D) -> Result<usize, DispatchError> {
-        Self::dispatching_impl(&mut self.backend.lock().unwrap(), &mut self.rx, &self.handle, data)
+        Self::dispatching_impl(&self.backend, &mut self.rx, &self.handle, data)
     }
 
     /// Block waiting for events and dispatch them
@@ -162,24 +178,72 @@ impl<D> EventQueue<D> {
     /// pending events it will also block waiting for the Wayland server to send an event.
     ///
     /// A simple app event loop can consist in invoking this method in a loop.
+    ///
+    /// How this method waits for events depends on the [`ReadingMode`] of this queue.
     pub fn blocking_dispatch(&mut self, data: &mut D) -> Result<usize, DispatchError> {
-        let dispatched = Self::dispatching_impl(
-            &mut self.backend.lock().unwrap(),
-            &mut self.rx,
-            &self.handle,
-            data,
-        )?;
+        self.blocking_dispatch_impl(data, None)
+    }
+
+    /// Block waiting for events and dispatch them, with a timeout
+    ///
+    /// This is similar to [`blocking_dispatch()`](EventQueue::blocking_dispatch), but returns
+    /// [`DispatchError::Timeout`] if no event was received before the timeout expired.
+    pub fn blocking_dispatch_timeout(
+        &mut self,
+        data: &mut D,
+        timeout: Duration,
+    ) -> Result<usize, DispatchError> {
+        self.blocking_dispatch_impl(data, Some(Instant::now() + timeout))
+    }
+
+    fn blocking_dispatch_impl(
+        &mut self,
+        data: &mut D,
+        deadline: Option<Instant>,
+    ) -> Result<usize, DispatchError> {
+        let dispatched = Self::dispatching_impl(&self.backend, &mut self.rx, &self.handle, data)?;
         if dispatched > 0 {
-            Ok(dispatched)
+            return Ok(dispatched);
+        }
+        let ready = match self.mode {
+            ReadingMode::Socket => {
+                crate::conn::blocking_dispatch_impl(self.backend.clone(), deadline)?.is_some()
+            }
+            ReadingMode::External => {
+                self.backend.flush()?;
+                let fd = self.handle.notifier.enable().map_err(WaylandError::Io)?;
+                crate::conn::wait_readable(fd, deadline).map_err(WaylandError::Io)?
+            }
+        };
+        if ready {
+            Self::dispatching_impl(&self.backend, &mut self.rx, &self.handle, data)
         } else {
-            crate::conn::blocking_dispatch_impl(self.backend.clone())?;
-            Self::dispatching_impl(
-                &mut self.backend.lock().unwrap(),
-                &mut self.rx,
-                &self.handle,
-                data,
-            )
+            Err(DispatchError::Timeout)
+        }
+    }
+
+    /// Get the file descriptor of this event queue
+    ///
+    /// This file descriptor becomes readable when events are queued in this event queue, and stays so until
+    /// they are dispatched with [`dispatch_pending()`](EventQueue::dispatch_pending) or
+    /// [`blocking_dispatch()`](EventQueue::blocking_dispatch). It is created on the first call to this method
+    /// and lives as long as the event queue and its [`QueueHandle`]s.
+    ///
+    /// Unlike the Wayland socket, it is only woken up by events for this event queue, and can be polled by an
+    /// event loop while an other thread reads the socket.
+    pub fn queue_fd(&self) -> std::io::Result<RawFd> {
+        self.handle.notifier.enable()
+    }
+
+    /// Set how the blocking methods of this event queue wait for events
+    ///
+    /// Setting it to [`ReadingMode::External`] creates the [queue fd](EventQueue::queue_fd) if necessary.
+    pub fn set_reading_mode(&mut self, mode: ReadingMode) -> std::io::Result<()> {
+        if mode == ReadingMode::External {
+            self.handle.notifier.enable()?;
         }
+        self.mode = mode;
+        Ok(())
     }
 
     /// Start a synchronized read from the socket
@@ -199,20 +263,31 @@ impl<D> EventQueue<D> {
     ///
     /// This needs to be done regularly to ensure the server receives all your requests.
     pub fn flush(&self) -> Result<(), WaylandError> {
-        self.backend.lock().unwrap().flush()
+        self.backend.flush()
     }
 
     fn dispatching_impl(
-        backend: &mut Backend,
+        backend: &Backend,
         rx: &mut UnboundedReceiver<QueueEvent<D>>,
         qhandle: &QueueHandle<D>,
         data: &mut D,
     ) -> Result<usize, DispatchError> {
-        let mut handle = ConnectionHandle::from_handle(backend.handle());
+        let mut handle = ConnectionHandle::new(backend);
         let mut dispatched = 0;
 
-        while let Ok(Some(QueueEvent(cb, msg, odata))) = rx.try_next() {
-            cb(&mut handle, msg, data, odata, qhandle)?;
+        qhandle.notifier.clear();
+
+        while let Ok(Some(evt)) = rx.try_next() {
+            match evt {
+                QueueEvent::Event(cb, msg, odata) => {
+                    if let Err(e) = cb(&mut handle, msg, data, odata, qhandle) {
+                        // events may still be queued, the queue fd must stay readable for them
+                        qhandle.notifier.notify();
+                        return Err(e);
+                    }
+                }
+                QueueEvent::Destroyed(cb, id, odata) => cb(&mut handle, id, data, odata, qhandle),
+            }
             dispatched += 1;
         }
         Ok(dispatched)
@@ -222,6 +297,7 @@ impl<D> EventQueue<D> {
 /// A handle representing an [`EventQueue`], used to assign objects upon creation.
 pub struct QueueHandle<D> {
     tx: UnboundedSender<QueueEvent<D>>,
+    notifier: Arc<QueueNotifier>,
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -233,26 +309,99 @@ impl<Data> std::fmt::Debug for QueueHandle<Data> {
 
 impl<Data> Clone for QueueHandle<Data> {
     fn clone(&self) -> Self {
-        QueueHandle { tx: self.tx.clone() }
+        QueueHandle { tx: self.tx.clone(), notifier: self.notifier.clone() }
     }
 }
 
 pub(crate) struct QueueSender<D> {
     func: QueueCallback<D>,
+    destroyed_func: DestroyedCallback<D>,
     pub(crate) handle: QueueHandle<D>,
 }
 
 pub(crate) trait ErasedQueueSender<I> {
     fn send(&self, msg: Message<ObjectId>, odata: Arc<dyn ObjectData>);
+    fn send_destroyed(&self, id: ObjectId, odata: Arc<dyn ObjectData>);
 }
 
-impl<I: Proxy, D> ErasedQueueSender<I> for QueueSender<D>
-where
-    D: Dispatch<I>,
-{
+impl<I: Proxy, D> ErasedQueueSender<I> for QueueSender<D> {
     fn send(&self, msg: Message<ObjectId>, odata: Arc<dyn ObjectData>) {
-        if self.handle.tx.unbounded_send(QueueEvent(self.func, msg, odata)).is_err() {
+        if self.handle.tx.unbounded_send(QueueEvent::Event(self.func, msg, odata)).is_err() {
             log::error!("Event received for EventQueue after it was dropped.");
+        } else {
+            self.handle.notifier.notify();
+        }
+    }
+
+    fn send_destroyed(&self, id: ObjectId, odata: Arc<dyn ObjectData>) {
+        if self
+            .handle
+            .tx
+            .unbounded_send(QueueEvent::Destroyed(self.destroyed_func, id, odata))
+            .is_err()
+        {
+            log::error!("Object destroyed for EventQueue after it was dropped.");
+        } else {
+            self.handle.notifier.notify();
+        }
+    }
+}
+
+/// Wakeup mechanism behind the queue fd of an [`EventQueue`]
+///
+/// The fd is the reading end of a non-blocking pipe, in which a byte is written when an event is
+/// queued while none is pending, and which is drained before dispatching the queue.
+#[derive(Debug, Default)]
+struct QueueNotifier {
+    pipe: Mutex<Option<(RawFd, RawFd)>>,
+    pending: AtomicBool,
+}
+
+impl QueueNotifier {
+    fn enable(&self) -> std::io::Result<RawFd> {
+        let mut pipe = self.pipe.lock().unwrap();
+        if let Some((read, _)) = *pipe {
+            return Ok(read);
+        }
+        let (read, write) =
+            nix::unistd::pipe2(nix::fcntl::OFlag::O_CLOEXEC | nix::fcntl::OFlag::O_NONBLOCK)?;
+        *pipe = Some((read, write));
+        // events may already be waiting in the queue
+        self.pending.store(true, Ordering::SeqCst);
+        let _ = nix::unistd::write(write, &[0]);
+        Ok(read)
+    }
+
+    fn notify(&self) {
+        if !self.pending.swap(true, Ordering::SeqCst) {
+            if let Some((_, write)) = *self.pipe.lock().unwrap() {
+                // if the pipe is full, it is readable anyway
+                let _ = nix::unistd::write(write, &[0]);
+            }
+        }
+    }
+
+    fn clear(&self) {
+        // must be done before reading the queue, so that no event queued afterwards is missed
+        if let Some((read, _)) = *self.pipe.lock().unwrap() {
+            let mut buffer = [0u8; 64];
+            while let Ok(n) = nix::unistd::read(read, &mut buffer) {
+                if n < buffer.len() {
+                    break;
+                }
+            }
+        }
+        // only reset the flag once the pipe is drained, otherwise the byte written by a concurrent
+        // notification could be drained while the flag stays set, and no later one would be written
+        self.pending.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Drop for QueueNotifier {
+    fn drop(&mut self) {
+        if let Some((read, write)) = *self.pipe.get_mut().unwrap() {
+            let _ = nix::unistd::close(read);
+            let _ = nix::unistd::close(write);
         }
     }
 }
@@ -263,32 +412,91 @@ impl<D: 'static> QueueHandle<D> {
     /// This creates an implementation of [`ObjectData`] fitting for direct use with `wayland-backend` APIs
     /// that forwards all events to the event queue associated with this token, integrating the object into
     /// the [`Dispatch`]-based logic of `wayland-client`.
-    pub fn make_data<I: Proxy + 'static>(
+    ///
+    /// The [`Dispatch`] implementation handling the events of this object is selected by the interface `I`
+    /// and the type `U` of the provided user data. The objects created by its events are initialized via
+    /// [`EventChildren`].
+    pub fn make_data<I: Proxy + EventChildren<D, U> + 'static, U: Send + Sync + 'static>(
         &self,
-        user_data: <D as Dispatch<I>>::UserData,
+        user_data: U,
     ) -> Arc<dyn ObjectData>
     where
-        D: Dispatch<I>,
+        D: Dispatch<I, U>,
     {
-        let sender = Box::new(QueueSender { func: queue_callback::<I, D>, handle: self.clone() });
+        self.make_queue_data::<I, U>(
+            user_data,
+            queue_callback::<I, U, D>,
+            queue_destroyed::<I, U, D>,
+        )
+    }
+
+    /// Create an object data handling the events of its object with a closure
+    ///
+    /// This is the counterpart of [`make_data()`](QueueHandle::make_data) for the objects handled by a
+    /// [`ClosureData`] rather than by a [`Dispatch`] implementation of your state. The returned object data
+    /// can be given to [`Proxy::send_constructor()`](crate::Proxy::send_constructor).
+    pub fn make_closure_data<I: Proxy + EventChildren<D, ClosureData<I, D>> + 'static>(
+        &self,
+        data: ClosureData<I, D>,
+    ) -> Arc<dyn ObjectData> {
+        self.make_queue_data::<I, ClosureData<I, D>>(
+            data,
+            closure_callback::<I, D>,
+            |_, _, _, _, _| {},
+        )
+    }
+
+    fn make_queue_data<I: Proxy + EventChildren<D, U> + 'static, U: Send + Sync + 'static>(
+        &self,
+        user_data: U,
+        func: QueueCallback<D>,
+        destroyed_func: DestroyedCallback<D>,
+    ) -> Arc<dyn ObjectData> {
+        let sender = Box::new(QueueSender { func, destroyed_func, handle: self.clone() });
 
         let has_creating_event =
             I::interface().events.iter().any(|desc| desc.child_interface.is_some());
 
         let odata_maker = if has_creating_event {
             let qhandle = self.clone();
-            Box::new(move |msg: &Message<ObjectId>| {
-                Some(<D as Dispatch<I>>::event_created_child(msg.opcode, &qhandle))
+            Box::new(move |msg: &Message<ObjectId>, udata: &U| {
+                I::event_created_child(msg.opcode, udata, &qhandle)
             }) as Box<_>
         } else {
-            Box::new(|_: &Message<ObjectId>| None) as Box<_>
+            Box::new(|_: &Message<ObjectId>, _: &U| None) as Box<_>
         };
-        Arc::new(QueueProxyData { sender, odata_maker, udata: user_data })
+        Arc::new(QueueProxyData::<I, U> { sender, odata_maker, udata: user_data })
     }
 }
 
-fn queue_callback<I: Proxy + 'static, D: Dispatch<I> + 'static>(
-    handle: &mut ConnectionHandle<'_>,
+fn queue_callback<I: Proxy + 'static, U: Send + Sync + 'static, D: Dispatch<I, U> + 'static>(
+    handle: &mut ConnectionHandle,
+    msg: Message<ObjectId>,
+    data: &mut D,
+    odata: Arc<dyn ObjectData>,
+    qhandle: &QueueHandle<D>,
+) -> Result<(), DispatchError> {
+    let (proxy, event) = I::parse_event(handle, msg)?;
+    let proxy_data =
+        (&*odata).downcast_ref::<QueueProxyData<I, U>>().expect("Wrong user_data value for object");
+    data.event(&proxy, event, &proxy_data.udata, handle, qhandle);
+    Ok(())
+}
+
+fn queue_destroyed<I: Proxy + 'static, U: Send + Sync + 'static, D: Dispatch<I, U> + 'static>(
+    handle: &mut ConnectionHandle,
+    id: ObjectId,
+    data: &mut D,
+    odata: Arc<dyn ObjectData>,
+    qhandle: &QueueHandle<D>,
+) {
+    let proxy_data =
+        (&*odata).downcast_ref::<QueueProxyData<I, U>>().expect("Wrong user_data value for object");
+    data.destroyed(id, &proxy_data.udata, handle, qhandle);
+}
+
+fn closure_callback<I: Proxy + 'static, D: 'static>(
+    handle: &mut ConnectionHandle,
     msg: Message<ObjectId>,
     data: &mut D,
     odata: Arc<dyn ObjectData>,
@@ -296,18 +504,19 @@ fn queue_callback<I: Proxy + 'static, D: Dispatch<I> + 'static>(
 ) -> Result<(), DispatchError> {
     let (proxy, event) = I::parse_event(handle, msg)?;
     let proxy_data = (&*odata)
-        .downcast_ref::<QueueProxyData<I, <D as Dispatch<I>>::UserData>>()
+        .downcast_ref::<QueueProxyData<I, ClosureData<I, D>>>()
         .expect("Wrong user_data value for object");
-    data.event(&proxy, event, &proxy_data.udata, handle, qhandle);
+    (proxy_data.udata.handler.lock().unwrap())(data, &proxy, event, handle, qhandle);
     Ok(())
 }
 
-type ObjectDataFactory = dyn Fn(&Message<ObjectId>) -> Option<Arc<dyn ObjectData>> + Send + Sync;
+type ObjectDataFactory<U> =
+    dyn Fn(&Message<ObjectId>, &U) -> Option<Arc<dyn ObjectData>> + Send + Sync;
 
 /// The [`ObjectData`] implementation used by Wayland proxies, integrating with [`Dispatch`]
 pub struct QueueProxyData<I: Proxy, U> {
     pub(crate) sender: Box<dyn ErasedQueueSender<I> + Send + Sync>,
-    odata_maker: Box<ObjectDataFactory>,
+    odata_maker: Box<ObjectDataFactory<U>>,
     /// The user data associated with this object
     pub udata: U,
 }
@@ -318,12 +527,14 @@ impl<I: Proxy + 'static, U: Send + Sync + 'static> ObjectData for QueueProxyData
         _: &mut Handle,
         msg: Message<ObjectId>,
     ) -> Option<Arc<dyn ObjectData>> {
-        let ret = (self.odata_maker)(&msg);
+        let ret = (self.odata_maker)(&msg, &self.udata);
         self.sender.send(msg, self.clone());
         ret
     }
 
-    fn destroyed(&self, _: ObjectId) {}
+    fn destroyed(self: Arc<Self>, id: ObjectId) {
+        self.sender.send_destroyed(id, self.clone());
+    }
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -333,6 +544,53 @@ impl<I: Proxy, U: std::fmt::Debug> std::fmt::Debug for QueueProxyData<I, U> {
     }
 }
 
+/*
+ * Closure-based handlers
+ */
+
+type ClosureHandler<I, D> =
+    dyn FnMut(&mut D, &I, <I as Proxy>::Event, &mut ConnectionHandle, &QueueHandle<D>) + Send;
+
+/// A user data value handling the events of its object with a closure
+///
+/// An object created with the object data given by [`QueueHandle::make_closure_data()`] has its events
+/// processed by the provided closure rather than by a dedicated [`Dispatch`] implementation of your state.
+/// Such objects can be freely mixed with trait-based ones on the same [`EventQueue`]:
+///
+/// ```ignore
+/// let callback: wl_callback::WlCallback = display.send_constructor(
+///     wl_display::Request::Sync {},
+///     qh.make_closure_data(ClosureData::new(|state: &mut MyState, _, _, _, _| state.sync_done = true)),
+/// )?;
+/// ```
+///
+/// These objects do not go through the [`Dispatch`] trait, which your state can thus implement generically
+/// over the user data type.
+///
+/// Objects whose interface has events creating new objects can only be handled by closures if the
+/// `ChildrenData` trait of their interface is implemented for the corresponding `ClosureData` type, see
+/// [`EventChildren`].
+pub struct ClosureData<I: Proxy, D> {
+    handler: Mutex<Box<ClosureHandler<I, D>>>,
+}
+
+impl<I: Proxy, D> ClosureData<I, D> {
+    /// Create a new closure-based handler
+    pub fn new<F>(handler: F) -> ClosureData<I, D>
+    where
+        F: FnMut(&mut D, &I, I::Event, &mut ConnectionHandle, &QueueHandle<D>) + Send + 'static,
+    {
+        ClosureData { handler: Mutex::new(Box::new(handler)) }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<I: Proxy, D> std::fmt::Debug for ClosureData<I, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClosureData").finish_non_exhaustive()
+    }
+}
+
 struct TemporaryData;
 
 impl ObjectData for TemporaryData {
@@ -340,22 +598,17 @@ impl ObjectData for TemporaryData {
         unreachable!()
     }
 
-    fn destroyed(&self, _: ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: ObjectId) {}
 }
 
 /*
  * Dispatch delegation helpers
  */
 
-/// The base trait used to define a delegate type to handle some type of proxy.
-pub trait DelegateDispatchBase<I: Proxy> {
-    /// The type of user data the delegate holds
-    type UserData: Send + Sync + 'static;
-}
-
 /// A trait which defines a delegate type to handle some type of proxy.
 ///
-/// This trait is useful for building modular handlers of proxies.
+/// This trait is useful for building modular handlers of proxies. Like [`Dispatch`], it is keyed on both the
+/// interface `I` of the proxy and the type `U` of its user data.
 ///
 /// ## Usage
 ///
@@ -363,24 +616,21 @@ pub trait DelegateDispatchBase<I: Proxy> {
 ///
 /// ```
 /// # // Maintainers: If this example changes, please make sure you also carry those changes over to the delegate_dispatch macro.
-/// use wayland_client::{protocol::wl_registry, DelegateDispatch, DelegateDispatchBase, Dispatch};
+/// use wayland_client::{protocol::wl_registry, DelegateDispatch, Dispatch};
 ///
 /// /// The type we want to delegate to
 /// struct DelegateToMe;
 ///
-/// // Now implement DelegateDispatchBase.
-/// impl DelegateDispatchBase<wl_registry::WlRegistry> for DelegateToMe {
-///     /// The type of user data associated with the delegation of events from a registry is defined here.
-///     ///
-///     /// If you don't need user data, the unit type, `()`, may be used.
-///     type UserData = ();
-/// }
+/// /// The user data associated with the registries handled by our delegate
+/// ///
+/// /// If you don't need user data, the unit type, `()`, may be used.
+/// struct MyUserData;
 ///
 /// // Now implement DelegateDispatch.
-/// impl<D> DelegateDispatch<wl_registry::WlRegistry, D> for DelegateToMe
+/// impl<D> DelegateDispatch<wl_registry::WlRegistry, MyUserData, D> for DelegateToMe
 /// where
 ///     // `D` is the type which has delegated to this type.
-///     D: Dispatch<wl_registry::WlRegistry, UserData = Self::UserData>,
+///     D: Dispatch<wl_registry::WlRegistry, MyUserData>,
 ///     // If your delegate type has some internal state, it'll need to access it, and you can
 ///     // require it via an AsMut<_> implementation for example
 ///     D: AsMut<DelegateToMe>,
@@ -389,7 +639,7 @@ pub trait DelegateDispatchBase<I: Proxy> {
 ///         data: &mut D,
 ///         _proxy: &wl_registry::WlRegistry,
 ///         _event: wl_registry::Event,
-///         _udata: &Self::UserData,
+///         _udata: &MyUserData,
 ///         _connhandle: &mut wayland_client::ConnectionHandle,
 ///         _qhandle: &wayland_client::QueueHandle<D>,
 ///     ) {
@@ -402,11 +652,7 @@ pub trait DelegateDispatchBase<I: Proxy> {
 ///     }
 /// }
 /// ```
-pub trait DelegateDispatch<
-    I: Proxy,
-    D: Dispatch<I, UserData = <Self as DelegateDispatchBase<I>>::UserData>,
->: Sized + DelegateDispatchBase<I>
-{
+pub trait DelegateDispatch<I: Proxy, U, D: Dispatch<I, U>>: Sized {
     /// Called when an event from the server is processed.
     ///
     /// The implementation of this function may vary depending on protocol requirements. Typically the client
@@ -415,21 +661,23 @@ pub trait DelegateDispatch<
         data: &mut D,
         proxy: &I,
         event: I::Event,
-        udata: &Self::UserData,
+        udata: &U,
         connhandle: &mut ConnectionHandle,
         qhandle: &QueueHandle<D>,
     );
 
-    /// Method used to initialize the user-data of objects created by events
+    /// Called when the object this user data is associated with has been destroyed.
     ///
-    /// If the interface does not have any such event, you can ignore it. If not, the
-    /// [`event_created_child!`](event_created_child!) macro is provided for overriding it.
-    fn event_created_child(opcode: u16, _qhandle: &QueueHandle<D>) -> Arc<dyn ObjectData> {
-        panic!(
-            "Missing event_created_child specialization for event opcode {} of {}",
-            opcode,
-            I::interface().name
-        );
+    /// See [`Dispatch::destroyed()`] for details.
+    ///
+    /// By default this method does nothing.
+    fn destroyed(
+        _data: &mut D,
+        _object: ObjectId,
+        _udata: &U,
+        _connhandle: &mut ConnectionHandle,
+        _qhandle: &QueueHandle<D>,
+    ) {
     }
 }
 
@@ -449,23 +697,20 @@ pub trait DelegateDispatch<
 /// ```
 /// use wayland_client::{delegate_dispatch, protocol::wl_registry};
 /// #
-/// # use wayland_client::{DelegateDispatch, DelegateDispatchBase, Dispatch};
+/// # use wayland_client::{DelegateDispatch, Dispatch};
 /// #
 /// # struct DelegateToMe;
+/// # struct MyUserData;
 /// #
-/// # impl DelegateDispatchBase<wl_registry::WlRegistry> for DelegateToMe {
-/// #     type UserData = ();
-/// # }
-/// #
-/// # impl<D> DelegateDispatch<wl_registry::WlRegistry, D> for DelegateToMe
+/// # impl<D> DelegateDispatch<wl_registry::WlRegistry, MyUserData, D> for DelegateToMe
 /// # where
-/// #     D: Dispatch<wl_registry::WlRegistry, UserData = Self::UserData> + AsMut<DelegateToMe>,
+/// #     D: Dispatch<wl_registry::WlRegistry, MyUserData> + AsMut<DelegateToMe>,
 /// # {
 /// #     fn event(
 /// #         _data: &mut D,
 /// #         _proxy: &wl_registry::WlRegistry,
 /// #         _event: wl_registry::Event,
-/// #         _udata: &Self::UserData,
+/// #         _udata: &MyUserData,
 /// #         _connhandle: &mut wayland_client::ConnectionHandle,
 /// #         _qhandle: &wayland_client::QueueHandle<D>,
 /// #     ) {
@@ -480,8 +725,8 @@ pub trait DelegateDispatch<
 ///     delegate: DelegateToMe,
 /// }
 ///
-/// // Use delegate_dispatch to implement Dispatch<wl_registry::WlRegistry> for ExampleApp.
-/// delegate_dispatch!(ExampleApp: [wl_registry::WlRegistry] => DelegateToMe);
+/// // Use delegate_dispatch to implement Dispatch<wl_registry::WlRegistry, MyUserData> for ExampleApp.
+/// delegate_dispatch!(ExampleApp: [wl_registry::WlRegistry: MyUserData] => DelegateToMe);
 ///
 /// // But DelegateToMe requires that ExampleApp implements AsMut<DelegateToMe>, so we provide this impl
 /// impl AsMut<DelegateToMe> for ExampleApp {
@@ -492,13 +737,12 @@ pub trait DelegateDispatch<
 ///
 /// // To explain the macro above, you may read it as the following:
 /// //
-/// // For ExampleApp, delegate WlRegistry to DelegateToMe and use the closure to get an `&mut` reference to
-/// // the delegate.
+/// // For ExampleApp, delegate WlRegistry with user data MyUserData to DelegateToMe.
 ///
 /// // Assert ExampleApp can Dispatch events for wl_registry
 /// fn assert_is_registry_delegate<T>()
 /// where
-///     T: Dispatch<wl_registry::WlRegistry, UserData = ()>,
+///     T: Dispatch<wl_registry::WlRegistry, MyUserData>,
 /// {
 /// }
 ///
@@ -513,31 +757,32 @@ pub trait DelegateDispatch<
 ///
 /// ```ignore
 /// # // This is not tested because xdg_output is in wayland-protocols.
-/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput, xdg_output::XdgOutput] => OutputDelegate);
+/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput: OutputData, xdg_output::XdgOutput: OutputData] => OutputDelegate);
 /// ```
 #[macro_export]
 macro_rules! delegate_dispatch {
-    ($dispatch_from: ty: [$($interface: ty),*] => $dispatch_to: ty) => {
+    ($dispatch_from: ty: [$($interface: ty: $udata: ty),* $(,)?] => $dispatch_to: ty) => {
         $(
-            impl $crate::Dispatch<$interface> for $dispatch_from {
-                type UserData = <$dispatch_to as $crate::DelegateDispatchBase<$interface>>::UserData;
-
+            impl $crate::Dispatch<$interface, $udata> for $dispatch_from {
                 fn event(
                     &mut self,
                     proxy: &$interface,
                     event: <$interface as $crate::Proxy>::Event,
-                    data: &Self::UserData,
+                    data: &$udata,
                     connhandle: &mut $crate::ConnectionHandle,
                     qhandle: &$crate::QueueHandle<Self>,
                 ) {
-                    <$dispatch_to as $crate::DelegateDispatch<$interface, Self>>::event(self, proxy, event, data, connhandle, qhandle)
+                    <$dispatch_to as $crate::DelegateDispatch<$interface, $udata, Self>>::event(self, proxy, event, data, connhandle, qhandle)
                 }
 
-                fn event_created_child(
-                    opcode: u16,
-                    qhandle: &$crate::QueueHandle<Self>
-                ) -> ::std::sync::Arc<dyn $crate::backend::ObjectData> {
-                    <$dispatch_to as $crate::DelegateDispatch<$interface, Self>>::event_created_child(opcode, qhandle)
+                fn destroyed(
+                    &mut self,
+                    object: $crate::backend::ObjectId,
+                    data: &$udata,
+                    connhandle: &mut $crate::ConnectionHandle,
+                    qhandle: &$crate::QueueHandle<Self>,
+                ) {
+                    <$dispatch_to as $crate::DelegateDispatch<$interface, $udata, Self>>::destroyed(self, object, data, connhandle, qhandle)
                 }
             }
         )*
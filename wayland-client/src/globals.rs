@@ -3,12 +3,12 @@
 use std::ops::Range;
 
 use crate::{
-    protocol::wl_registry, ConnectionHandle, DelegateDispatch, DelegateDispatchBase, Dispatch,
-    Proxy, QueueHandle,
+    protocol::wl_registry, Connection, ConnectionHandle, DelegateDispatch, Dispatch, DispatchError,
+    EventChildren, EventQueue, Proxy, QueueHandle, RequestError,
 };
 
 /// Description of an advertized global
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GlobalDescription {
     /// identifier of this global
     pub name: u32,
@@ -18,24 +18,39 @@ pub struct GlobalDescription {
     pub version: u32,
 }
 
+/// A change in the list of advertized globals
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobalEvent {
+    /// A new global was advertized by the server
+    New(GlobalDescription),
+    /// A global was removed by the server
+    Removed(GlobalDescription),
+}
+
 /// A helper to retrieve a list of globals and bind them
 ///
 /// The `GlobalList` can be used as a [`Dispatch`](crate::Dispatch) target for the `wl_registry`. It
 /// maintains a list of globals advertized by the compositor, and provides a way to bind according to
 /// specified version requirements. It is an easy way to ensure at startup that the server advertized
 /// all the globals your app needs, and bind them all at once.
+///
+/// Globals may also appear or disappear during the lifetime of your app (outputs or seats being
+/// plugged or unplugged for example). Once enabled with [`GlobalList::set_record_events()`], every such
+/// change is recorded as a [`GlobalEvent`], which you can retrieve using [`GlobalList::take_events()`]
+/// after dispatching your event queue.
+///
+/// See [`registry_queue_init()`] for a convenient way to setup the registry and wait for the
+/// initial list of globals.
 #[derive(Debug)]
 pub struct GlobalList {
     globals: Vec<GlobalDescription>,
+    events: Vec<GlobalEvent>,
+    record_events: bool,
 }
 
-impl DelegateDispatchBase<wl_registry::WlRegistry> for GlobalList {
-    type UserData = ();
-}
-
-impl<D> DelegateDispatch<wl_registry::WlRegistry, D> for GlobalList
+impl<D> DelegateDispatch<wl_registry::WlRegistry, (), D> for GlobalList
 where
-    D: Dispatch<wl_registry::WlRegistry, UserData = ()> + AsMut<GlobalList>,
+    D: Dispatch<wl_registry::WlRegistry, ()> + AsMut<GlobalList>,
 {
     fn event(
         handle: &mut D,
@@ -48,10 +63,19 @@ where
         let me = handle.as_mut();
         match event {
             wl_registry::Event::Global { name, interface, version } => {
-                me.globals.push(GlobalDescription { name, interface, version });
+                let desc = GlobalDescription { name, interface, version };
+                if me.record_events {
+                    me.events.push(GlobalEvent::New(desc.clone()));
+                }
+                me.globals.push(desc);
             }
             wl_registry::Event::GlobalRemove { name } => {
-                me.globals.retain(|desc| desc.name != name);
+                if let Some(pos) = me.globals.iter().position(|desc| desc.name == name) {
+                    let desc = me.globals.remove(pos);
+                    if me.record_events {
+                        me.events.push(GlobalEvent::Removed(desc));
+                    }
+                }
             }
         }
     }
@@ -63,19 +87,17 @@ impl AsMut<GlobalList> for GlobalList {
     }
 }
 
-impl Dispatch<wl_registry::WlRegistry> for GlobalList {
-    type UserData = ();
-
+impl Dispatch<wl_registry::WlRegistry, ()> for GlobalList {
     #[inline]
     fn event(
         &mut self,
         proxy: &wl_registry::WlRegistry,
         event: wl_registry::Event,
-        data: &Self::UserData,
+        data: &(),
         connhandle: &mut ConnectionHandle,
         qhandle: &QueueHandle<Self>,
     ) {
-        <Self as DelegateDispatch<wl_registry::WlRegistry, Self>>::event(
+        <Self as DelegateDispatch<wl_registry::WlRegistry, (), Self>>::event(
             self, proxy, event, data, connhandle, qhandle,
         )
     }
@@ -90,7 +112,18 @@ impl Default for GlobalList {
 impl GlobalList {
     /// Create a new `GLobalList`
     pub fn new() -> GlobalList {
-        GlobalList { globals: Vec::new() }
+        GlobalList { globals: Vec::new(), events: Vec::new(), record_events: false }
+    }
+
+    /// Sets whether the changes of the global list should be recorded as [`GlobalEvent`]s
+    ///
+    /// The recorded events are kept until they are retrieved with [`take_events()`](GlobalList::take_events),
+    /// and disabling the recording discards them. This is disabled by default.
+    pub fn set_record_events(&mut self, enabled: bool) {
+        self.record_events = enabled;
+        if !enabled {
+            self.events.clear();
+        }
     }
 
     /// Access the list of currently advertized globals
@@ -98,40 +131,148 @@ impl GlobalList {
         &self.globals
     }
 
+    /// Take the changes of the global list that happened since the last call to this method
+    ///
+    /// The changes are only recorded once enabled with [`set_record_events()`](GlobalList::set_record_events).
+    /// If you did not use [`registry_queue_init()`], this includes the initial advertisement of all globals.
+    pub fn take_events(&mut self) -> Vec<GlobalEvent> {
+        std::mem::take(&mut self.events)
+    }
+
     /// Bind a global
     ///
     /// You can specify the requested interface as type parameter, and the version range. You
     /// also need to provide the user data value that will be set for the newly created object.
-    pub fn bind<I: Proxy + 'static, D: Dispatch<I> + 'static>(
+    ///
+    /// The global is bound with the highest version allowed by both the server and the upper bound of
+    /// the range. An error is returned if the server only advertizes versions lower than the start of
+    /// the range. If the server advertizes several instances of this global, the first one satisfying the
+    /// version requirements is bound, see [`bind_all()`](GlobalList::bind_all) to bind all of them.
+    pub fn bind<
+        I: Proxy + EventChildren<D, U> + 'static,
+        U: Send + Sync + 'static,
+        D: Dispatch<I, U> + 'static,
+    >(
         &self,
-        conn: &mut ConnectionHandle<'_>,
         qh: &QueueHandle<D>,
         registry: &wl_registry::WlRegistry,
         version: Range<u32>,
-        user_data: <D as Dispatch<I>>::UserData,
+        user_data: U,
     ) -> Result<I, BindError> {
-        for desc in &self.globals {
-            if desc.interface != I::interface().name {
-                continue;
+        let mut got = None;
+        for desc in self.globals.iter().filter(|desc| desc.interface == I::interface().name) {
+            if desc.version >= version.start {
+                return bind_desc(qh, registry, desc, &version, user_data);
             }
+            got = Some(got.map_or(desc.version, |v: u32| v.max(desc.version)));
+        }
 
-            if version.contains(&desc.version) {
-                return Ok(registry
-                    .bind::<I, D>(conn, desc.name, desc.version, qh, user_data)
-                    .expect("invalid wl_registry"));
-            } else {
-                return Err(BindError::WrongVersion {
-                    interface: I::interface().name,
-                    requested: version,
-                    got: desc.version,
-                });
-            }
+        match got {
+            Some(got) => Err(BindError::WrongVersion {
+                interface: I::interface().name,
+                requested: version,
+                got,
+            }),
+            None => Err(BindError::MissingGlobal { interface: I::interface().name }),
+        }
+    }
+
+    /// Bind all instances of a global
+    ///
+    /// This is similar to [`bind()`](GlobalList::bind), but binds all globals of the requested
+    /// interface advertized with a compatible version, each of them receiving a clone of the provided
+    /// user data. This is useful for globals that can have several instances, like `wl_output` or
+    /// `wl_seat`. Globals advertized with a version lower than the start of the range are skipped.
+    /// An error is returned if the bind requests could not be sent.
+    pub fn bind_all<
+        I: Proxy + EventChildren<D, U> + 'static,
+        U: Clone + Send + Sync + 'static,
+        D: Dispatch<I, U> + 'static,
+    >(
+        &self,
+        qh: &QueueHandle<D>,
+        registry: &wl_registry::WlRegistry,
+        version: Range<u32>,
+        user_data: U,
+    ) -> Result<Vec<I>, BindError> {
+        self.globals
+            .iter()
+            .filter(|desc| desc.interface == I::interface().name && desc.version >= version.start)
+            .map(|desc| bind_desc(qh, registry, desc, &version, user_data.clone()))
+            .collect()
+    }
+
+    /// Bind a specific global from its name
+    ///
+    /// This is notably useful to bind globals reported by a [`GlobalEvent::New`]. The version is
+    /// clamped in the same way as [`bind()`](GlobalList::bind).
+    pub fn bind_name<
+        I: Proxy + EventChildren<D, U> + 'static,
+        U: Send + Sync + 'static,
+        D: Dispatch<I, U> + 'static,
+    >(
+        &self,
+        qh: &QueueHandle<D>,
+        registry: &wl_registry::WlRegistry,
+        name: u32,
+        version: Range<u32>,
+        user_data: U,
+    ) -> Result<I, BindError> {
+        let desc = self
+            .globals
+            .iter()
+            .find(|desc| desc.name == name && desc.interface == I::interface().name)
+            .ok_or(BindError::MissingGlobal { interface: I::interface().name })?;
+
+        if desc.version < version.start {
+            return Err(BindError::WrongVersion {
+                interface: I::interface().name,
+                requested: version,
+                got: desc.version,
+            });
         }
 
-        Err(BindError::MissingGlobal { interface: I::interface().name })
+        bind_desc(qh, registry, desc, &version, user_data)
     }
 }
 
+fn bind_desc<
+    I: Proxy + EventChildren<D, U> + 'static,
+    U: Send + Sync + 'static,
+    D: Dispatch<I, U> + 'static,
+>(
+    qh: &QueueHandle<D>,
+    registry: &wl_registry::WlRegistry,
+    desc: &GlobalDescription,
+    version: &Range<u32>,
+    user_data: U,
+) -> Result<I, BindError> {
+    // the range is exclusive, but never go below its start
+    let version = desc.version.min(version.end.saturating_sub(1)).max(version.start);
+    Ok(registry.bind::<I, U, D>(desc.name, version, qh, user_data)?)
+}
+
+/// Create a registry and retrieve the initial list of globals
+///
+/// This creates a `wl_registry` associated with the provided event queue and does a roundtrip to the
+/// server, dispatching the event queue so that the [`GlobalList`] of your state is populated with the
+/// initial globals once this function returns. The events generated by this initial advertisement are
+/// discarded, so [`GlobalList::take_events()`] will only report the changes that happen afterwards.
+pub fn registry_queue_init<D>(
+    conn: &Connection,
+    event_queue: &mut EventQueue<D>,
+    data: &mut D,
+) -> Result<wl_registry::WlRegistry, DispatchError>
+where
+    D: Dispatch<wl_registry::WlRegistry, ()> + AsMut<GlobalList> + 'static,
+{
+    let registry = conn.handle().display().get_registry(&event_queue.handle(), ())?;
+    conn.roundtrip()?;
+    event_queue.dispatch_pending(data)?;
+    data.as_mut().events.clear();
+    Ok(registry)
+}
+
 /// Error when trying to bind a global
 #[derive(Debug, thiserror::Error)]
 pub enum BindError {
@@ -151,4 +292,7 @@ pub enum BindError {
         /// The advertized version
         got: u32,
     },
+    /// The bind request could not be sent
+    #[error(transparent)]
+    Request(#[from] RequestError),
 }
@@ -2,8 +2,10 @@
 
 #![warn(missing_docs, missing_debug_implementations)]
 
+use std::sync::Arc;
+
 use wayland_backend::{
-    client::{InvalidId, ObjectId, WaylandError},
+    client::{InvalidId, ObjectData, ObjectId, WaylandError},
     protocol::{Interface, Message},
 };
 
@@ -23,9 +25,10 @@ pub mod backend {
 
 pub use wayland_backend::protocol::WEnum;
 
-pub use conn::{ConnectError, Connection, ConnectionHandle};
+pub use conn::{ConnectError, Connection, ConnectionHandle, SyncHandle, WeakConnection};
 pub use event_queue::{
-    DelegateDispatch, DelegateDispatchBase, Dispatch, EventQueue, QueueHandle, QueueProxyData,
+    ClosureData, DelegateDispatch, Dispatch, EventChildren, EventQueue, QueueHandle,
+    QueueProxyData, ReadingMode,
 };
 
 /// Generated protocol definitions
@@ -61,6 +64,30 @@ pub trait Proxy: Sized {
     /// Access the user-data associated with this object
     fn data<U: Send + Sync + 'static>(&self) -> Option<&U>;
 
+    /// Access the connection this object belongs to
+    fn connection(&self) -> &WeakConnection;
+
+    /// Checks if this object is still alive
+    ///
+    /// This returns `false` once the object has been destroyed, or if its connection has been dropped.
+    fn is_alive(&self) -> bool {
+        match self.connection().upgrade() {
+            Some(conn) => conn.handle().object_info(self.id()).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Create a weak handle to this object
+    ///
+    /// See [`Weak`] for details.
+    fn downgrade(&self) -> Weak<Self> {
+        Weak {
+            id: self.id(),
+            connection: self.connection().clone(),
+            _iface: std::marker::PhantomData,
+        }
+    }
+
     /// Create an object proxy from its ID
     ///
     /// Returns an error this the provided object ID does not correspond to
@@ -87,7 +114,149 @@ pub trait Proxy: Sized {
         &self,
         conn: &mut ConnectionHandle,
         req: Self::Request,
-    ) -> Result<Message<ObjectId>, InvalidId>;
+    ) -> Result<Message<ObjectId>, RequestError>;
+
+    /// Send a request for this object
+    ///
+    /// The request is sent through the connection this object belongs to, this is what the request methods
+    /// of the generated types use. An error is returned if this connection has been dropped or if the
+    /// object is no longer alive.
+    fn send_request(&self, req: Self::Request) -> Result<(), RequestError> {
+        let conn = self.connection().upgrade().ok_or(RequestError::Disconnected)?;
+        let mut handle = conn.handle();
+        handle.send_request(self, req, None).map(|_| ())
+    }
+
+    /// Send a request creating an object for this object
+    ///
+    /// This is similar to [`send_request()`](Proxy::send_request), for requests creating a new object
+    /// of interface `I`, whose object data is given by `data`. It can be created with
+    /// [`QueueHandle::make_data()`].
+    fn send_constructor<I: Proxy>(
+        &self,
+        req: Self::Request,
+        data: Arc<dyn ObjectData>,
+    ) -> Result<I, RequestError> {
+        let conn = self.connection().upgrade().ok_or(RequestError::Disconnected)?;
+        let mut handle = conn.handle();
+        let id = handle.send_request(self, req, Some(data))?;
+        Ok(I::from_id(&mut handle, id)?)
+    }
+}
+
+/// A weak handle to a Wayland object
+///
+/// This handle does not keep the user data of the object alive, and can be upgraded back into a proxy
+/// as long as the object has not been destroyed. It can be compared and hashed like the proxy it was
+/// created from.
+pub struct Weak<I> {
+    id: ObjectId,
+    connection: WeakConnection,
+    _iface: std::marker::PhantomData<fn() -> I>,
+}
+
+impl<I: Proxy> Weak<I> {
+    /// Try to upgrade this handle into the proxy of the object
+    ///
+    /// Returns an error if the object has been destroyed, or if its connection has been dropped.
+    pub fn upgrade(&self) -> Result<I, InvalidId> {
+        let conn = self.connection.upgrade().ok_or(InvalidId)?;
+        let mut handle = conn.handle();
+        // the object may have been destroyed while its id is still in the map
+        handle.object_info(self.id.clone())?;
+        I::from_id(&mut handle, self.id.clone())
+    }
+
+    /// Checks if the object is still alive
+    pub fn is_alive(&self) -> bool {
+        match self.connection.upgrade() {
+            Some(conn) => conn.handle().object_info(self.id.clone()).is_ok(),
+            None => false,
+        }
+    }
+
+    /// The ID of the object
+    pub fn id(&self) -> ObjectId {
+        self.id.clone()
+    }
+}
+
+impl<I> Clone for Weak<I> {
+    fn clone(&self) -> Self {
+        Weak {
+            id: self.id.clone(),
+            connection: self.connection.clone(),
+            _iface: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<I> PartialEq for Weak<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<I> Eq for Weak<I> {}
+
+impl<I: Proxy> PartialEq<I> for Weak<I> {
+    fn eq(&self, other: &I) -> bool {
+        self.id == other.id()
+    }
+}
+
+impl<I> std::hash::Hash for Weak<I> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<I> std::fmt::Debug for Weak<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Weak").field(&self.id).finish()
+    }
+}
+
+/// Error when sending a request
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum RequestError {
+    /// An object used by the request is not valid, or no longer alive
+    #[error(transparent)]
+    InvalidId(#[from] InvalidId),
+    /// The connection the object belongs to has been dropped
+    #[error("The Wayland connection has been dropped")]
+    Disconnected,
+    /// The value of an enum argument is not supported by the version of the object
+    #[error("Value {value} of argument {arg} of {interface}.{request} requires version {since}, but the object has version {version}")]
+    UnsupportedValue {
+        /// The interface of the object
+        interface: &'static str,
+        /// The name of the request
+        request: &'static str,
+        /// The name of the argument
+        arg: &'static str,
+        /// The numeric value of the argument
+        value: u32,
+        /// The version of the interface since which the value is supported
+        since: u32,
+        /// The version of the object
+        version: u32,
+    },
+}
+
+/// Trait implemented by the `Error` enums of the interfaces
+///
+/// It allows decoding a [`ProtocolError`](backend::protocol::ProtocolError) into the error enum of
+/// the interface that raised it, see [`Connection::protocol_error_as()`].
+pub trait InterfaceError: Sized + Copy + std::fmt::Debug {
+    /// The interface this error enum belongs to
+    fn interface() -> &'static Interface;
+
+    /// Convert a raw error code into this enum, if it is valid
+    fn from_code(code: u32) -> Option<Self>;
+
+    /// A short description of this error
+    fn description(&self) -> &'static str;
 }
 
 /// Wayland dispatching error
@@ -104,4 +273,10 @@ pub enum DispatchError {
     /// The backend generated an error
     #[error("Backend error: {0}")]
     Backend(#[from] WaylandError),
+    /// The timeout of a blocking method expired
+    #[error("Timed out waiting for the server")]
+    Timeout,
+    /// A request could not be sent
+    #[error(transparent)]
+    Request(#[from] RequestError),
 }
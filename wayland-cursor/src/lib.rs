@@ -38,8 +38,8 @@
 //!     // Here, we obtain the right cursor frame...
 //!     let buffer = &cursor[fr_info.frame_index];
 //!     // and attach it to a wl_surface.
-//!     cursor_surface.attach(&mut connection.handle(), Some(&buffer), 0, 0);
-//!     cursor_surface.commit(&mut connection.handle());
+//!     cursor_surface.attach(Some(&buffer), 0, 0);
+//!     cursor_surface.commit();
 //!
 //!     sleep(Duration::from_millis(fr_info.frame_duration as u64));
 //! }
@@ -142,11 +142,14 @@ impl CursorTheme {
         // Flush to ensure the compositor has access to the buffer when it tries to map it.
         file.flush().expect("Flush on shm fd failed");
 
-        let pool_id = conn.send_request(
-            &shm,
-            wl_shm::Request::CreatePool { fd: file.as_raw_fd(), size: INITIAL_POOL_SIZE },
-            Some(Arc::new(IgnoreObjectData)),
-        )?;
+        // this request has no enum argument, so it can only fail because of an invalid id
+        let pool_id = conn
+            .send_request(
+                &shm,
+                wl_shm::Request::CreatePool { fd: file.as_raw_fd(), size: INITIAL_POOL_SIZE },
+                Some(Arc::new(IgnoreObjectData)),
+            )
+            .map_err(|_| InvalidId)?;
         let pool = WlShmPool::from_id(conn, pool_id)?;
 
         let name = String::from(name);
@@ -199,10 +202,10 @@ impl CursorTheme {
     /// Grow the wl_shm_pool this theme is stored on.
     ///
     /// This method does nothing if the provided size is smaller or equal to the pool's current size.
-    fn grow(&mut self, conn: &mut ConnectionHandle, size: i32) {
+    fn grow(&mut self, size: i32) {
         if size > self.pool_size {
             self.file.set_len(size as u64).expect("Failed to set new buffer length");
-            self.pool.resize(conn, size);
+            let _ = self.pool.resize(size);
             self.pool_size = size;
         }
     }
@@ -313,7 +316,7 @@ impl CursorImageBuffer {
 
         // Resize memory before writing to it to handle shm correctly.
         let new_size = offset + buf.len() as u64;
-        theme.grow(conn, new_size as i32);
+        theme.grow(new_size as i32);
 
         theme.file.write_all(buf).unwrap();
 
@@ -440,5 +443,5 @@ impl ObjectData for IgnoreObjectData {
     ) -> Option<Arc<dyn ObjectData>> {
         None
     }
-    fn destroyed(&self, _: wayland_client::backend::ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: wayland_client::backend::ObjectId) {}
 }
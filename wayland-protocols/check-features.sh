@@ -0,0 +1,23 @@
+#!/bin/sh
+# Check that each protocol feature of wayland-protocols builds in isolation
+#
+# Usage: ./check-features.sh [feature...]
+#
+# Without arguments all the protocol features are checked, the grouping features (`default`, `client`,
+# `server` and `*_protocols`) being skipped. Requires `jq`.
+set -e
+
+cd "$(dirname "$0")"
+
+if [ $# -gt 0 ]; then
+    features="$*"
+else
+    features=$(cargo metadata --no-deps --format-version 1 \
+        | jq -r '.packages[] | select(.name == "wayland-protocols") | .features | keys[]' \
+        | grep -v -x -e default -e client -e server -e 'wayland-.*' -e '.*_protocols')
+fi
+
+for feature in $features; do
+    echo "Checking feature $feature"
+    cargo check -p wayland-protocols --no-default-features --features "client server $feature"
+done
@@ -7,9 +7,24 @@
 //! protocol. The creation of these modules (and the dependency on the associated crate) is
 //! controlled by the two cargo features `client` and `server`.
 //!
-//! The cargo feature `unstable_protocols` adds an `unstable` module, containing bindings
-//! to protocols that are not yet considered stable. As such, no stability guarantee is
-//! given for these protocols.
+//! Each protocol (or family of versions of a protocol) is enabled by its own cargo feature, named
+//! after its module (the unstable `xdg_shell` versions and the wlr protocols are respectively
+//! behind the `xdg_shell_unstable` and `wlr_*` features). The features of the protocols another
+//! protocol depends on are enabled along with it:
+//!
+//! - `ext_image_copy_capture` enables `ext_image_capture_source`, which enables
+//!   `ext_foreign_toplevel_list`
+//! - `cursor_shape` enables `tablet`
+//! - `input_method_v2` enables `text_input`
+//! - `xdg_decoration`, `xdg_toplevel_icon` and `wlr_layer_shell` enable `xdg_shell`
+//!
+//! The features `stable_protocols` (enabled by default), `staging_protocols` and `unstable_protocols`
+//! enable whole groups of protocols. For compatibility `unstable_protocols` also enables the
+//! `xdg_activation` and `drm_lease` staging protocols, which it used to provide, but not the other
+//! ones.
+//!
+//! The protocols of the `unstable` module are not yet considered stable. As such, no stability
+//! guarantee is given for these protocols.
 //!
 //! Some protocols require unstable rust features, the inclusion of them is controlled
 //! by the cargo feature `nightly`.
@@ -19,10 +34,7 @@
 #[macro_use]
 mod protocol_macro;
 
-#[cfg(feature = "unstable_protocols")]
 pub mod staging;
-
-#[cfg(feature = "unstable_protocols")]
 pub mod unstable;
 
 pub mod misc;
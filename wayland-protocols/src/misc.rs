@@ -11,6 +11,7 @@
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
+#[cfg(feature = "gtk_primary_selection")]
 pub mod gtk_primary_selection {
     //! Gtk primary selection protocol
     //!
@@ -59,7 +60,7 @@ pub mod gtk_primary_selection {
     wayland_protocol!("./misc/gtk-primary-selection.xml", []);
 }
 
-#[cfg(feature = "unstable_protocols")]
+#[cfg(feature = "input_method_v2")]
 pub mod zwp_input_method_v2 {
     //! Input method v2 unstable
     //! 
@@ -81,10 +82,17 @@ pub mod zwp_input_method_v2 {
     //! version number in the protocol and interface names are removed and the
     //! interface version number is reset.
 
-    wayland_protocol!("./misc/input-method-unstable-v2.xml", [crate::unstable::text_input::v3]);
+    wayland_protocol!(
+        "./misc/input-method-unstable-v2.xml",
+        [
+            "./protocols/unstable/text-input/text-input-unstable-v3.xml"
+                => crate::unstable::text_input::v3
+        ]
+    );
 }
 
-pub mod server_decoration{
+#[cfg(feature = "server_decoration")]
+pub mod server_decoration {
     //! KDE server decoration protocol
     //!
     //! This interface allows to coordinate whether the server should create
@@ -1,5 +1,5 @@
 macro_rules! wayland_protocol(
-    ($path:expr, [$($imports:path),*]) => {
+    ($path:expr, [$($dep_path:literal => $($dep_mod:ident)::+),* $(,)?]) => {
         #[cfg(feature = "client")]
         pub use self::generated::client;
 
@@ -16,16 +16,20 @@ macro_rules! wayland_protocol(
                 //! Client-side API of this protocol
                 use wayland_client;
                 use wayland_client::protocol::*;
-                $(use $imports::{client::*};)*
 
                 pub mod __interfaces {
                     use wayland_client::protocol::__interfaces::*;
-                    $(use $imports::{client::__interfaces::*};)*
-                    wayland_scanner::generate_interfaces!($path);
+                    wayland_scanner::generate_interfaces!(
+                        $path,
+                        [$($dep_path => $($dep_mod)::+::client),*]
+                    );
                 }
                 use self::__interfaces::*;
 
-                wayland_scanner::generate_client_code!($path);
+                wayland_scanner::generate_client_code!(
+                    $path,
+                    [$($dep_path => $($dep_mod)::+::client),*]
+                );
             }
 
             #[cfg(feature = "server")]
@@ -33,16 +37,20 @@ macro_rules! wayland_protocol(
                 //! Server-side API of this protocol
                 use wayland_server;
                 use wayland_server::protocol::*;
-                $(use $imports::{server::*};)*
 
                 pub mod __interfaces {
                     use wayland_server::protocol::__interfaces::*;
-                    $(use $imports::{server::__interfaces::*};)*
-                    wayland_scanner::generate_interfaces!($path);
+                    wayland_scanner::generate_interfaces!(
+                        $path,
+                        [$($dep_path => $($dep_mod)::+::server),*]
+                    );
                 }
                 use self::__interfaces::*;
 
-                wayland_scanner::generate_server_code!($path);
+                wayland_scanner::generate_server_code!(
+                    $path,
+                    [$($dep_path => $($dep_mod)::+::server),*]
+                );
             }
         }
     }
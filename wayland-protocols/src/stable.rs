@@ -1,5 +1,6 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
+#[cfg(feature = "presentation_time")]
 pub mod presentation_time {
     //! Presentation time protocol
     //!
@@ -11,6 +12,7 @@ pub mod presentation_time {
     );
 }
 
+#[cfg(feature = "xdg_shell")]
 pub mod xdg_shell {
     //! XDG Shell protocol
     //!
@@ -22,6 +24,7 @@ pub mod xdg_shell {
     );
 }
 
+#[cfg(feature = "viewporter")]
 pub mod viewporter {
     //! Viewporter protocol
     //!
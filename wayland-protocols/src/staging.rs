@@ -11,6 +11,7 @@
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
+#[cfg(feature = "xdg_activation")]
 pub mod xdg_activation {
     //! The way for a client to pass focus to another toplevel is as follows.
     //!
@@ -41,6 +42,7 @@ pub mod xdg_activation {
     }
 }
 
+#[cfg(feature = "drm_lease")]
 pub mod drm_lease {
     //! This protocol is used by Wayland compositors which act as Direct
     //! Renderering Manager (DRM) masters to lease DRM resources to Wayland
@@ -76,3 +78,294 @@ pub mod drm_lease {
         );
     }
 }
+
+#[cfg(feature = "ext_session_lock")]
+pub mod ext_session_lock {
+    //! This protocol allows for a privileged Wayland client to lock the session
+    //! and display arbitrary graphics while the session is locked.
+    //!
+    //! The compositor may choose to restrict this protocol to a special client
+    //! launched by the compositor itself or expose it to all privileged clients,
+    //! this is compositor policy.
+    //!
+    //! The client is responsible for performing authentication and informing the
+    //! compositor when the session should be unlocked. If the client dies while
+    //! the session is locked the session remains locked, possibly permanently
+    //! depending on compositor policy.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-session-lock/ext-session-lock-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "ext_idle_notify")]
+pub mod ext_idle_notify {
+    //! This protocol allows clients to monitor user idle status.
+    //!
+    //! After being notified about idleness, clients are notified again when
+    //! the user resumes activity.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-idle-notify/ext-idle-notify-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "ext_foreign_toplevel_list")]
+pub mod ext_foreign_toplevel_list {
+    //! The purpose of this protocol is to provide protocol object handles for
+    //! toplevels, possibly originating from another client.
+    //!
+    //! This protocol is intentionally minimalistic and expects additional
+    //! functionality (e.g. creating a screencopy source from a toplevel handle,
+    //! getting information about the state of the toplevel) to be implemented
+    //! in extension protocols.
+    //!
+    //! The compositor may choose to restrict this protocol to a special client
+    //! launched by the compositor itself or expose it to all clients,
+    //! this is compositor policy.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-foreign-toplevel-list/ext-foreign-toplevel-list-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "ext_transient_seat")]
+pub mod ext_transient_seat {
+    //! The transient seat protocol can be used by privileged clients to create
+    //! independent seats that will be removed from the compositor when the client
+    //! destroys its transient seat.
+    //!
+    //! This protocol is intended for use with virtual input protocols such as
+    //! "virtual_keyboard_unstable_v1" or "wlr_virtual_pointer_unstable_v1", both
+    //! of which allow the user to select a seat.
+    //!
+    //! The "wl_seat" global created by this protocol does not generate input events
+    //! on its own, or have any capabilities except those assigned to it by other
+    //! protocol extensions, such as the ones mentioned above.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-transient-seat/ext-transient-seat-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "ext_image_capture_source")]
+pub mod ext_image_capture_source {
+    //! This protocol serves as an intermediary between capturing protocols and
+    //! potential image capture sources such as outputs and toplevels.
+    //!
+    //! This protocol may be extended to support more image capture sources in the
+    //! future, thereby adding those image capture sources to other protocols that
+    //! use the image capture source object without having to modify those
+    //! protocols.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-image-capture-source/ext-image-capture-source-v1.xml",
+            [
+                "./protocols/staging/ext-foreign-toplevel-list/ext-foreign-toplevel-list-v1.xml"
+                    => crate::staging::ext_foreign_toplevel_list::v1
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "ext_image_copy_capture")]
+pub mod ext_image_copy_capture {
+    //! This protocol allows clients to ask the compositor to capture image sources
+    //! such as outputs and toplevels into user submitted buffers.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-image-copy-capture/ext-image-copy-capture-v1.xml",
+            [
+                "./protocols/staging/ext-image-capture-source/ext-image-capture-source-v1.xml"
+                    => crate::staging::ext_image_capture_source::v1
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "ext_data_control")]
+pub mod ext_data_control {
+    //! This protocol allows a privileged client to control data devices. In
+    //! particular, the client will be able to manage the current selection and take
+    //! the role of a clipboard manager.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/ext-data-control/ext-data-control-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "fractional_scale")]
+pub mod fractional_scale {
+    //! This protocol allows a compositor to suggest for surfaces to render at
+    //! fractional scales.
+    //!
+    //! A client can submit scaled content by utilizing wp_viewport. This is done by
+    //! creating a wp_viewport object for the surface and setting the destination
+    //! rectangle to the surface size before the scale factor is applied.
+    //!
+    //! The buffer size is calculated by multiplying the surface size by the
+    //! intended scale.
+    //!
+    //! The wl_surface buffer scale should remain set to 1.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/fractional-scale/fractional-scale-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "cursor_shape")]
+pub mod cursor_shape {
+    //! This protocol extension offers a simpler way for clients to set a cursor.
+    //!
+    //! The cursor shape device can be obtained from a wl_pointer or from a
+    //! zwp_tablet_tool_v2, the latter depending on the `tablet` module.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/cursor-shape/cursor-shape-v1.xml",
+            [
+                "./protocols/unstable/tablet/tablet-unstable-v2.xml"
+                    => crate::unstable::tablet::v2
+            ]
+        );
+    }
+}
+
+#[cfg(feature = "content_type")]
+pub mod content_type {
+    //! This protocol allows a client to describe the kind of content a surface
+    //! will display, to allow the compositor to optimize its behavior for it.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/content-type/content-type-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "tearing_control")]
+pub mod tearing_control {
+    //! For some use cases like games or drawing tablets it can make sense to
+    //! reduce latency by accepting tearing with the use of asynchronous page
+    //! flips. This global is a factory interface, allowing clients to inform
+    //! which type of presentation the content of their surfaces is suitable for.
+    //!
+    //! Graphics APIs like EGL or Vulkan, that manage the buffer queue and commits
+    //! of a wl_surface themselves, are likely to be using this extension
+    //! internally. If a client is using such an API for a wl_surface, it should
+    //! not directly use this extension on that surface, to avoid raising a
+    //! tearing_control_exists protocol error.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/tearing-control/tearing-control-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "single_pixel_buffer")]
+pub mod single_pixel_buffer {
+    //! This protocol extension allows clients to create single-pixel buffers.
+    //!
+    //! Compositors supporting this protocol extension should also support the
+    //! viewporter protocol extension. Clients may use viewporter to scale a
+    //! single-pixel buffer to a desired size.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/single-pixel-buffer/single-pixel-buffer-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "alpha_modifier")]
+pub mod alpha_modifier {
+    //! This interface allows a client to set a factor for the alpha values on a
+    //! surface, which can be used to offload such operations from the client to
+    //! the compositor, which can in turn offload them to KMS.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/alpha-modifier/alpha-modifier-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "linux_drm_syncobj")]
+pub mod linux_drm_syncobj {
+    //! This protocol allows clients to request explicit synchronization for
+    //! buffers. It is tied to the Linux DRM synchronization object framework.
+    //!
+    //! Synchronization refers to co-ordination of pipelined operations performed
+    //! on buffers. Most GPU clients will schedule an asynchronous operation to
+    //! render to the buffer, then immediately send the buffer to the compositor
+    //! to be attached to a surface.
+    //!
+    //! With explicit synchronization, an acquire timeline point is attached to
+    //! each buffer commit, signalling when the client is done with the buffer,
+    //! and a release timeline point signals when the compositor is done with it.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/linux-drm-syncobj/linux-drm-syncobj-v1.xml",
+            []
+        );
+    }
+}
+
+#[cfg(feature = "xdg_toplevel_icon")]
+pub mod xdg_toplevel_icon {
+    //! This protocol allows clients to set icons for their toplevel surfaces
+    //! either via the XDG icon stock (using an icon name), or from pixel data.
+    //!
+    //! A toplevel icon represents the individual toplevel (unlike the application
+    //! or launcher icon, which represents the application as a whole), and may be
+    //! shown in window switchers, window overviews and taskbars that list
+    //! individual windows.
+
+    #[allow(missing_docs)]
+    pub mod v1 {
+        wayland_protocol!(
+            "./protocols/staging/xdg-toplevel-icon/xdg-toplevel-icon-v1.xml",
+            ["./protocols/stable/xdg-shell/xdg-shell.xml" => crate::xdg_shell]
+        );
+    }
+}
@@ -16,6 +16,7 @@
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
+#[cfg(feature = "fullscreen_shell")]
 pub mod fullscreen_shell {
     //! Fullscreen shell protocol
 
@@ -28,6 +29,7 @@ pub mod fullscreen_shell {
     }
 }
 
+#[cfg(feature = "idle_inhibit")]
 pub mod idle_inhibit {
     //! Screensaver inhibition protocol
 
@@ -40,6 +42,7 @@ pub mod idle_inhibit {
     }
 }
 
+#[cfg(feature = "input_method")]
 pub mod input_method {
     //! Input method protocol
 
@@ -52,6 +55,7 @@ pub mod input_method {
     }
 }
 
+#[cfg(feature = "input_timestamps")]
 pub mod input_timestamps {
     //! Input timestamps protocol
 
@@ -64,6 +68,7 @@ pub mod input_timestamps {
     }
 }
 
+#[cfg(feature = "keyboard_shortcuts_inhibit")]
 pub mod keyboard_shortcuts_inhibit {
     //! Protocol for inhibiting the compositor keyboard shortcuts
     //!
@@ -80,6 +85,7 @@ pub mod keyboard_shortcuts_inhibit {
     }
 }
 
+#[cfg(feature = "linux_dmabuf")]
 pub mod linux_dmabuf {
     //! Linux DMA-BUF protocol
 
@@ -92,6 +98,7 @@ pub mod linux_dmabuf {
     }
 }
 
+#[cfg(feature = "linux_explicit_synchronization")]
 pub mod linux_explicit_synchronization {
     //! Linux explicit synchronization protocol
 
@@ -104,6 +111,7 @@ pub mod linux_explicit_synchronization {
     }
 }
 
+#[cfg(feature = "pointer_constraints")]
 pub mod pointer_constraints {
     //! protocol for constraining pointer motions
     //!
@@ -126,6 +134,7 @@ pub mod pointer_constraints {
     }
 }
 
+#[cfg(feature = "pointer_gestures")]
 pub mod pointer_gestures {
     //! Pointer gestures protocol
 
@@ -138,6 +147,7 @@ pub mod pointer_gestures {
     }
 }
 
+#[cfg(feature = "primary_selection")]
 pub mod primary_selection {
     //! Primary selection protocol
 
@@ -150,6 +160,7 @@ pub mod primary_selection {
     }
 }
 
+#[cfg(feature = "relative_pointer")]
 pub mod relative_pointer {
     //! protocol for relative pointer motion events
     //!
@@ -176,6 +187,7 @@ pub mod relative_pointer {
     }
 }
 
+#[cfg(feature = "tablet")]
 pub mod tablet {
     //! Wayland protocol for graphics tablets
     //!
@@ -273,6 +285,7 @@ pub mod tablet {
     }
 }
 
+#[cfg(feature = "text_input")]
 pub mod text_input {
     //! Text input protocol
 
@@ -293,6 +306,7 @@ pub mod text_input {
     }
 }
 
+#[cfg(feature = "xdg_decoration")]
 pub mod xdg_decoration {
     //! This interface allows a compositor to announce support for server-side
     //! decorations.
@@ -312,11 +326,12 @@ pub mod xdg_decoration {
     pub mod v1 {
         wayland_protocol!(
             "./protocols/unstable/xdg-decoration/xdg-decoration-unstable-v1.xml",
-            [crate::xdg_shell]
+            ["./protocols/stable/xdg-shell/xdg-shell.xml" => crate::xdg_shell]
         );
     }
 }
 
+#[cfg(feature = "xdg_foreign")]
 pub mod xdg_foreign {
     //! Protocol for exporting xdg surface handles
     //!
@@ -358,6 +373,7 @@ pub mod xdg_foreign {
     }
 }
 
+#[cfg(feature = "xdg_output")]
 pub mod xdg_output {
     //! Protocol to describe output regions
     //!
@@ -387,6 +403,7 @@ pub mod xdg_output {
     }
 }
 
+#[cfg(feature = "xdg_shell_unstable")]
 pub mod xdg_shell {
     //! XDG Shell protocol
     //!
@@ -412,6 +429,7 @@ pub mod xdg_shell {
     }
 }
 
+#[cfg(feature = "xwayland_keyboard_grab")]
 pub mod xwayland_keyboard_grab {
     //! Protocol for grabbing the keyboard from Xwayland
     //!
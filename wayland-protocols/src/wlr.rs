@@ -5,7 +5,6 @@
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
-#[cfg(feature = "unstable_protocols")]
 pub mod unstable {
     //! Unstable protocols from wlr-protocols
     //!
@@ -21,6 +20,7 @@ pub mod unstable {
     //! interface names are removed and the interface version number is
     //! reset.
 
+    #[cfg(feature = "wlr_data_control")]
     pub mod data_control {
         //! Control data devices, particularly the clipboard.
         //!
@@ -36,6 +36,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_export_dmabuf")]
     pub mod export_dmabuf {
         //! A protocol for low overhead screen content capturing
         //!
@@ -50,6 +51,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_foreign_toplevel")]
     pub mod foreign_toplevel {
         //! List and control opened apps
         //!
@@ -64,6 +66,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_gamma_control")]
     pub mod gamma_control {
         //! Manage gamma tables of outputs.
         //!
@@ -78,6 +81,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_input_inhibitor")]
     pub mod input_inhibitor {
         //! Inhibits input events to other clients
 
@@ -90,6 +94,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_layer_shell")]
     pub mod layer_shell {
         //! Layered shell protocol
 
@@ -97,11 +102,12 @@ pub mod unstable {
         pub mod v1 {
             wayland_protocol!(
                 "./wlr-protocols/unstable/wlr-layer-shell-unstable-v1.xml",
-                [crate::xdg_shell]
+                ["./protocols/stable/xdg-shell/xdg-shell.xml" => crate::xdg_shell]
             );
         }
     }
 
+    #[cfg(feature = "wlr_output_management")]
     pub mod output_management {
         //! Output management protocol
         //!
@@ -116,6 +122,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_output_power_management")]
     pub mod output_power_management {
         //! Output power management protocol
         //!
@@ -133,6 +140,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_screencopy")]
     pub mod screencopy {
         //! Screen content capturing on client buffers
         //!
@@ -148,6 +156,7 @@ pub mod unstable {
         }
     }
 
+    #[cfg(feature = "wlr_virtual_pointer")]
     pub mod virtual_pointer {
         //! Virtual pointer protocol
         //!
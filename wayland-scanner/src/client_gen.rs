@@ -4,7 +4,7 @@ use quote::{format_ident, quote};
 
 use crate::{
     protocol::{Interface, Protocol, Type},
-    util::{dotted_to_relname, is_keyword, snake_to_camel},
+    util::{deprecated_attr, dotted_to_relname, is_keyword, snake_to_camel, to_doc_attr},
     Side,
 };
 
@@ -20,6 +20,7 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
 
     let enums = crate::common::generate_enums_for(interface);
     let sinces = crate::common::gen_since_constants(&interface.requests, &interface.events);
+    let tables = crate::common::gen_message_tables(&interface.requests, &interface.events);
 
     let requests = crate::common::gen_message_enum(
         &format_ident!("Request"),
@@ -37,6 +38,28 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
     let parse_body = crate::common::gen_parse_body(interface, Side::Client);
     let write_body = crate::common::gen_write_body(interface, Side::Client);
     let methods = gen_methods(interface);
+    let event_children = gen_event_children(interface, &iface_name);
+
+    let error_impl = interface.enums.iter().find(|e| e.name == "error" && !e.bitfield).map(|_| {
+        quote! {
+            impl super::wayland_client::InterfaceError for Error {
+                #[inline]
+                fn interface() -> &'static Interface {
+                    &super::#iface_const_name
+                }
+
+                #[inline]
+                fn from_code(code: u32) -> Option<Error> {
+                    <Error as std::convert::TryFrom<u32>>::try_from(code).ok()
+                }
+
+                #[inline]
+                fn description(&self) -> &'static str {
+                    Error::description(self)
+                }
+            }
+        }
+    });
 
     quote! {
         #mod_doc
@@ -44,12 +67,15 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
             use std::sync::Arc;
 
             use super::wayland_client::{
-                backend::{smallvec, ObjectData, ObjectId, InvalidId, protocol::{WEnum, Argument, Message, Interface, same_interface}},
-                QueueProxyData, Proxy, ConnectionHandle, Dispatch, QueueHandle, DispatchError
+                backend::{smallvec, ObjectData, ObjectId, InvalidId, protocol::{WEnum, Argument, Message, MessageInfo, Interface, same_interface}},
+                QueueProxyData, Proxy, ConnectionHandle, Dispatch, EventChildren, QueueHandle, DispatchError,
+                RequestError, WeakConnection
             };
 
             #enums
+            #error_impl
             #sinces
+            #tables
             #requests
             #events
 
@@ -58,6 +84,7 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
                 id: ObjectId,
                 version: u32,
                 data: Option<Arc<dyn ObjectData>>,
+                backend: WeakConnection,
             }
 
             impl std::cmp::PartialEq for #iface_name {
@@ -68,6 +95,12 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
 
             impl std::cmp::Eq for #iface_name {}
 
+            impl std::hash::Hash for #iface_name {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.id.hash(state)
+                }
+            }
+
             impl super::wayland_client::Proxy for #iface_name {
                 type Request = Request;
                 type Event = Event;
@@ -92,6 +125,11 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
                     self.data.as_ref().and_then(|arc| (&**arc).downcast_ref::<QueueProxyData<Self, U>>()).map(|data| &data.udata)
                 }
 
+                #[inline]
+                fn connection(&self) -> &WeakConnection {
+                    &self.backend
+                }
+
                 #[inline]
                 fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
                     if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
@@ -99,18 +137,21 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
                     }
                     let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
                     let data = conn.get_object_data(id.clone()).ok();
-                    Ok(#iface_name { id, data, version })
+                    let backend = conn.downgrade();
+                    Ok(#iface_name { id, data, version, backend })
                 }
 
                 fn parse_event(conn: &mut ConnectionHandle, msg: Message<ObjectId>) -> Result<(Self, Self::Event), DispatchError> {
                     #parse_body
                 }
 
-                fn write_request(&self, conn: &mut ConnectionHandle, msg: Self::Request) -> Result<Message<ObjectId>, InvalidId> {
+                fn write_request(&self, conn: &mut ConnectionHandle, msg: Self::Request) -> Result<Message<ObjectId>, RequestError> {
                     #write_body
                 }
             }
 
+            #event_children
+
             impl #iface_name {
                 #methods
             }
@@ -118,11 +159,93 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
     }
 }
 
+fn gen_event_children(interface: &Interface, iface_name: &Ident) -> TokenStream {
+    let creating_events = interface
+        .events
+        .iter()
+        .enumerate()
+        .filter_map(|(opcode, evt)| {
+            evt.args
+                .iter()
+                .find(|arg| arg.typ == Type::NewId)
+                .and_then(|arg| arg.interface.as_ref())
+                .map(|child| (opcode as u16, evt, child))
+        })
+        .collect::<Vec<_>>();
+
+    if creating_events.is_empty() {
+        // no object is ever created by the events of this interface
+        return quote! {
+            impl<D, U> EventChildren<D, U> for #iface_name {
+                #[inline]
+                fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+                    None
+                }
+            }
+        };
+    }
+
+    let mut data_types = Vec::new();
+    let mut bounds = Vec::new();
+    let mut match_arms = Vec::new();
+
+    for (opcode, evt, child) in creating_events {
+        let child_mod = Ident::new(child, Span::call_site());
+        let child_type = Ident::new(&snake_to_camel(child), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&evt.name));
+        let method_name =
+            format_ident!("{}{}", if is_keyword(&evt.name) { "_" } else { "" }, evt.name);
+        let type_doc =
+            format!("User data of the `{}` objects created by the `{}` event", child, evt.name);
+        let method_doc = format!(
+            "Create the user data of a `{}` object created by the `{}` event",
+            child, evt.name
+        );
+
+        data_types.push(quote! {
+            #[doc = #type_doc]
+            type #data_type: Send + Sync + 'static;
+            #[doc = #method_doc]
+            fn #method_name(&self) -> Self::#data_type;
+        });
+        bounds.push(quote! {
+            D: Dispatch<super::#child_mod::#child_type, <U as ChildrenData<D>>::#data_type>,
+            super::#child_mod::#child_type: EventChildren<D, <U as ChildrenData<D>>::#data_type>,
+        });
+        match_arms.push(quote! {
+            #opcode => Some(qhandle.make_data::<super::#child_mod::#child_type, _>(parent_data.#method_name())),
+        });
+    }
+
+    quote! {
+        /// User data of the objects created by the events of this interface
+        ///
+        /// This trait must be implemented by the user data type of the objects of this interface, to
+        /// provide the user data of each object created by its events.
+        pub trait ChildrenData<D> {
+            #(#data_types)*
+        }
+
+        impl<D: 'static, U: ChildrenData<D>> EventChildren<D, U> for #iface_name
+        where
+            #(#bounds)*
+        {
+            fn event_created_child(opcode: u16, parent_data: &U, qhandle: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+                match opcode {
+                    #(#match_arms)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
 fn gen_methods(interface: &Interface) -> TokenStream {
     interface.requests.iter().map(|request| {
         let created_interface = request.args.iter().find(|arg| arg.typ == Type::NewId).map(|arg| &arg.interface);
 
         let method_name = format_ident!("{}{}", if is_keyword(&request.name) { "_" } else { "" }, request.name);
+        let handle_method_name = format_ident!("{}_with_handle", request.name);
         let enum_variant = Ident::new(&snake_to_camel(&request.name), Span::call_site());
 
         let fn_args = request.args.iter().flat_map(|arg| {
@@ -189,54 +312,99 @@ fn gen_methods(interface: &Interface) -> TokenStream {
             }
         });
 
+        // both the method and its handle-based variant use the arguments
+        let fn_args = fn_args.collect::<Vec<_>>();
+        let enum_args = enum_args.collect::<Vec<_>>();
+        let deprecated_attr = deprecated_attr(request.deprecated_since);
+
         match created_interface {
             Some(Some(ref created_interface)) => {
                 // a regular creating request
                 let created_iface_mod = Ident::new(created_interface, Span::call_site());
                 let created_iface_type = Ident::new(&snake_to_camel(created_interface), Span::call_site());
                 quote! {
+                    #deprecated_attr
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #method_name<U: Send + Sync + 'static, D: Dispatch<super::#created_iface_mod::#created_iface_type, U> + 'static>(&self, #(#fn_args,)* qh: &QueueHandle<D>, udata: U) -> Result<super::#created_iface_mod::#created_iface_type, RequestError>
+                    where
+                        super::#created_iface_mod::#created_iface_type: EventChildren<D, U>
+                    {
+                        self.send_constructor(
+                            Request::#enum_variant {
+                                #(#enum_args),*
+                            },
+                            qh.make_data::<super::#created_iface_mod::#created_iface_type, U>(udata)
+                        )
+                    }
+
+                    #deprecated_attr
                     #[allow(clippy::too_many_arguments)]
-                    pub fn #method_name<D: Dispatch<super::#created_iface_mod::#created_iface_type> + 'static>(&self, conn: &mut ConnectionHandle, #(#fn_args,)* qh: &QueueHandle<D>, udata: <D as Dispatch<super::#created_iface_mod::#created_iface_type>>::UserData) -> Result<super::#created_iface_mod::#created_iface_type, InvalidId> {
+                    pub fn #handle_method_name<U: Send + Sync + 'static, D: Dispatch<super::#created_iface_mod::#created_iface_type, U> + 'static>(&self, conn: &mut ConnectionHandle, #(#fn_args,)* qh: &QueueHandle<D>, udata: U) -> Result<super::#created_iface_mod::#created_iface_type, RequestError>
+                    where
+                        super::#created_iface_mod::#created_iface_type: EventChildren<D, U>
+                    {
                         let ret = conn.send_request(
                             self,
                             Request::#enum_variant {
                                 #(#enum_args),*
                             },
-                            Some(qh.make_data::<super::#created_iface_mod::#created_iface_type>(udata))
+                            Some(qh.make_data::<super::#created_iface_mod::#created_iface_type, U>(udata))
                         )?;
-                        Proxy::from_id(conn, ret)
+                        Ok(Proxy::from_id(conn, ret)?)
                     }
                 }
             },
             Some(None) => {
                 // a bind-like request
                 quote! {
+                    #deprecated_attr
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #method_name<I: Proxy + EventChildren<D, U> + 'static, U: Send + Sync + 'static, D: Dispatch<I, U> + 'static>(&self, #(#fn_args,)* qh: &QueueHandle<D>, udata: U) -> Result<I, RequestError> {
+                        self.send_constructor(
+                            Request::#enum_variant {
+                                #(#enum_args),*
+                            },
+                            qh.make_data::<I, U>(udata)
+                        )
+                    }
+
+                    #deprecated_attr
                     #[allow(clippy::too_many_arguments)]
-                    pub fn #method_name<I: Proxy + 'static, D: Dispatch<I> + 'static>(&self, conn: &mut ConnectionHandle, #(#fn_args,)* qh: &QueueHandle<D>, udata: <D as Dispatch<I>>::UserData) -> Result<I, InvalidId> {
-                        let placeholder = conn.placeholder_id(Some((I::interface(), version)));
+                    pub fn #handle_method_name<I: Proxy + EventChildren<D, U> + 'static, U: Send + Sync + 'static, D: Dispatch<I, U> + 'static>(&self, conn: &mut ConnectionHandle, #(#fn_args,)* qh: &QueueHandle<D>, udata: U) -> Result<I, RequestError> {
                         let ret = conn.send_request(
                             self,
                             Request::#enum_variant {
                                 #(#enum_args),*
                             },
-                            Some(qh.make_data::<I>(udata))
+                            Some(qh.make_data::<I, U>(udata))
                         )?;
-                        Proxy::from_id(conn, ret)
+                        Ok(Proxy::from_id(conn, ret)?)
                     }
                 }
             },
             None => {
                 // a non-creating request
                 quote! {
+                    #deprecated_attr
                     #[allow(clippy::too_many_arguments)]
-                    pub fn #method_name(&self, conn: &mut ConnectionHandle, #(#fn_args),*) {
-                        let _ = conn.send_request(
+                    pub fn #method_name(&self, #(#fn_args),*) -> Result<(), RequestError> {
+                        self.send_request(
+                            Request::#enum_variant {
+                                #(#enum_args),*
+                            }
+                        )
+                    }
+
+                    #deprecated_attr
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #handle_method_name(&self, conn: &mut ConnectionHandle, #(#fn_args),*) -> Result<(), RequestError> {
+                        conn.send_request(
                             self,
                             Request::#enum_variant {
                                 #(#enum_args),*
                             },
                             None
-                        );
+                        ).map(|_| ())
                     }
                 }
             }
@@ -244,6 +412,222 @@ fn gen_methods(interface: &Interface) -> TokenStream {
     }).collect()
 }
 
+/// Generate a handler trait for all the events of a protocol, and an adapter implementing the
+/// delegation trait of wayland-client from it
+pub fn generate_client_handler(protocol: &Protocol) -> TokenStream {
+    let protocol_name = snake_to_camel(&protocol.name.replace('-', "_"));
+    let handler_name = format_ident!("{}Handler", protocol_name);
+    let adapter_name = format_ident!("{}Adapter", protocol_name);
+    let delegate_macro_name = format_ident!("delegate_{}", protocol.name.replace('-', "_"));
+
+    let interfaces = protocol
+        .interfaces
+        .iter()
+        .filter(|iface| iface.name != "wl_display" && iface.name != "wl_registry")
+        .collect::<Vec<_>>();
+
+    let data_types = interfaces.iter().map(|iface| {
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let doc_attr = to_doc_attr(&format!("User data of the `{}` objects", iface.name));
+        quote! {
+            #doc_attr
+            type #data_type: Send + Sync + 'static;
+        }
+    });
+
+    let event_methods = interfaces.iter().flat_map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        iface.events.iter().map(move |event| {
+            let method_name = format_ident!("{}_{}", iface.name, event.name);
+            let mut docs = format!("The `{}.{}` event", iface.name, event.name);
+            if let Some((ref short, _)) = event.description {
+                docs += &format!("\n\n{}", short);
+            }
+            let doc_attr = to_doc_attr(&docs);
+            let args = event.args.iter().flat_map(|arg| {
+                let arg_name =
+                    format_ident!("{}{}", if is_keyword(&arg.name) { "_" } else { "" }, arg.name);
+                let arg_type = crate::common::handler_arg_type(iface, arg, Side::Client)?;
+                Some(quote! { #arg_name: #arg_type })
+            });
+            quote! {
+                #doc_attr
+                #[allow(clippy::too_many_arguments)]
+                fn #method_name(
+                    &mut self,
+                    proxy: &super::#iface_mod::#iface_name,
+                    #(#args,)*
+                    data: &Self::#data_type,
+                    connhandle: &mut ConnectionHandle,
+                    qhandle: &QueueHandle<Self>,
+                );
+            }
+        })
+    });
+
+    let destroyed_methods = interfaces.iter().map(|iface| {
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let method_name = format_ident!("{}_destroyed", iface.name);
+        let doc_attr = to_doc_attr(&format!(
+            "A `{}` object has been destroyed\n\nBy default this method does nothing.",
+            iface.name
+        ));
+        quote! {
+            #doc_attr
+            fn #method_name(
+                &mut self,
+                _object: ObjectId,
+                _data: &Self::#data_type,
+                _connhandle: &mut ConnectionHandle,
+                _qhandle: &QueueHandle<Self>,
+            ) {}
+        }
+    });
+
+    let dispatch_impls = interfaces.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let destroyed_name = format_ident!("{}_destroyed", iface.name);
+        let arms = iface.events.iter().map(|event| {
+            let method_name = format_ident!("{}_{}", iface.name, event.name);
+            let variant = Ident::new(&snake_to_camel(&event.name), Span::call_site());
+            let fields = event
+                .args
+                .iter()
+                .map(|arg| {
+                    let field_name = format_ident!(
+                        "{}{}",
+                        if is_keyword(&arg.name) { "_" } else { "" },
+                        arg.name
+                    );
+                    let binding = format_ident!("arg_{}", arg.name);
+                    (field_name, binding)
+                })
+                .collect::<Vec<_>>();
+            let patterns = fields.iter().map(|(field, binding)| quote! { #field: #binding });
+            let bindings = fields.iter().map(|(_, binding)| binding);
+            quote! {
+                super::#iface_mod::Event::#variant { #(#patterns,)* } => {
+                    <D as #handler_name>::#method_name(state, proxy, #(#bindings,)* data, connhandle, qhandle)
+                }
+            }
+        });
+        // the arguments are not used when the interface has no event
+        let allow_unused = if iface.events.is_empty() {
+            Some(quote! { #[allow(unused_variables)] })
+        } else {
+            None
+        };
+        quote! {
+            impl<D> DelegateDispatch<super::#iface_mod::#iface_name, <D as #handler_name>::#data_type, D> for #adapter_name<D>
+            where
+                D: Dispatch<super::#iface_mod::#iface_name, <D as #handler_name>::#data_type> + #handler_name,
+            {
+                #allow_unused
+                fn event(
+                    state: &mut D,
+                    proxy: &super::#iface_mod::#iface_name,
+                    event: super::#iface_mod::Event,
+                    data: &<D as #handler_name>::#data_type,
+                    connhandle: &mut ConnectionHandle,
+                    qhandle: &QueueHandle<D>,
+                ) {
+                    match event {
+                        #(#arms)*
+                    }
+                }
+
+                fn destroyed(
+                    state: &mut D,
+                    object: ObjectId,
+                    data: &<D as #handler_name>::#data_type,
+                    connhandle: &mut ConnectionHandle,
+                    qhandle: &QueueHandle<D>,
+                ) {
+                    <D as #handler_name>::#destroyed_name(state, object, data, connhandle, qhandle)
+                }
+            }
+        }
+    });
+
+    let delegate_dispatch_list = interfaces.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        quote! {
+            $($module)::+::#iface_mod::#iface_name: <$dispatch_from as $($module)::+::handler::#handler_name>::#data_type
+        }
+    });
+
+    let handler_doc = to_doc_attr(&format!(
+        "Handler of all the events of the `{name}` protocol\n\n\
+         The user data of the objects of each interface is set by the associated types of this trait. \
+         Its implementation is wired to the [`Dispatch`] implementations of your state by the \
+         `{macro_name}!` macro.",
+        name = protocol.name,
+        macro_name = delegate_macro_name,
+    ));
+    let delegate_macro_doc = to_doc_attr(&format!(
+        "Implements [`Dispatch`] for all the interfaces of the `{name}` protocol by delegating them \
+         to [`{adapter}`]\n\n\
+         The user data of each interface is the matching associated type of the [`{handler}`] \
+         implementation of your state. The second argument is the path of the module in which the \
+         protocol was generated:\n\n\
+         ```ignore\n\
+         protocol::handler::{macro_name}!(State => protocol);\n\
+         ```",
+        name = protocol.name,
+        adapter = adapter_name,
+        handler = handler_name,
+        macro_name = delegate_macro_name,
+    ));
+    let adapter_doc = to_doc_attr(&format!(
+        "Delegate type implementing the events of the `{}` protocol from [`{}`]",
+        protocol.name, handler_name
+    ));
+
+    quote! {
+        pub mod handler {
+            #[allow(unused_imports)]
+            use super::wayland_client::{
+                backend::{ObjectId, protocol::WEnum},
+                ConnectionHandle, DelegateDispatch, Dispatch, QueueHandle,
+            };
+
+            #handler_doc
+            pub trait #handler_name: Sized {
+                #(#data_types)*
+                #(#event_methods)*
+                #(#destroyed_methods)*
+            }
+
+            #adapter_doc
+            #[derive(Debug)]
+            pub struct #adapter_name<D>(::std::marker::PhantomData<D>);
+
+            #(#dispatch_impls)*
+
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use super::wayland_client::delegate_dispatch as __delegate_dispatch;
+
+            #delegate_macro_doc
+            macro_rules! #delegate_macro_name {
+                ($dispatch_from: ty => $($module: ident)::+) => {
+                    $($module)::+::handler::__delegate_dispatch!($dispatch_from: [
+                        #(#delegate_dispatch_list),*
+                    ] => $($module)::+::handler::#adapter_name<$dispatch_from>);
+                };
+            }
+            #[allow(unused_imports)]
+            pub(crate) use #delegate_macro_name;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -264,4 +648,23 @@ mod tests {
             panic!("Generated does not match reference!")
         }
     }
+
+    #[test]
+    fn client_handler_gen() {
+        let protocol_file =
+            std::fs::File::open("./tests/scanner_assets/test-protocol.xml").unwrap();
+        let protocol_parsed = crate::parse::parse(protocol_file);
+        let generated: String = super::generate_client_handler(&protocol_parsed).to_string();
+        let generated = crate::format_rust_code(&generated);
+
+        let reference =
+            std::fs::read_to_string("./tests/scanner_assets/test-client-handler.rs").unwrap();
+        let reference = crate::format_rust_code(&reference);
+
+        if reference != generated {
+            let diff = similar::TextDiff::from_lines(&reference, &generated);
+            print!("{}", diff.unified_diff().context_radius(10).header("reference", "generated"));
+            panic!("Generated does not match reference!")
+        }
+    }
 }
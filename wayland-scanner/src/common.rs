@@ -23,6 +23,10 @@ impl ToTokens for Enum {
                     .as_ref()
                     .map(description_to_doc_attr)
                     .or_else(|| entry.summary.as_ref().map(|s| to_doc_attr(s)));
+                // a #[deprecated] attribute would trigger warnings in the code generated by bitflags
+                let deprecated_doc = entry.deprecated_since.map(|version| {
+                    to_doc_attr(&format!("Deprecated since version {} of the interface", version))
+                });
 
                 let prefix = if entry.name.chars().next().unwrap().is_numeric() { "_" } else { "" };
                 let ident = format_ident!("{}{}", prefix, snake_to_camel(&entry.name));
@@ -31,6 +35,7 @@ impl ToTokens for Enum {
 
                 quote! {
                     #doc_attr
+                    #deprecated_doc
                     const #ident = #value;
                 }
             });
@@ -43,7 +48,40 @@ impl ToTokens for Enum {
                     }
                 }
             };
+            // the flags set determine the version required, a flag of value 0 is always contained
+            let since_checks = self
+                .entries
+                .iter()
+                .filter(|entry| entry.since > 1 && entry.value != 0)
+                .map(|entry| {
+                    let prefix =
+                        if entry.name.chars().next().unwrap().is_numeric() { "_" } else { "" };
+                    let flag = format_ident!("{}{}", prefix, snake_to_camel(&entry.name));
+                    let since = entry.since as u32;
+                    quote! {
+                        if self.contains(#ident::#flag) {
+                            since = since.max(#since);
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+            let since_body = if since_checks.is_empty() {
+                quote! { 1 }
+            } else {
+                quote! {
+                    let mut since = 1;
+                    #(#since_checks)*
+                    since
+                }
+            };
+
             enum_impl = quote! {
+                impl #ident {
+                    /// The minimal version of the interface supporting these flags
+                    pub fn since(&self) -> u32 {
+                        #since_body
+                    }
+                }
                 impl std::convert::TryFrom<u32> for #ident {
                     type Error = ();
                     fn try_from(val: u32) -> Result<#ident, ()> {
@@ -66,11 +104,13 @@ impl ToTokens for Enum {
 
                 let prefix = if entry.name.chars().next().unwrap().is_numeric() { "_" } else { "" };
                 let variant = format_ident!("{}{}", prefix, snake_to_camel(&entry.name));
+                let deprecated_attr = deprecated_attr(entry.deprecated_since);
 
                 let value = Literal::u32_unsuffixed(entry.value);
 
                 quote! {
                     #doc_attr
+                    #deprecated_attr
                     #variant = #value
                 }
             });
@@ -96,7 +136,50 @@ impl ToTokens for Enum {
                 }
             });
 
+            // the conversions must be able to name deprecated variants
+            let allow_deprecated = if self.entries.iter().any(|e| e.deprecated_since.is_some()) {
+                Some(quote! { #[allow(deprecated)] })
+            } else {
+                None
+            };
+
+            let since_arms = self.entries.iter().map(|entry| {
+                let prefix = if entry.name.chars().next().unwrap().is_numeric() { "_" } else { "" };
+                let variant = format_ident!("{}{}", prefix, snake_to_camel(&entry.name));
+                let since = entry.since as u32;
+
+                quote! {
+                    #ident::#variant => #since
+                }
+            });
+
+            // error enums get a description of their entries, to report them
+            let description_fn = if self.name == "error" {
+                let description_arms = self.entries.iter().map(|entry| {
+                    let prefix =
+                        if entry.name.chars().next().unwrap().is_numeric() { "_" } else { "" };
+                    let variant = format_ident!("{}{}", prefix, snake_to_camel(&entry.name));
+                    let description = entry.summary.as_ref().unwrap_or(&entry.name);
+
+                    quote! {
+                        #ident::#variant => #description
+                    }
+                });
+
+                Some(quote! {
+                    /// A short description of this error
+                    pub fn description(&self) -> &'static str {
+                        match self {
+                            #(#description_arms,)*
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
             enum_impl = quote! {
+                #allow_deprecated
                 impl std::convert::TryFrom<u32> for #ident {
                     type Error = ();
                     fn try_from(val: u32) -> Result<#ident, ()> {
@@ -111,6 +194,16 @@ impl ToTokens for Enum {
                         val as u32
                     }
                 }
+                #allow_deprecated
+                impl #ident {
+                    /// The minimal version of the interface supporting this value
+                    pub fn since(&self) -> u32 {
+                        match self {
+                            #(#since_arms,)*
+                        }
+                    }
+                    #description_fn
+                }
             };
         }
 
@@ -143,6 +236,119 @@ pub(crate) fn gen_since_constants(requests: &[Message], events: &[Message]) -> T
     }
 }
 
+pub(crate) fn gen_message_tables(requests: &[Message], events: &[Message]) -> TokenStream {
+    fn gen_infos(messages: &[Message]) -> impl Iterator<Item = TokenStream> + '_ {
+        messages.iter().enumerate().map(|(opcode, msg)| {
+            let name = &msg.name;
+            let opcode = opcode as u16;
+            let since = msg.since;
+            let deprecated_since = match msg.deprecated_since {
+                Some(v) => quote! { Some(#v) },
+                None => quote! { None },
+            };
+            let is_destructor = msg.typ == Some(Type::Destructor);
+            quote! {
+                MessageInfo {
+                    name: #name,
+                    opcode: #opcode,
+                    since: #since,
+                    deprecated_since: #deprecated_since,
+                    is_destructor: #is_destructor,
+                }
+            }
+        })
+    }
+    let req_infos = gen_infos(requests);
+    let evt_infos = gen_infos(events);
+
+    quote! {
+        /// The metadata of the requests of this interface, indexed by opcode
+        pub const REQUESTS: &[MessageInfo] = &[#(#req_infos,)*];
+        /// The metadata of the events of this interface, indexed by opcode
+        pub const EVENTS: &[MessageInfo] = &[#(#evt_infos,)*];
+    }
+}
+
+/// The type of the field of a message enum variant representing the argument `arg`, if it has one
+pub(crate) fn message_arg_type(arg: &Arg, side: Side, receiver: bool) -> Option<TokenStream> {
+    let field_type_inner = if let Some(ref enu) = arg.enum_ {
+        let enum_type = dotted_to_relname(enu);
+        quote! { WEnum<#enum_type> }
+    } else {
+        match arg.typ {
+            Type::Uint => quote! { u32 },
+            Type::Int => quote! { i32 },
+            Type::Fixed => quote! { f64 },
+            Type::String => quote! { String },
+            Type::Array => quote! { Vec<u8> },
+            Type::Fd => quote! { ::std::os::unix::io::RawFd },
+            Type::Object => {
+                if let Some(ref iface) = arg.interface {
+                    let iface_mod = Ident::new(iface, Span::call_site());
+                    let iface_type = Ident::new(&snake_to_camel(iface), Span::call_site());
+                    quote! { super::#iface_mod::#iface_type }
+                } else if side == Side::Client {
+                    quote! { super::wayland_client::backend::ObjectId }
+                } else {
+                    quote! { super::wayland_server::backend::ObjectId }
+                }
+            }
+            Type::NewId if !receiver && side == Side::Client => {
+                // Client-side sending does not have a pre-existing object
+                // so skip serializing it
+                if arg.interface.is_some() {
+                    return None;
+                } else {
+                    quote! { (&'static Interface, u32) }
+                }
+            }
+            Type::NewId => {
+                if let Some(ref iface) = arg.interface {
+                    let iface_mod = Ident::new(iface, Span::call_site());
+                    let iface_type = Ident::new(&snake_to_camel(iface), Span::call_site());
+                    if receiver && side == Side::Server {
+                        quote! { New<super::#iface_mod::#iface_type> }
+                    } else {
+                        quote! { super::#iface_mod::#iface_type }
+                    }
+                } else {
+                    // bind-like function
+                    if side == Side::Client {
+                        quote! { (String, u32, super::wayland_client::backend::ObjectId) }
+                    } else {
+                        quote! { (String, u32, super::wayland_server::backend::ObjectId) }
+                    }
+                }
+            }
+            Type::Destructor => panic!("An argument cannot have type \"destructor\"."),
+        }
+    };
+
+    let field_type = if arg.allow_null {
+        quote! { Option<#field_type_inner> }
+    } else {
+        field_type_inner.into_token_stream()
+    };
+
+    Some(field_type)
+}
+
+/// The type of an argument of a handler method, relative to the `handler` module
+pub(crate) fn handler_arg_type(
+    interface: &Interface,
+    arg: &Arg,
+    side: Side,
+) -> Option<TokenStream> {
+    match arg.enum_ {
+        // qualify the enums of the interface itself, as the handler lives in its own module
+        Some(ref enu) if !enu.contains('.') => {
+            let arg = Arg { enum_: Some(format!("{}.{}", interface.name, enu)), ..arg.clone() };
+            message_arg_type(&arg, side, true)
+        }
+        _ => message_arg_type(arg, side, true),
+    }
+}
+
 pub(crate) fn gen_message_enum(
     name: &Ident,
     side: Side,
@@ -163,6 +369,9 @@ pub(crate) fn gen_message_enum(
         if msg.since > 1 {
             docs += &format!("\nOnly available since version {} of the interface", msg.since);
         }
+        if let Some(deprecated_since) = msg.deprecated_since {
+            docs += &format!("\nDeprecated since version {} of the interface", deprecated_since);
+        }
 
         let doc_attr = to_doc_attr(&docs);
         let msg_name = Ident::new(&snake_to_camel(&msg.name), Span::call_site());
@@ -172,66 +381,7 @@ pub(crate) fn gen_message_enum(
             let fields = msg.args.iter().flat_map(|arg| {
                 let field_name =
                     format_ident!("{}{}", if is_keyword(&arg.name) { "_" } else { "" }, arg.name);
-                let field_type_inner = if let Some(ref enu) = arg.enum_ {
-                    let enum_type = dotted_to_relname(enu);
-                    quote! { WEnum<#enum_type> }
-                } else {
-                    match arg.typ {
-                        Type::Uint => quote! { u32 },
-                        Type::Int => quote! { i32 },
-                        Type::Fixed => quote! { f64 },
-                        Type::String => quote! { String },
-                        Type::Array => quote! { Vec<u8> },
-                        Type::Fd => quote! { ::std::os::unix::io::RawFd },
-                        Type::Object => {
-                            if let Some(ref iface) = arg.interface {
-                                let iface_mod = Ident::new(iface, Span::call_site());
-                                let iface_type =
-                                    Ident::new(&snake_to_camel(iface), Span::call_site());
-                                quote! { super::#iface_mod::#iface_type }
-                            } else if side == Side::Client {
-                                quote! { super::wayland_client::ObjectId }
-                            } else {
-                                quote! { super::wayland_server::ObjectId }
-                            }
-                        }
-                        Type::NewId if !receiver && side == Side::Client => {
-                            // Client-side sending does not have a pre-existing object
-                            // so skip serializing it
-                            if arg.interface.is_some() {
-                                return None;
-                            } else {
-                                quote! { (&'static Interface, u32) }
-                            }
-                        }
-                        Type::NewId => {
-                            if let Some(ref iface) = arg.interface {
-                                let iface_mod = Ident::new(iface, Span::call_site());
-                                let iface_type =
-                                    Ident::new(&snake_to_camel(iface), Span::call_site());
-                                if receiver && side == Side::Server {
-                                    quote! { New<super::#iface_mod::#iface_type> }
-                                } else {
-                                    quote! { super::#iface_mod::#iface_type }
-                                }
-                            } else {
-                                // bind-like function
-                                if side == Side::Client {
-                                    quote! { (String, u32, super::wayland_client::ObjectId) }
-                                } else {
-                                    quote! { (String, u32, super::wayland_server::ObjectId) }
-                                }
-                            }
-                        }
-                        Type::Destructor => panic!("An argument cannot have type \"destructor\"."),
-                    }
-                };
-
-                let field_type = if arg.allow_null {
-                    quote! { Option<#field_type_inner> }
-                } else {
-                    field_type_inner.into_token_stream()
-                };
+                let field_type = message_arg_type(arg, side, receiver)?;
 
                 let doc_attr = arg
                     .description
@@ -258,15 +408,69 @@ pub(crate) fn gen_message_enum(
         }
     });
 
+    let table = format_ident!("{}S", name.to_string().to_ascii_uppercase());
+    let opcodes = messages.iter().enumerate().map(|(opcode, msg)| {
+        let msg_name = Ident::new(&snake_to_camel(&msg.name), Span::call_site());
+        let opcode = opcode as u16;
+        quote! { #name::#msg_name { .. } => #opcode }
+    });
+
     quote! {
         #[derive(Debug)]
         #[non_exhaustive]
         pub enum #name {
             #(#variants,)*
         }
+
+        impl #name {
+            /// The opcode of this message
+            #[inline]
+            pub fn opcode(&self) -> u16 {
+                match *self {
+                    #(#opcodes,)*
+                }
+            }
+
+            /// The metadata of this message
+            #[inline]
+            pub fn info(&self) -> &'static MessageInfo {
+                &#table[self.opcode() as usize]
+            }
+
+            /// The name of this message
+            #[inline]
+            pub fn name(&self) -> &'static str {
+                self.info().name
+            }
+
+            /// The minimal object version supporting this message
+            #[inline]
+            pub fn since(&self) -> u32 {
+                self.info().since
+            }
+
+            /// Whether this message is a destructor
+            #[inline]
+            pub fn is_destructor(&self) -> bool {
+                self.info().is_destructor
+            }
+        }
     }
 }
 
+/// The entry of the error enum of an interface to post when a request contains an invalid value of the
+/// enum `enu`, by convention named `invalid_<enum name>`
+fn invalid_value_error<'a>(interface: &'a Interface, enu: &str) -> Option<&'a Entry> {
+    let enum_name = enu.rsplit('.').next().unwrap();
+    interface
+        .enums
+        .iter()
+        .find(|e| e.name == "error" && !e.bitfield)?
+        .entries
+        .iter()
+        .find(|entry| entry.name == format!("invalid_{}", enum_name))
+}
+
 pub(crate) fn gen_parse_body(interface: &Interface, side: Side) -> TokenStream {
     let msgs = match side {
         Side::Client => &interface.events,
@@ -310,8 +514,30 @@ pub(crate) fn gen_parse_body(interface: &Interface, side: Side) -> TokenStream {
 
         let arg_names = msg.args.iter().map(|arg| {
             let arg_name = format_ident!("{}{}", if is_keyword(&arg.name) { "_" } else { "" }, arg.name);
-            if arg.enum_.is_some() {
-                quote! { #arg_name: From::from(*#arg_name as u32) }
+            if let Some(ref enu) = arg.enum_ {
+                // the server rejects invalid values if the interface has an error for them
+                let invalid_entry = if side == Side::Server { invalid_value_error(interface, enu) } else { None };
+                if let Some(entry) = invalid_entry {
+                    let enum_type = dotted_to_relname(enu);
+                    let code = entry.value;
+                    let request_name = &msg.name;
+                    let arg_str = &arg.name;
+                    quote! {
+                        #arg_name: match WEnum::<#enum_type>::from(*#arg_name as u32) {
+                            WEnum::Value(val) if val.since() <= version => WEnum::Value(val),
+                            _ => return Err(DispatchError::InvalidValue {
+                                value: *#arg_name as u32,
+                                code: #code,
+                                interface: Self::interface().name,
+                                request: #request_name,
+                                arg: #arg_str,
+                                msg,
+                            }),
+                        }
+                    }
+                } else {
+                    quote! { #arg_name: From::from(*#arg_name as u32) }
+                }
             } else {
                 match arg.typ {
                     Type::Uint | Type::Int | Type::Fd => quote!{ #arg_name: *#arg_name },
@@ -404,9 +630,20 @@ pub(crate) fn gen_parse_body(interface: &Interface, side: Side) -> TokenStream {
             }
         });
 
+        // the version of the object is needed to validate the values of enums
+        let version_decl = if side == Side::Server
+            && msg.args.iter().any(|arg| {
+                arg.enum_.as_ref().map(|enu| invalid_value_error(interface, enu).is_some()).unwrap_or(false)
+            }) {
+            Some(quote! { let version = me.version(); })
+        } else {
+            None
+        };
+
         quote! {
             #opcode => {
                 if let [#(#args_pat),*] = &msg.args[..] {
+                    #version_decl
                     Ok((me, #msg_type::#msg_name { #(#arg_names),* }))
                 } else {
                     Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
@@ -502,14 +739,51 @@ pub(crate) fn gen_write_body(interface: &Interface, side: Side) -> TokenStream {
                 Type::Destructor => panic!("Argument {}.{}.{} has type destructor ?!", interface.name, msg.name, arg.name),
             }
         });
-        quote! {
-            #msg_type::#msg_name { #(#arg_names),* } => Ok(Message {
+        // the client refuses to send enum values the version of the object does not support
+        let version_checks = msg
+            .args
+            .iter()
+            .filter(|arg| arg.enum_.is_some() && side == Side::Client)
+            .map(|arg| {
+                let arg_name = format_ident!("{}{}", if is_keyword(&arg.name) { "_" } else { "" }, arg.name);
+                let request_name = &msg.name;
+                let arg_str = &arg.name;
+                quote! {
+                    if let WEnum::Value(val) = #arg_name {
+                        if val.since() > self.version {
+                            return Err(RequestError::UnsupportedValue {
+                                interface: Self::interface().name,
+                                request: #request_name,
+                                arg: #arg_str,
+                                value: val.into(),
+                                since: val.since(),
+                                version: self.version,
+                            });
+                        }
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let message = quote! {
+            Ok(Message {
                 sender_id: self.id.clone(),
                 opcode: #opcode,
                 args: smallvec::smallvec![
                     #(#args),*
                 ]
             })
+        };
+        if version_checks.is_empty() {
+            quote! {
+                #msg_type::#msg_name { #(#arg_names),* } => #message
+            }
+        } else {
+            quote! {
+                #msg_type::#msg_name { #(#arg_names),* } => {
+                    #(#version_checks)*
+                    #message
+                }
+            }
         }
     });
     quote! {
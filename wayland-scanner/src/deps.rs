@@ -0,0 +1,187 @@
+//! Resolution of the interfaces a protocol borrows from other protocols
+
+use std::{collections::BTreeSet, rc::Rc};
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::protocol::{Protocol, Type};
+
+/// A protocol the generated protocol depends on
+pub(crate) struct Dependency {
+    /// Path of the protocol file, as given to the macro
+    pub file: String,
+    /// Path of the module in which the code of this protocol was generated
+    pub module: syn::Path,
+    pub protocol: Protocol,
+}
+
+thread_local! {
+    // parsed on first use, and shared by all the macro invocations of the compilation
+    static CORE_PROTOCOL: Rc<Protocol> =
+        Rc::new(crate::parse::parse(&include_bytes!("../wayland.xml")[..]));
+}
+
+/// The core wayland protocol, whose interfaces are always in scope of the generated code
+pub(crate) fn core_protocol() -> Rc<Protocol> {
+    CORE_PROTOCOL.with(Rc::clone)
+}
+
+/// Generate the imports of the interfaces of `protocol` that are defined by its dependencies
+///
+/// When `for_interfaces` is true the imports target the `__interfaces` modules of the dependencies,
+/// otherwise they target the modules of their interfaces. Each interface that cannot be found in the
+/// protocol itself, in the core protocol, nor in any of the dependencies produces an error message.
+pub(crate) fn resolve_imports(
+    protocol: &Protocol,
+    deps: &[Dependency],
+    for_interfaces: bool,
+) -> Result<TokenStream, Vec<String>> {
+    let core = core_protocol();
+    let mut imports = BTreeSet::new();
+    let mut missing = Vec::new();
+
+    for (iface_name, location) in references(protocol, !for_interfaces) {
+        if protocol.interfaces.iter().any(|iface| iface.name == iface_name)
+            || core.interfaces.iter().any(|iface| iface.name == iface_name)
+        {
+            continue;
+        }
+        match deps
+            .iter()
+            .position(|dep| dep.protocol.interfaces.iter().any(|iface| iface.name == iface_name))
+        {
+            Some(idx) => {
+                imports.insert((idx, iface_name));
+            }
+            None => {
+                if !missing.iter().any(|&(name, _)| name == iface_name) {
+                    missing.push((iface_name, location));
+                }
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        let origin = if deps.is_empty() {
+            "and no dependency was declared".to_owned()
+        } else {
+            let list = deps
+                .iter()
+                .map(|dep| format!("`{}` ({})", dep.protocol.name, dep.file))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("nor by its dependencies: {}", list)
+        };
+        return Err(missing
+            .into_iter()
+            .map(|(iface_name, location)| {
+                format!(
+                    "Interface `{}` used by {} is not defined by protocol `{}` nor by the core wayland protocol, {}",
+                    iface_name, location, protocol.name, origin
+                )
+            })
+            .collect());
+    }
+
+    Ok(imports
+        .into_iter()
+        .map(|(idx, iface_name)| {
+            let module = &deps[idx].module;
+            if for_interfaces {
+                let const_name = format_ident!("{}_INTERFACE", iface_name.to_ascii_uppercase());
+                let c_name = format_ident!("{}_interface", iface_name);
+                quote! { use #module::__interfaces::{#const_name, #c_name}; }
+            } else {
+                let iface_mod = Ident::new(iface_name, Span::call_site());
+                quote! { use #module::#iface_mod; }
+            }
+        })
+        .collect())
+}
+
+/// List the interfaces referenced by the messages of a protocol, along with where they are referenced
+fn references(protocol: &Protocol, with_enums: bool) -> Vec<(&str, String)> {
+    let mut refs = Vec::new();
+    for iface in &protocol.interfaces {
+        let messages = iface
+            .requests
+            .iter()
+            .map(|msg| ("request", msg))
+            .chain(iface.events.iter().map(|msg| ("event", msg)));
+        for (kind, msg) in messages {
+            for arg in &msg.args {
+                let location =
+                    || format!("argument `{}` of {} `{}.{}`", arg.name, kind, iface.name, msg.name);
+                if let (Type::Object, Some(name)) | (Type::NewId, Some(name)) =
+                    (arg.typ, arg.interface.as_ref())
+                {
+                    refs.push((name.as_str(), location()));
+                }
+                if let Some((enum_iface, _)) =
+                    arg.enum_.as_ref().filter(|_| with_enums).and_then(|e| e.split_once('.'))
+                {
+                    refs.push((enum_iface, location()));
+                }
+            }
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(file: &str) -> Protocol {
+        crate::parse::parse(std::fs::File::open(file).unwrap())
+    }
+
+    #[test]
+    fn resolve_dependent_protocol() {
+        let protocol = load("./tests/scanner_assets/test-dependent-protocol.xml");
+        let deps = vec![Dependency {
+            file: "test-protocol.xml".into(),
+            module: syn::parse_quote!(crate::test_protocol::client),
+            protocol: load("./tests/scanner_assets/test-protocol.xml"),
+        }];
+
+        let imports = resolve_imports(&protocol, &deps, false).unwrap();
+        assert_eq!(
+            imports.to_string(),
+            quote! {
+                use crate::test_protocol::client::secondary;
+                use crate::test_protocol::client::test_global;
+            }
+            .to_string()
+        );
+
+        // enums are not needed by the interfaces
+        let imports = resolve_imports(&protocol, &deps, true).unwrap();
+        assert_eq!(
+            imports.to_string(),
+            quote! {
+                use crate::test_protocol::client::__interfaces::{SECONDARY_INTERFACE, secondary_interface};
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn report_missing_interfaces() {
+        let protocol = load("./tests/scanner_assets/test-dependent-protocol.xml");
+
+        let errors = resolve_imports(&protocol, &[], false).unwrap_err();
+        assert_eq!(
+            errors,
+            [
+                "Interface `secondary` used by argument `sec` of request `test_wrapper_manager.wrap` \
+                 is not defined by protocol `test-dependent-protocol` nor by the core wayland \
+                 protocol, and no dependency was declared",
+                "Interface `test_global` used by argument `mode` of request \
+                 `test_wrapper_manager.wrap` is not defined by protocol `test-dependent-protocol` \
+                 nor by the core wayland protocol, and no dependency was declared",
+            ]
+        );
+    }
+}
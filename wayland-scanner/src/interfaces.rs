@@ -57,6 +57,10 @@ fn build_messagedesc_list(list: &[Message]) -> TokenStream {
     let desc_list = list.iter().map(|message| {
         let name = &message.name;
         let since = message.since;
+        let deprecated_since = match message.deprecated_since {
+            Some(version) => quote! { Some(#version) },
+            None => quote! { None },
+        };
         let is_destructor = message.typ == Some(Type::Destructor);
         let signature = message.args.iter().map(|arg| {
             if arg.typ == Type::NewId && arg.interface.is_none() {
@@ -107,6 +111,7 @@ fn build_messagedesc_list(list: &[Message]) -> TokenStream {
                 name: #name,
                 signature: &[ #(#signature),* ],
                 since: #since,
+                deprecated_since: #deprecated_since,
                 is_destructor: #is_destructor,
                 child_interface: #child_interface,
                 arg_interfaces: &[ #(#arg_interfaces),* ],
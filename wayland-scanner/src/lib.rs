@@ -1,10 +1,19 @@
 use std::{ffi::OsString, path::PathBuf};
 
-use syn::{parse_macro_input, LitStr};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    LitStr, Token,
+};
 
 mod c_interfaces;
 mod client_gen;
 mod common;
+mod deps;
 mod interfaces;
 mod parse;
 mod protocol;
@@ -13,43 +22,150 @@ mod util;
 
 #[proc_macro]
 pub fn generate_interfaces(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let path: OsString = parse_macro_input!(stream as LitStr).value().into();
-    let path = if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
-        let mut buf = PathBuf::from(manifest_dir);
-        buf.push(path);
-        buf
-    } else {
-        path.into()
-    };
-    let file = match std::fs::File::open(&path) {
-        Ok(file) => file,
-        Err(e) => panic!("Failed to open protocol file {}: {}", path.display(), e),
-    };
-    let protocol = parse::parse(file);
-    interfaces::generate(&protocol, true).into()
+    let input = parse_macro_input!(stream as MacroInput);
+    let protocol = load_protocol(&input.path.value());
+    input.with_imports(&protocol, true, interfaces::generate(&protocol, true)).into()
 }
 
 #[proc_macro]
 pub fn generate_client_code(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let path: OsString = parse_macro_input!(stream as LitStr).value().into();
-    let path = if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
-        let mut buf = PathBuf::from(manifest_dir);
-        buf.push(path);
-        buf
-    } else {
-        path.into()
-    };
-    let file = match std::fs::File::open(&path) {
-        Ok(file) => file,
-        Err(e) => panic!("Failed to open protocol file {}: {}", path.display(), e),
-    };
-    let protocol = parse::parse(file);
-    client_gen::generate_client_objects(&protocol).into()
+    let input = parse_macro_input!(stream as MacroInput);
+    let protocol = load_protocol(&input.path.value());
+    input.with_imports(&protocol, false, client_gen::generate_client_objects(&protocol)).into()
 }
 
 #[proc_macro]
 pub fn generate_server_code(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let path: OsString = parse_macro_input!(stream as LitStr).value().into();
+    let input = parse_macro_input!(stream as MacroInput);
+    let protocol = load_protocol(&input.path.value());
+    input.with_imports(&protocol, false, server_gen::generate_server_objects(&protocol)).into()
+}
+
+/// Generate the handler trait of a protocol for wayland-client
+///
+/// This generates a `handler` module containing a trait with one method per event of the protocol (prefixed
+/// by the name of the interface) and an associated user data type per interface, along with an adapter type
+/// implementing the `DelegateDispatch` trait from it. It is meant to be invoked alongside
+/// [`generate_client_code!`], in the same module.
+///
+/// The module also provides a `delegate_<protocol>!` macro implementing `Dispatch` for all the interfaces
+/// of the protocol through the adapter, taking the state type and the path of the module of the protocol:
+///
+/// ```ignore
+/// protocol::handler::delegate_my_protocol!(State => protocol);
+/// ```
+#[proc_macro]
+pub fn generate_client_handler(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(stream as MacroInput);
+    let protocol = load_protocol(&input.path.value());
+    input.with_imports(&protocol, false, client_gen::generate_client_handler(&protocol)).into()
+}
+
+/// Generate the handler trait of a protocol for wayland-server
+///
+/// This generates a `handler` module containing a trait with one method per request of the protocol
+/// (prefixed by the name of the interface) and per global, and an associated user data type per interface
+/// and global, along with an adapter type implementing the `DelegateDispatch` and `DelegateGlobalDispatch`
+/// traits from it. It is meant to be invoked alongside [`generate_server_code!`], in the same module.
+/// Like for [`generate_client_handler!`], a `delegate_<protocol>!` macro implements `Dispatch` and
+/// `GlobalDispatch` for all the interfaces of the protocol through the adapter.
+///
+/// The interfaces that are not created by any message of the protocol, of its declared dependencies nor
+/// of the core wayland protocol are handled as globals.
+#[proc_macro]
+pub fn generate_server_handler(stream: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(stream as MacroInput);
+    let protocol = load_protocol(&input.path.value());
+    let deps = input
+        .dependencies()
+        .into_iter()
+        .map(|dep| dep.protocol)
+        .chain(std::iter::once(protocol::Protocol::clone(&deps::core_protocol())))
+        .collect::<Vec<_>>();
+    input
+        .with_imports(&protocol, false, server_gen::generate_server_handler(&protocol, &deps))
+        .into()
+}
+
+/// Input of the code generation macros
+///
+/// It is the path of the protocol file, optionally followed by the list of the protocols it depends
+/// on, each given as the path of its file and the path of the module in which its code was generated:
+///
+/// ```text
+/// "./xdg-decoration.xml", ["./xdg-shell.xml" => crate::xdg_shell::client]
+/// ```
+///
+/// When the dependencies are given, the interfaces the protocol borrows from them are imported by the
+/// generated code, and any interface that cannot be resolved is reported as a compilation error.
+/// Otherwise, the interfaces of other protocols are expected to be in scope of the macro invocation.
+struct MacroInput {
+    path: LitStr,
+    deps: Option<Vec<(LitStr, syn::Path)>>,
+}
+
+impl Parse for MacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        if input.is_empty() {
+            return Ok(MacroInput { path, deps: None });
+        }
+        input.parse::<Token![,]>()?;
+        let content;
+        bracketed!(content in input);
+        let deps = Punctuated::<_, Token![,]>::parse_terminated_with(&content, |dep| {
+            let file = dep.parse::<LitStr>()?;
+            dep.parse::<Token![=>]>()?;
+            Ok((file, dep.parse::<syn::Path>()?))
+        })?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(MacroInput { path, deps: Some(deps.into_iter().collect()) })
+    }
+}
+
+impl MacroInput {
+    /// Load the protocols this protocol depends on
+    fn dependencies(&self) -> Vec<deps::Dependency> {
+        self.deps
+            .iter()
+            .flatten()
+            .map(|(file, module)| deps::Dependency {
+                file: file.value(),
+                module: module.clone(),
+                protocol: load_protocol(&file.value()),
+            })
+            .collect()
+    }
+
+    /// Prepend the imports of the dependencies to the generated code, or replace it with the
+    /// resolution errors
+    fn with_imports(
+        &self,
+        protocol: &protocol::Protocol,
+        for_interfaces: bool,
+        code: TokenStream,
+    ) -> TokenStream {
+        if self.deps.is_none() {
+            return code;
+        }
+        let deps = self.dependencies();
+        match deps::resolve_imports(protocol, &deps, for_interfaces) {
+            Ok(imports) => quote! {
+                #imports
+                #code
+            },
+            Err(errors) => errors
+                .into_iter()
+                .map(|msg| syn::Error::new(self.path.span(), msg).to_compile_error())
+                .collect(),
+        }
+    }
+}
+
+fn load_protocol(path: &str) -> protocol::Protocol {
+    let path: OsString = path.into();
     let path = if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
         let mut buf = PathBuf::from(manifest_dir);
         buf.push(path);
@@ -61,8 +177,7 @@ pub fn generate_server_code(stream: proc_macro::TokenStream) -> proc_macro::Toke
         Ok(file) => file,
         Err(e) => panic!("Failed to open protocol file {}: {}", path.display(), e),
     };
-    let protocol = parse::parse(file);
-    server_gen::generate_server_objects(&protocol).into()
+    parse::parse(file)
 }
 
 #[cfg(test)]
@@ -139,6 +139,7 @@ fn parse_request<R: Read>(reader: &mut EventReader<R>, attrs: Vec<OwnedAttribute
             "name" => request.name = attr.value,
             "type" => request.typ = Some(parse_type(&attr.value)),
             "since" => request.since = attr.value.parse().unwrap(),
+            "deprecated-since" => request.deprecated_since = Some(attr.value.parse().unwrap()),
             _ => {}
         }
     }
@@ -195,6 +196,7 @@ fn parse_event<R: Read>(reader: &mut EventReader<R>, attrs: Vec<OwnedAttribute>)
             "name" => event.name = attr.value,
             "type" => event.typ = Some(parse_type(&attr.value)),
             "since" => event.since = attr.value.parse().unwrap(),
+            "deprecated-since" => event.deprecated_since = Some(attr.value.parse().unwrap()),
             _ => {}
         }
     }
@@ -276,6 +278,7 @@ fn parse_entry<R: Read>(reader: &mut EventReader<R>, attrs: Vec<OwnedAttribute>)
                 };
             }
             "since" => entry.since = attr.value.parse().unwrap(),
+            "deprecated-since" => entry.deprecated_since = Some(attr.value.parse().unwrap()),
             "summary" => {
                 entry.summary = Some(attr.value.split_whitespace().collect::<Vec<_>>().join(" "))
             }
@@ -4,7 +4,7 @@ use quote::{format_ident, quote};
 
 use crate::{
     protocol::{Interface, Protocol, Type},
-    util::{dotted_to_relname, is_keyword, snake_to_camel},
+    util::{deprecated_attr, dotted_to_relname, is_keyword, snake_to_camel, to_doc_attr},
     Side,
 };
 
@@ -25,6 +25,7 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
 
     let enums = crate::common::generate_enums_for(interface);
     let sinces = crate::common::gen_since_constants(&interface.requests, &interface.events);
+    let tables = crate::common::gen_message_tables(&interface.requests, &interface.events);
 
     let requests = crate::common::gen_message_enum(
         &format_ident!("Request"),
@@ -42,6 +43,7 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
     let parse_body = crate::common::gen_parse_body(interface, Side::Server);
     let write_body = crate::common::gen_write_body(interface, Side::Server);
     let methods = gen_methods(interface);
+    let error_methods = gen_error_methods(interface);
 
     quote! {
         #mod_doc
@@ -49,12 +51,13 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
             use std::sync::Arc;
 
             use super::wayland_server::{
-                backend::{smallvec, ObjectData, ObjectId, InvalidId, protocol::{WEnum, Argument, Message, Interface, same_interface}},
+                backend::{smallvec, ObjectData, ObjectId, InvalidId, protocol::{WEnum, Argument, Message, MessageInfo, Interface, same_interface}},
                 Resource, Dispatch, DisplayHandle, DispatchError, ResourceData, New,
             };
 
             #enums
             #sinces
+            #tables
             #requests
             #events
 
@@ -73,6 +76,12 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
 
             impl std::cmp::Eq for #iface_name {}
 
+            impl std::hash::Hash for #iface_name {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    self.id.hash(state)
+                }
+            }
+
             impl super::wayland_server::Resource for #iface_name {
                 type Request = Request;
                 type Event = Event;
@@ -121,12 +130,33 @@ fn generate_objects_for(interface: &Interface) -> TokenStream {
             }
 
             impl #iface_name {
+                #error_methods
                 #methods
             }
         }
     }
 }
 
+fn gen_error_methods(interface: &Interface) -> Option<TokenStream> {
+    interface.enums.iter().find(|e| e.name == "error" && !e.bitfield).map(|_| {
+        quote! {
+            /// Post a protocol error on this object
+            ///
+            /// This shadows [`Resource::post_error()`] to only accept the error codes of this interface.
+            #[inline]
+            pub fn post_error(&self, dh: &mut DisplayHandle, error: Error, message: impl Into<String>) {
+                <Self as Resource>::post_error(self, dh, error, message)
+            }
+
+            /// Post a protocol error on this object, using the description of the error as message
+            #[inline]
+            pub fn post_error_default(&self, dh: &mut DisplayHandle, error: Error) {
+                <Self as Resource>::post_error(self, dh, error, error.description())
+            }
+        }
+    })
+}
+
 fn gen_methods(interface: &Interface) -> TokenStream {
     interface
         .events
@@ -201,7 +231,10 @@ fn gen_methods(interface: &Interface) -> TokenStream {
                 }
             });
 
+            let deprecated_attr = deprecated_attr(request.deprecated_since);
+
             quote! {
+                #deprecated_attr
                 #[allow(clippy::too_many_arguments)]
                 pub fn #method_name(&self, conn: &mut DisplayHandle, #(#fn_args),*) {
                     let _ = conn.send_event(
@@ -216,6 +249,306 @@ fn gen_methods(interface: &Interface) -> TokenStream {
         .collect()
 }
 
+/// Generate a handler trait for all the requests of a protocol, and an adapter implementing the
+/// delegation traits of wayland-server from it
+///
+/// The interfaces that are not created by any message of the protocol nor of its dependencies are
+/// handled as globals.
+pub fn generate_server_handler(protocol: &Protocol, deps: &[Protocol]) -> TokenStream {
+    let protocol_name = snake_to_camel(&protocol.name.replace('-', "_"));
+    let handler_name = format_ident!("{}Handler", protocol_name);
+    let adapter_name = format_ident!("{}Adapter", protocol_name);
+    let delegate_macro_name = format_ident!("delegate_{}", protocol.name.replace('-', "_"));
+
+    let interfaces = protocol
+        .interfaces
+        .iter()
+        .filter(|iface| iface.name != "wl_display" && iface.name != "wl_registry")
+        .collect::<Vec<_>>();
+
+    let globals = interfaces
+        .iter()
+        .filter(|iface| {
+            !std::iter::once(protocol)
+                .chain(deps)
+                .flat_map(|proto| proto.interfaces.iter())
+                .flat_map(|other| other.requests.iter().chain(other.events.iter()))
+                .flat_map(|msg| msg.args.iter())
+                .any(|arg| arg.typ == Type::NewId && arg.interface.as_ref() == Some(&iface.name))
+        })
+        .collect::<Vec<_>>();
+
+    let data_types = interfaces.iter().map(|iface| {
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let doc_attr = to_doc_attr(&format!("User data of the `{}` resources", iface.name));
+        quote! {
+            #doc_attr
+            type #data_type: Send + Sync + 'static;
+        }
+    });
+
+    let global_data_types = globals.iter().map(|iface| {
+        let data_type = format_ident!("{}GlobalData", snake_to_camel(&iface.name));
+        let doc_attr = to_doc_attr(&format!("Data of the `{}` globals", iface.name));
+        quote! {
+            #doc_attr
+            type #data_type: Send + Sync + 'static;
+        }
+    });
+
+    let bind_methods = globals.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let global_data_type = format_ident!("{}GlobalData", snake_to_camel(&iface.name));
+        let method_name = format_ident!("{}_bind", iface.name);
+        let doc_attr = to_doc_attr(&format!("A client bound the `{}` global", iface.name));
+        quote! {
+            #doc_attr
+            fn #method_name(
+                &mut self,
+                dhandle: &mut DisplayHandle<'_>,
+                client: &Client,
+                resource: New<super::#iface_mod::#iface_name>,
+                global_data: &Self::#global_data_type,
+                data_init: &mut DataInit<'_, Self>,
+            );
+        }
+    });
+
+    let request_methods = interfaces.iter().flat_map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        iface.requests.iter().map(move |request| {
+            let method_name = format_ident!("{}_{}", iface.name, request.name);
+            let mut docs = format!("The `{}.{}` request", iface.name, request.name);
+            if let Some((ref short, _)) = request.description {
+                docs += &format!("\n\n{}", short);
+            }
+            let doc_attr = to_doc_attr(&docs);
+            let args = request.args.iter().flat_map(|arg| {
+                let arg_name =
+                    format_ident!("{}{}", if is_keyword(&arg.name) { "_" } else { "" }, arg.name);
+                let arg_type = crate::common::handler_arg_type(iface, arg, Side::Server)?;
+                Some(quote! { #arg_name: #arg_type })
+            });
+            quote! {
+                #doc_attr
+                #[allow(clippy::too_many_arguments)]
+                fn #method_name(
+                    &mut self,
+                    client: &Client,
+                    resource: &super::#iface_mod::#iface_name,
+                    #(#args,)*
+                    data: &Self::#data_type,
+                    dhandle: &mut DisplayHandle<'_>,
+                    data_init: &mut DataInit<'_, Self>,
+                );
+            }
+        })
+    });
+
+    let destroyed_methods = interfaces.iter().map(|iface| {
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let method_name = format_ident!("{}_destroyed", iface.name);
+        let doc_attr = to_doc_attr(&format!(
+            "A `{}` resource has been destroyed\n\nBy default this method does nothing.",
+            iface.name
+        ));
+        quote! {
+            #doc_attr
+            fn #method_name(&mut self, _client: ClientId, _resource: ObjectId, _data: &Self::#data_type) {}
+        }
+    });
+
+    let dispatch_impls = interfaces.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        let destroyed_name = format_ident!("{}_destroyed", iface.name);
+        let arms = iface.requests.iter().map(|request| {
+            let method_name = format_ident!("{}_{}", iface.name, request.name);
+            let variant = Ident::new(&snake_to_camel(&request.name), Span::call_site());
+            let fields = request
+                .args
+                .iter()
+                .map(|arg| {
+                    let field_name = format_ident!(
+                        "{}{}",
+                        if is_keyword(&arg.name) { "_" } else { "" },
+                        arg.name
+                    );
+                    let binding = format_ident!("arg_{}", arg.name);
+                    (field_name, binding)
+                })
+                .collect::<Vec<_>>();
+            let patterns = fields.iter().map(|(field, binding)| quote! { #field: #binding });
+            let bindings = fields.iter().map(|(_, binding)| binding);
+            quote! {
+                super::#iface_mod::Request::#variant { #(#patterns,)* } => {
+                    <D as #handler_name>::#method_name(state, client, resource, #(#bindings,)* data, dhandle, data_init)
+                }
+            }
+        });
+        // the arguments are not used when the interface has no request
+        let allow_unused = if iface.requests.is_empty() {
+            Some(quote! { #[allow(unused_variables)] })
+        } else {
+            None
+        };
+        quote! {
+            impl<D> DelegateDispatch<super::#iface_mod::#iface_name, <D as #handler_name>::#data_type, D> for #adapter_name<D>
+            where
+                D: Dispatch<super::#iface_mod::#iface_name, <D as #handler_name>::#data_type> + #handler_name,
+            {
+                #allow_unused
+                fn request(
+                    state: &mut D,
+                    client: &Client,
+                    resource: &super::#iface_mod::#iface_name,
+                    request: super::#iface_mod::Request,
+                    data: &<D as #handler_name>::#data_type,
+                    dhandle: &mut DisplayHandle<'_>,
+                    data_init: &mut DataInit<'_, D>,
+                ) {
+                    match request {
+                        #(#arms)*
+                    }
+                }
+
+                fn destroyed(state: &mut D, client: ClientId, resource: ObjectId, data: &<D as #handler_name>::#data_type) {
+                    <D as #handler_name>::#destroyed_name(state, client, resource, data)
+                }
+            }
+        }
+    });
+
+    let global_impls = globals.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let global_data_type = format_ident!("{}GlobalData", snake_to_camel(&iface.name));
+        let method_name = format_ident!("{}_bind", iface.name);
+        quote! {
+            impl<D: #handler_name> DelegateGlobalDispatchBase<super::#iface_mod::#iface_name> for #adapter_name<D> {
+                type GlobalData = <D as #handler_name>::#global_data_type;
+            }
+
+            impl<D> DelegateGlobalDispatch<super::#iface_mod::#iface_name, D> for #adapter_name<D>
+            where
+                D: GlobalDispatch<super::#iface_mod::#iface_name, GlobalData = <D as #handler_name>::#global_data_type> + #handler_name,
+            {
+                fn bind(
+                    state: &mut D,
+                    dhandle: &mut DisplayHandle<'_>,
+                    client: &Client,
+                    resource: New<super::#iface_mod::#iface_name>,
+                    global_data: &<D as #handler_name>::#global_data_type,
+                    data_init: &mut DataInit<'_, D>,
+                ) {
+                    <D as #handler_name>::#method_name(state, dhandle, client, resource, global_data, data_init)
+                }
+            }
+        }
+    });
+
+    let delegate_dispatch_list = interfaces.iter().map(|iface| {
+        let iface_mod = Ident::new(&iface.name, Span::call_site());
+        let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+        let data_type = format_ident!("{}Data", snake_to_camel(&iface.name));
+        quote! {
+            $($module)::+::#iface_mod::#iface_name: <$dispatch_from as $($module)::+::handler::#handler_name>::#data_type
+        }
+    });
+
+    let delegate_global_dispatch = if globals.is_empty() {
+        None
+    } else {
+        let global_list = globals.iter().map(|iface| {
+            let iface_mod = Ident::new(&iface.name, Span::call_site());
+            let iface_name = Ident::new(&snake_to_camel(&iface.name), Span::call_site());
+            quote! { $($module)::+::#iface_mod::#iface_name }
+        });
+        Some(quote! {
+            $($module)::+::handler::__delegate_global_dispatch!($dispatch_from: [
+                #(#global_list),*
+            ] => $($module)::+::handler::#adapter_name<$dispatch_from>);
+        })
+    };
+
+    let handler_doc = to_doc_attr(&format!(
+        "Handler of all the requests of the `{name}` protocol\n\n\
+         The user data of the resources of each interface and the data of each global are set by the \
+         associated types of this trait. Its implementation is wired to the [`Dispatch`] and \
+         [`GlobalDispatch`] implementations of your state by the `{macro_name}!` macro.",
+        name = protocol.name,
+        macro_name = delegate_macro_name,
+    ));
+    let delegate_macro_doc = to_doc_attr(&format!(
+        "Implements [`Dispatch`] and [`GlobalDispatch`] for all the interfaces of the `{name}` protocol \
+         by delegating them to [`{adapter}`]\n\n\
+         The user data of each interface is the matching associated type of the [`{handler}`] \
+         implementation of your state. The second argument is the path of the module in which the \
+         protocol was generated:\n\n\
+         ```ignore\n\
+         protocol::handler::{macro_name}!(State => protocol);\n\
+         ```",
+        name = protocol.name,
+        adapter = adapter_name,
+        handler = handler_name,
+        macro_name = delegate_macro_name,
+    ));
+    let adapter_doc = to_doc_attr(&format!(
+        "Delegate type implementing the requests of the `{}` protocol from [`{}`]",
+        protocol.name, handler_name
+    ));
+
+    quote! {
+        pub mod handler {
+            #[allow(unused_imports)]
+            use super::wayland_server::{
+                backend::{ClientId, ObjectId, protocol::WEnum},
+                Client, DataInit, DelegateDispatch, DelegateGlobalDispatch, DelegateGlobalDispatchBase,
+                Dispatch, DisplayHandle, GlobalDispatch, New,
+            };
+
+            #handler_doc
+            pub trait #handler_name: Sized {
+                #(#data_types)*
+                #(#global_data_types)*
+                #(#bind_methods)*
+                #(#request_methods)*
+                #(#destroyed_methods)*
+            }
+
+            #adapter_doc
+            #[derive(Debug)]
+            pub struct #adapter_name<D>(::std::marker::PhantomData<D>);
+
+            #(#dispatch_impls)*
+            #(#global_impls)*
+
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use super::wayland_server::{
+                delegate_dispatch as __delegate_dispatch,
+                delegate_global_dispatch as __delegate_global_dispatch,
+            };
+
+            #delegate_macro_doc
+            macro_rules! #delegate_macro_name {
+                ($dispatch_from: ty => $($module: ident)::+) => {
+                    $($module)::+::handler::__delegate_dispatch!($dispatch_from: [
+                        #(#delegate_dispatch_list),*
+                    ] => $($module)::+::handler::#adapter_name<$dispatch_from>);
+                    #delegate_global_dispatch
+                };
+            }
+            #[allow(unused_imports)]
+            pub(crate) use #delegate_macro_name;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -236,4 +569,47 @@ mod tests {
             panic!("Generated does not match reference!")
         }
     }
+
+    #[test]
+    fn server_handler_gen() {
+        let protocol_file =
+            std::fs::File::open("./tests/scanner_assets/test-protocol.xml").unwrap();
+        let protocol_parsed = crate::parse::parse(protocol_file);
+        let generated: String = super::generate_server_handler(&protocol_parsed, &[]).to_string();
+        let generated = crate::format_rust_code(&generated);
+
+        let reference =
+            std::fs::read_to_string("./tests/scanner_assets/test-server-handler.rs").unwrap();
+        let reference = crate::format_rust_code(&reference);
+
+        if reference != generated {
+            let diff = similar::TextDiff::from_lines(&reference, &generated);
+            print!("{}", diff.unified_diff().context_radius(10).header("reference", "generated"));
+            panic!("Generated does not match reference!")
+        }
+    }
+
+    #[test]
+    fn server_handler_dependency_globals() {
+        let protocol_file =
+            std::fs::File::open("./tests/scanner_assets/test-protocol.xml").unwrap();
+        let protocol_parsed = crate::parse::parse(protocol_file);
+        // a dependency creating the test_global objects, which are thus not globals
+        let dependency = crate::parse::parse(
+            &br#"<protocol name="test-factory">
+                <interface name="test_factory" version="1">
+                    <request name="create">
+                        <arg name="id" type="new_id" interface="test_global" />
+                    </request>
+                </interface>
+            </protocol>"#[..],
+        );
+
+        let generated = super::generate_server_handler(&protocol_parsed, &[]).to_string();
+        assert!(generated.contains("test_global_bind"));
+        assert!(generated.contains("DelegateGlobalDispatch <"));
+        let generated = super::generate_server_handler(&protocol_parsed, &[dependency]).to_string();
+        assert!(!generated.contains("test_global_bind"));
+        assert!(!generated.contains("DelegateGlobalDispatch <"));
+    }
 }
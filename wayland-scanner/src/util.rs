@@ -12,6 +12,15 @@ pub(crate) fn description_to_doc_attr(&(ref short, ref long): &(String, String))
     to_doc_attr(&format!("{}\n\n{}", short, long))
 }
 
+pub(crate) fn deprecated_attr(
+    deprecated_since: Option<impl std::fmt::Display>,
+) -> Option<TokenStream> {
+    deprecated_since.map(|version| {
+        let note = format!("Deprecated since version {} of the interface", version);
+        quote!(#[deprecated(note = #note)])
+    })
+}
+
 pub fn is_keyword(txt: &str) -> bool {
     matches!(
         txt,
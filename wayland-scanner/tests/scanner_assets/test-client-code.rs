@@ -2,10 +2,11 @@
 pub mod wl_display {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = "global error values\n\nThese errors are global and can be emitted in response to any\nserver request."]
@@ -39,6 +40,42 @@ pub mod wl_display {
             val as u32
         }
     }
+    impl Error {
+        #[doc = r" The minimal version of the interface supporting this value"]
+        pub fn since(&self) -> u32 {
+            match self {
+                Error::InvalidObject => 1u32,
+                Error::InvalidMethod => 1u32,
+                Error::NoMemory => 1u32,
+                Error::Implementation => 1u32,
+            }
+        }
+        #[doc = r" A short description of this error"]
+        pub fn description(&self) -> &'static str {
+            match self {
+                Error::InvalidObject => "server couldn't find object",
+                Error::InvalidMethod => {
+                    "method doesn't exist on the specified interface or malformed request"
+                }
+                Error::NoMemory => "server is out of memory",
+                Error::Implementation => "implementation error in compositor",
+            }
+        }
+    }
+    impl super::wayland_client::InterfaceError for Error {
+        #[inline]
+        fn interface() -> &'static Interface {
+            &super::WL_DISPLAY_INTERFACE
+        }
+        #[inline]
+        fn from_code(code: u32) -> Option<Error> {
+            <Error as std::convert::TryFrom<u32>>::try_from(code).ok()
+        }
+        #[inline]
+        fn description(&self) -> &'static str {
+            Error::description(self)
+        }
+    }
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_SYNC_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this request"]
@@ -47,6 +84,40 @@ pub mod wl_display {
     pub const EVT_ERROR_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_DELETE_ID_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "sync",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "get_registry",
+            opcode: 1u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+    ];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "error",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "delete_id",
+            opcode: 1u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+    ];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
@@ -55,13 +126,43 @@ pub mod wl_display {
         #[doc = "get global registry object\n\nThis request creates a registry object that allows the client\nto list and bind the global objects available from the\ncompositor.\n\nIt should be noted that the server side resources consumed in\nresponse to a get_registry request can only be released when the\nclient disconnects, not when the client side proxy is destroyed.\nTherefore, clients should invoke get_registry as infrequently as\npossible to avoid wasting memory."]
         GetRegistry {},
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Sync { .. } => 0u16,
+                Request::GetRegistry { .. } => 1u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {
         #[doc = "fatal error event\n\nThe error event is sent out when a fatal (non-recoverable)\nerror has occurred.  The object_id argument is the object\nwhere the error occurred, most often in response to a request\nto that object.  The code identifies the error and is defined\nby the object interface.  As such, each interface defines its\nown set of error codes.  The message is a brief description\nof the error, for (debugging) convenience."]
         Error {
             #[doc = "object where the error occurred"]
-            object_id: super::wayland_client::ObjectId,
+            object_id: super::wayland_client::backend::ObjectId,
             #[doc = "error code"]
             code: u32,
             #[doc = "error description"]
@@ -73,11 +174,42 @@ pub mod wl_display {
             id: u32,
         },
     }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::Error { .. } => 0u16,
+                Event::DeleteId { .. } => 1u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct WlDisplay {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for WlDisplay {
         fn eq(&self, other: &WlDisplay) -> bool {
@@ -85,6 +217,11 @@ pub mod wl_display {
         }
     }
     impl std::cmp::Eq for WlDisplay {}
+    impl std::hash::Hash for WlDisplay {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for WlDisplay {
         type Request = Request;
         type Event = Event;
@@ -108,13 +245,18 @@ pub mod wl_display {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(WlDisplay { id, data, version })
+            let backend = conn.downgrade();
+            Ok(WlDisplay { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -152,7 +294,7 @@ pub mod wl_display {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::Sync {} => Ok(Message {
                     sender_id: self.id.clone(),
@@ -181,34 +323,86 @@ pub mod wl_display {
             }
         }
     }
+    impl<D, U> EventChildren<D, U> for WlDisplay {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl WlDisplay {
         #[allow(clippy::too_many_arguments)]
-        pub fn sync<D: Dispatch<super::wl_callback::WlCallback> + 'static>(
+        pub fn sync<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::wl_callback::WlCallback, U> + 'static,
+        >(
+            &self,
+            qh: &QueueHandle<D>,
+            udata: U,
+        ) -> Result<super::wl_callback::WlCallback, RequestError>
+        where
+            super::wl_callback::WlCallback: EventChildren<D, U>,
+        {
+            self.send_constructor(
+                Request::Sync {},
+                qh.make_data::<super::wl_callback::WlCallback, U>(udata),
+            )
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn sync_with_handle<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::wl_callback::WlCallback, U> + 'static,
+        >(
             &self,
             conn: &mut ConnectionHandle,
             qh: &QueueHandle<D>,
-            udata: <D as Dispatch<super::wl_callback::WlCallback>>::UserData,
-        ) -> Result<super::wl_callback::WlCallback, InvalidId> {
+            udata: U,
+        ) -> Result<super::wl_callback::WlCallback, RequestError>
+        where
+            super::wl_callback::WlCallback: EventChildren<D, U>,
+        {
             let ret = conn.send_request(
                 self,
                 Request::Sync {},
-                Some(qh.make_data::<super::wl_callback::WlCallback>(udata)),
+                Some(qh.make_data::<super::wl_callback::WlCallback, U>(udata)),
             )?;
-            Proxy::from_id(conn, ret)
+            Ok(Proxy::from_id(conn, ret)?)
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn get_registry<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::wl_registry::WlRegistry, U> + 'static,
+        >(
+            &self,
+            qh: &QueueHandle<D>,
+            udata: U,
+        ) -> Result<super::wl_registry::WlRegistry, RequestError>
+        where
+            super::wl_registry::WlRegistry: EventChildren<D, U>,
+        {
+            self.send_constructor(
+                Request::GetRegistry {},
+                qh.make_data::<super::wl_registry::WlRegistry, U>(udata),
+            )
         }
         #[allow(clippy::too_many_arguments)]
-        pub fn get_registry<D: Dispatch<super::wl_registry::WlRegistry> + 'static>(
+        pub fn get_registry_with_handle<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::wl_registry::WlRegistry, U> + 'static,
+        >(
             &self,
             conn: &mut ConnectionHandle,
             qh: &QueueHandle<D>,
-            udata: <D as Dispatch<super::wl_registry::WlRegistry>>::UserData,
-        ) -> Result<super::wl_registry::WlRegistry, InvalidId> {
+            udata: U,
+        ) -> Result<super::wl_registry::WlRegistry, RequestError>
+        where
+            super::wl_registry::WlRegistry: EventChildren<D, U>,
+        {
             let ret = conn.send_request(
                 self,
                 Request::GetRegistry {},
-                Some(qh.make_data::<super::wl_registry::WlRegistry>(udata)),
+                Some(qh.make_data::<super::wl_registry::WlRegistry, U>(udata)),
             )?;
-            Proxy::from_id(conn, ret)
+            Ok(Proxy::from_id(conn, ret)?)
         }
     }
 }
@@ -216,10 +410,11 @@ pub mod wl_display {
 pub mod wl_registry {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
@@ -228,6 +423,31 @@ pub mod wl_registry {
     pub const EVT_GLOBAL_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_GLOBAL_REMOVE_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "bind",
+        opcode: 0u16,
+        since: 1u32,
+        deprecated_since: None,
+        is_destructor: false,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "global",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "global_remove",
+            opcode: 1u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+    ];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
@@ -239,6 +459,35 @@ pub mod wl_registry {
             id: (&'static Interface, u32)
         },
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Bind { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {
@@ -257,11 +506,42 @@ pub mod wl_registry {
             name: u32,
         },
     }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::Global { .. } => 0u16,
+                Event::GlobalRemove { .. } => 1u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct WlRegistry {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for WlRegistry {
         fn eq(&self, other: &WlRegistry) -> bool {
@@ -269,6 +549,11 @@ pub mod wl_registry {
         }
     }
     impl std::cmp::Eq for WlRegistry {}
+    impl std::hash::Hash for WlRegistry {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for WlRegistry {
         type Request = Request;
         type Event = Event;
@@ -292,13 +577,18 @@ pub mod wl_registry {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(WlRegistry { id, data, version })
+            let backend = conn.downgrade();
+            Ok(WlRegistry { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -337,7 +627,7 @@ pub mod wl_registry {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::Bind { name, id } => Ok(Message {
                     sender_id: self.id.clone(),
@@ -352,23 +642,49 @@ pub mod wl_registry {
             }
         }
     }
+    impl<D, U> EventChildren<D, U> for WlRegistry {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl WlRegistry {
         #[allow(clippy::too_many_arguments)]
-        pub fn bind<I: Proxy + 'static, D: Dispatch<I> + 'static>(
+        pub fn bind<
+            I: Proxy + EventChildren<D, U> + 'static,
+            U: Send + Sync + 'static,
+            D: Dispatch<I, U> + 'static,
+        >(
+            &self,
+            name: u32,
+            version: u32,
+            qh: &QueueHandle<D>,
+            udata: U,
+        ) -> Result<I, RequestError> {
+            self.send_constructor(
+                Request::Bind { name, id: (I::interface(), version) },
+                qh.make_data::<I, U>(udata),
+            )
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn bind_with_handle<
+            I: Proxy + EventChildren<D, U> + 'static,
+            U: Send + Sync + 'static,
+            D: Dispatch<I, U> + 'static,
+        >(
             &self,
             conn: &mut ConnectionHandle,
             name: u32,
             version: u32,
             qh: &QueueHandle<D>,
-            udata: <D as Dispatch<I>>::UserData,
-        ) -> Result<I, InvalidId> {
-            let placeholder = conn.placeholder_id(Some((I::interface(), version)));
+            udata: U,
+        ) -> Result<I, RequestError> {
             let ret = conn.send_request(
                 self,
                 Request::Bind { name, id: (I::interface(), version) },
-                Some(qh.make_data::<I>(udata)),
+                Some(qh.make_data::<I, U>(udata)),
             )?;
-            Proxy::from_id(conn, ret)
+            Ok(Proxy::from_id(conn, ret)?)
         }
     }
 }
@@ -376,17 +692,55 @@ pub mod wl_registry {
 pub mod wl_callback {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_DONE_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[MessageInfo {
+        name: "done",
+        opcode: 0u16,
+        since: 1u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {}
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {
@@ -396,11 +750,41 @@ pub mod wl_callback {
             callback_data: u32,
         },
     }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::Done { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct WlCallback {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for WlCallback {
         fn eq(&self, other: &WlCallback) -> bool {
@@ -408,6 +792,11 @@ pub mod wl_callback {
         }
     }
     impl std::cmp::Eq for WlCallback {}
+    impl std::hash::Hash for WlCallback {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for WlCallback {
         type Request = Request;
         type Event = Event;
@@ -431,13 +820,18 @@ pub mod wl_callback {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(WlCallback { id, data, version })
+            let backend = conn.downgrade();
+            Ok(WlCallback { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -459,21 +853,139 @@ pub mod wl_callback {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {}
         }
     }
+    impl<D, U> EventChildren<D, U> for WlCallback {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl WlCallback {}
 }
 pub mod test_global {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
+    #[repr(u32)]
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum Mode {
+        #[doc = "the fast mode"]
+        Fast = 0,
+        #[doc = "the legacy mode"]
+        #[deprecated(note = "Deprecated since version 3 of the interface")]
+        Legacy = 1,
+        #[doc = "the turbo mode"]
+        Turbo = 2,
+    }
+    #[allow(deprecated)]
+    impl std::convert::TryFrom<u32> for Mode {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Mode, ()> {
+            match val {
+                0 => Ok(Mode::Fast),
+                1 => Ok(Mode::Legacy),
+                2 => Ok(Mode::Turbo),
+                _ => Err(()),
+            }
+        }
+    }
+    impl std::convert::From<Mode> for u32 {
+        fn from(val: Mode) -> u32 {
+            val as u32
+        }
+    }
+    #[allow(deprecated)]
+    impl Mode {
+        #[doc = r" The minimal version of the interface supporting this value"]
+        pub fn since(&self) -> u32 {
+            match self {
+                Mode::Fast => 1u32,
+                Mode::Legacy => 1u32,
+                Mode::Turbo => 3u32,
+            }
+        }
+    }
+    bitflags::bitflags! { pub struct Features : u32 { const None = 0 ; const Basic = 1 ; const Extended = 2 ; } }
+    impl Features {
+        #[doc = r" The minimal version of the interface supporting these flags"]
+        pub fn since(&self) -> u32 {
+            let mut since = 1;
+            if self.contains(Features::Extended) {
+                since = since.max(2u32);
+            }
+            since
+        }
+    }
+    impl std::convert::TryFrom<u32> for Features {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Features, ()> {
+            Features::from_bits(val).ok_or(())
+        }
+    }
+    impl std::convert::From<Features> for u32 {
+        fn from(val: Features) -> u32 {
+            val.bits()
+        }
+    }
+    #[repr(u32)]
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum Error {
+        #[doc = "invalid mode value"]
+        InvalidMode = 0,
+    }
+    impl std::convert::TryFrom<u32> for Error {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Error, ()> {
+            match val {
+                0 => Ok(Error::InvalidMode),
+                _ => Err(()),
+            }
+        }
+    }
+    impl std::convert::From<Error> for u32 {
+        fn from(val: Error) -> u32 {
+            val as u32
+        }
+    }
+    impl Error {
+        #[doc = r" The minimal version of the interface supporting this value"]
+        pub fn since(&self) -> u32 {
+            match self {
+                Error::InvalidMode => 1u32,
+            }
+        }
+        #[doc = r" A short description of this error"]
+        pub fn description(&self) -> &'static str {
+            match self {
+                Error::InvalidMode => "invalid mode value",
+            }
+        }
+    }
+    impl super::wayland_client::InterfaceError for Error {
+        #[inline]
+        fn interface() -> &'static Interface {
+            &super::TEST_GLOBAL_INTERFACE
+        }
+        #[inline]
+        fn from_code(code: u32) -> Option<Error> {
+            <Error as std::convert::TryFrom<u32>>::try_from(code).ok()
+        }
+        #[inline]
+        fn description(&self) -> &'static str {
+            Error::description(self)
+        }
+    }
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_MANY_ARGS_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this request"]
@@ -484,12 +996,101 @@ pub mod test_global {
     pub const REQ_LINK_SINCE: u32 = 3u32;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 4u32;
+    #[doc = r" The minimal object version supporting this request"]
+    pub const REQ_RESET_SINCE: u32 = 1u32;
+    #[doc = r" The minimal object version supporting this request"]
+    pub const REQ_SET_MODE_SINCE: u32 = 2u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_MANY_ARGS_EVT_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_ACK_SECONDARY_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_CYCLE_QUAD_SINCE: u32 = 1u32;
+    #[doc = r" The minimal object version supporting this event"]
+    pub const EVT_LEGACY_EVT_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "many_args",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "get_secondary",
+            opcode: 1u16,
+            since: 2u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "get_tertiary",
+            opcode: 2u16,
+            since: 3u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "link",
+            opcode: 3u16,
+            since: 3u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "destroy",
+            opcode: 4u16,
+            since: 4u32,
+            deprecated_since: None,
+            is_destructor: true,
+        },
+        MessageInfo {
+            name: "reset",
+            opcode: 5u16,
+            since: 1u32,
+            deprecated_since: Some(3u32),
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "set_mode",
+            opcode: 6u16,
+            since: 2u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+    ];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "many_args_evt",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "ack_secondary",
+            opcode: 1u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "cycle_quad",
+            opcode: 2u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "legacy_evt",
+            opcode: 3u16,
+            since: 1u32,
+            deprecated_since: Some(2u32),
+            is_destructor: false,
+        },
+    ];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
@@ -516,6 +1117,45 @@ pub mod test_global {
         Link { sec: super::secondary::Secondary, ter: Option<super::tertiary::Tertiary>, time: u32 },
         #[doc = "This is a destructor, once sent this object cannot be used any longer.\nOnly available since version 4 of the interface"]
         Destroy,
+        #[doc = "reset the global\n\n\n\nDeprecated since version 3 of the interface"]
+        Reset,
+        #[doc = "Only available since version 2 of the interface"]
+        SetMode { mode: WEnum<Mode> },
+    }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::ManyArgs { .. } => 0u16,
+                Request::GetSecondary { .. } => 1u16,
+                Request::GetTertiary { .. } => 2u16,
+                Request::Link { .. } => 3u16,
+                Request::Destroy { .. } => 4u16,
+                Request::Reset { .. } => 5u16,
+                Request::SetMode { .. } => 6u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
     }
     #[derive(Debug)]
     #[non_exhaustive]
@@ -539,12 +1179,47 @@ pub mod test_global {
         AckSecondary { sec: super::secondary::Secondary },
         #[doc = "create a new quad optionally replacing a previous one"]
         CycleQuad { new_quad: super::quad::Quad, old_quad: Option<super::quad::Quad> },
+        #[doc = "an event superseded by ack_secondary\n\n\n\nDeprecated since version 2 of the interface"]
+        LegacyEvt,
+    }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::ManyArgsEvt { .. } => 0u16,
+                Event::AckSecondary { .. } => 1u16,
+                Event::CycleQuad { .. } => 2u16,
+                Event::LegacyEvt { .. } => 3u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
     }
     #[derive(Debug, Clone)]
     pub struct TestGlobal {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for TestGlobal {
         fn eq(&self, other: &TestGlobal) -> bool {
@@ -552,6 +1227,11 @@ pub mod test_global {
         }
     }
     impl std::cmp::Eq for TestGlobal {}
+    impl std::hash::Hash for TestGlobal {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for TestGlobal {
         type Request = Request;
         type Event = Event;
@@ -575,13 +1255,18 @@ pub mod test_global {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(TestGlobal { id, data, version })
+            let backend = conn.downgrade();
+            Ok(TestGlobal { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -673,6 +1358,13 @@ pub mod test_global {
                         Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
                     }
                 }
+                3u16 => {
+                    if let [] = &msg.args[..] {
+                        Ok((me, Event::LegacyEvt {}))
+                    } else {
+                        Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
+                    }
+                }
                 _ => Err(DispatchError::BadMessage { msg, interface: Self::interface().name }),
             }
         }
@@ -680,7 +1372,7 @@ pub mod test_global {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::ManyArgs {
                     unsigned_int,
@@ -743,12 +1435,81 @@ pub mod test_global {
                     opcode: 4u16,
                     args: smallvec::smallvec![],
                 }),
+                Request::Reset {} => Ok(Message {
+                    sender_id: self.id.clone(),
+                    opcode: 5u16,
+                    args: smallvec::smallvec![],
+                }),
+                Request::SetMode { mode } => {
+                    if let WEnum::Value(val) = mode {
+                        if val.since() > self.version {
+                            return Err(RequestError::UnsupportedValue {
+                                interface: Self::interface().name,
+                                request: "set_mode",
+                                arg: "mode",
+                                value: val.into(),
+                                since: val.since(),
+                                version: self.version,
+                            });
+                        }
+                    }
+                    Ok(Message {
+                        sender_id: self.id.clone(),
+                        opcode: 6u16,
+                        args: smallvec::smallvec![Argument::Uint(mode.into())],
+                    })
+                }
+            }
+        }
+    }
+    #[doc = r" User data of the objects created by the events of this interface"]
+    #[doc = r""]
+    #[doc = r" This trait must be implemented by the user data type of the objects of this interface, to"]
+    #[doc = r" provide the user data of each object created by its events."]
+    pub trait ChildrenData<D> {
+        #[doc = "User data of the `quad` objects created by the `cycle_quad` event"]
+        type CycleQuadData: Send + Sync + 'static;
+        #[doc = "Create the user data of a `quad` object created by the `cycle_quad` event"]
+        fn cycle_quad(&self) -> Self::CycleQuadData;
+    }
+    impl<D: 'static, U: ChildrenData<D>> EventChildren<D, U> for TestGlobal
+    where
+        D: Dispatch<super::quad::Quad, <U as ChildrenData<D>>::CycleQuadData>,
+        super::quad::Quad: EventChildren<D, <U as ChildrenData<D>>::CycleQuadData>,
+    {
+        fn event_created_child(
+            opcode: u16,
+            parent_data: &U,
+            qhandle: &QueueHandle<D>,
+        ) -> Option<Arc<dyn ObjectData>> {
+            match opcode {
+                2u16 => Some(qhandle.make_data::<super::quad::Quad, _>(parent_data.cycle_quad())),
+                _ => None,
             }
         }
     }
     impl TestGlobal {
         #[allow(clippy::too_many_arguments)]
         pub fn many_args(
+            &self,
+            unsigned_int: u32,
+            signed_int: i32,
+            fixed_point: f64,
+            number_array: Vec<u8>,
+            some_text: String,
+            file_descriptor: ::std::os::unix::io::RawFd,
+        ) -> Result<(), RequestError> {
+            self.send_request(Request::ManyArgs {
+                unsigned_int,
+                signed_int,
+                fixed_point,
+                number_array,
+                some_text,
+                file_descriptor,
+            })
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn many_args_with_handle(
             &self,
             conn: &mut ConnectionHandle,
             unsigned_int: u32,
@@ -757,8 +1518,8 @@ pub mod test_global {
             number_array: Vec<u8>,
             some_text: String,
             file_descriptor: ::std::os::unix::io::RawFd,
-        ) {
-            let _ = conn.send_request(
+        ) -> Result<(), RequestError> {
+            conn.send_request(
                 self,
                 Request::ManyArgs {
                     unsigned_int,
@@ -769,77 +1530,232 @@ pub mod test_global {
                     file_descriptor,
                 },
                 None,
-            );
+            )
+            .map(|_| ())
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn get_secondary<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::secondary::Secondary, U> + 'static,
+        >(
+            &self,
+            qh: &QueueHandle<D>,
+            udata: U,
+        ) -> Result<super::secondary::Secondary, RequestError>
+        where
+            super::secondary::Secondary: EventChildren<D, U>,
+        {
+            self.send_constructor(
+                Request::GetSecondary {},
+                qh.make_data::<super::secondary::Secondary, U>(udata),
+            )
         }
         #[allow(clippy::too_many_arguments)]
-        pub fn get_secondary<D: Dispatch<super::secondary::Secondary> + 'static>(
+        pub fn get_secondary_with_handle<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::secondary::Secondary, U> + 'static,
+        >(
             &self,
             conn: &mut ConnectionHandle,
             qh: &QueueHandle<D>,
-            udata: <D as Dispatch<super::secondary::Secondary>>::UserData,
-        ) -> Result<super::secondary::Secondary, InvalidId> {
+            udata: U,
+        ) -> Result<super::secondary::Secondary, RequestError>
+        where
+            super::secondary::Secondary: EventChildren<D, U>,
+        {
             let ret = conn.send_request(
                 self,
                 Request::GetSecondary {},
-                Some(qh.make_data::<super::secondary::Secondary>(udata)),
+                Some(qh.make_data::<super::secondary::Secondary, U>(udata)),
             )?;
-            Proxy::from_id(conn, ret)
+            Ok(Proxy::from_id(conn, ret)?)
         }
         #[allow(clippy::too_many_arguments)]
-        pub fn get_tertiary<D: Dispatch<super::tertiary::Tertiary> + 'static>(
+        pub fn get_tertiary<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::tertiary::Tertiary, U> + 'static,
+        >(
+            &self,
+            qh: &QueueHandle<D>,
+            udata: U,
+        ) -> Result<super::tertiary::Tertiary, RequestError>
+        where
+            super::tertiary::Tertiary: EventChildren<D, U>,
+        {
+            self.send_constructor(
+                Request::GetTertiary {},
+                qh.make_data::<super::tertiary::Tertiary, U>(udata),
+            )
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn get_tertiary_with_handle<
+            U: Send + Sync + 'static,
+            D: Dispatch<super::tertiary::Tertiary, U> + 'static,
+        >(
             &self,
             conn: &mut ConnectionHandle,
             qh: &QueueHandle<D>,
-            udata: <D as Dispatch<super::tertiary::Tertiary>>::UserData,
-        ) -> Result<super::tertiary::Tertiary, InvalidId> {
+            udata: U,
+        ) -> Result<super::tertiary::Tertiary, RequestError>
+        where
+            super::tertiary::Tertiary: EventChildren<D, U>,
+        {
             let ret = conn.send_request(
                 self,
                 Request::GetTertiary {},
-                Some(qh.make_data::<super::tertiary::Tertiary>(udata)),
+                Some(qh.make_data::<super::tertiary::Tertiary, U>(udata)),
             )?;
-            Proxy::from_id(conn, ret)
+            Ok(Proxy::from_id(conn, ret)?)
         }
         #[allow(clippy::too_many_arguments)]
         pub fn link(
+            &self,
+            sec: &super::secondary::Secondary,
+            ter: Option<&super::tertiary::Tertiary>,
+            time: u32,
+        ) -> Result<(), RequestError> {
+            self.send_request(Request::Link { sec: sec.clone(), ter: ter.cloned(), time })
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn link_with_handle(
             &self,
             conn: &mut ConnectionHandle,
             sec: &super::secondary::Secondary,
             ter: Option<&super::tertiary::Tertiary>,
             time: u32,
-        ) {
-            let _ = conn.send_request(self, Request::Link { sec: sec.clone(), ter: ter.cloned(), time }, None);
+        ) -> Result<(), RequestError> {
+            conn.send_request(
+                self,
+                Request::Link { sec: sec.clone(), ter: ter.cloned(), time },
+                None,
+            )
+            .map(|_| ())
         }
         #[allow(clippy::too_many_arguments)]
-        pub fn destroy(&self, conn: &mut ConnectionHandle) {
-            let _ = conn.send_request(self, Request::Destroy {}, None);
+        pub fn destroy(&self) -> Result<(), RequestError> {
+            self.send_request(Request::Destroy {})
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn destroy_with_handle(&self, conn: &mut ConnectionHandle) -> Result<(), RequestError> {
+            conn.send_request(self, Request::Destroy {}, None).map(|_| ())
+        }
+        #[deprecated(note = "Deprecated since version 3 of the interface")]
+        #[allow(clippy::too_many_arguments)]
+        pub fn reset(&self) -> Result<(), RequestError> {
+            self.send_request(Request::Reset {})
+        }
+        #[deprecated(note = "Deprecated since version 3 of the interface")]
+        #[allow(clippy::too_many_arguments)]
+        pub fn reset_with_handle(&self, conn: &mut ConnectionHandle) -> Result<(), RequestError> {
+            conn.send_request(self, Request::Reset {}, None).map(|_| ())
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn set_mode(&self, mode: Mode) -> Result<(), RequestError> {
+            self.send_request(Request::SetMode { mode: WEnum::Value(mode) })
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn set_mode_with_handle(
+            &self,
+            conn: &mut ConnectionHandle,
+            mode: Mode,
+        ) -> Result<(), RequestError> {
+            conn.send_request(self, Request::SetMode { mode: WEnum::Value(mode) }, None).map(|_| ())
         }
     }
 }
 pub mod secondary {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 2u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 2u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once sent this object cannot be used any longer.\nOnly available since version 2 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Secondary {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for Secondary {
         fn eq(&self, other: &Secondary) -> bool {
@@ -847,6 +1763,11 @@ pub mod secondary {
         }
     }
     impl std::cmp::Eq for Secondary {}
+    impl std::hash::Hash for Secondary {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for Secondary {
         type Request = Request;
         type Event = Event;
@@ -870,13 +1791,18 @@ pub mod secondary {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(Secondary { id, data, version })
+            let backend = conn.downgrade();
+            Ok(Secondary { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -891,7 +1817,7 @@ pub mod secondary {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::Destroy {} => Ok(Message {
                     sender_id: self.id.clone(),
@@ -901,38 +1827,116 @@ pub mod secondary {
             }
         }
     }
+    impl<D, U> EventChildren<D, U> for Secondary {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl Secondary {
         #[allow(clippy::too_many_arguments)]
-        pub fn destroy(&self, conn: &mut ConnectionHandle) {
-            let _ = conn.send_request(self, Request::Destroy {}, None);
+        pub fn destroy(&self) -> Result<(), RequestError> {
+            self.send_request(Request::Destroy {})
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn destroy_with_handle(&self, conn: &mut ConnectionHandle) -> Result<(), RequestError> {
+            conn.send_request(self, Request::Destroy {}, None).map(|_| ())
         }
     }
 }
 pub mod tertiary {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 3u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 3u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once sent this object cannot be used any longer.\nOnly available since version 3 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Tertiary {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for Tertiary {
         fn eq(&self, other: &Tertiary) -> bool {
@@ -940,6 +1944,11 @@ pub mod tertiary {
         }
     }
     impl std::cmp::Eq for Tertiary {}
+    impl std::hash::Hash for Tertiary {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for Tertiary {
         type Request = Request;
         type Event = Event;
@@ -963,13 +1972,18 @@ pub mod tertiary {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(Tertiary { id, data, version })
+            let backend = conn.downgrade();
+            Ok(Tertiary { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -984,7 +1998,7 @@ pub mod tertiary {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::Destroy {} => Ok(Message {
                     sender_id: self.id.clone(),
@@ -994,38 +2008,116 @@ pub mod tertiary {
             }
         }
     }
+    impl<D, U> EventChildren<D, U> for Tertiary {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl Tertiary {
         #[allow(clippy::too_many_arguments)]
-        pub fn destroy(&self, conn: &mut ConnectionHandle) {
-            let _ = conn.send_request(self, Request::Destroy {}, None);
+        pub fn destroy(&self) -> Result<(), RequestError> {
+            self.send_request(Request::Destroy {})
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn destroy_with_handle(&self, conn: &mut ConnectionHandle) -> Result<(), RequestError> {
+            conn.send_request(self, Request::Destroy {}, None).map(|_| ())
         }
     }
 }
 pub mod quad {
     use super::wayland_client::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
-        ConnectionHandle, Dispatch, DispatchError, Proxy, QueueHandle, QueueProxyData,
+        ConnectionHandle, Dispatch, DispatchError, EventChildren, Proxy, QueueHandle,
+        QueueProxyData, RequestError, WeakConnection,
     };
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 3u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 3u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once sent this object cannot be used any longer.\nOnly available since version 3 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Quad {
         id: ObjectId,
         version: u32,
         data: Option<Arc<dyn ObjectData>>,
+        backend: WeakConnection,
     }
     impl std::cmp::PartialEq for Quad {
         fn eq(&self, other: &Quad) -> bool {
@@ -1033,6 +2125,11 @@ pub mod quad {
         }
     }
     impl std::cmp::Eq for Quad {}
+    impl std::hash::Hash for Quad {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_client::Proxy for Quad {
         type Request = Request;
         type Event = Event;
@@ -1056,13 +2153,18 @@ pub mod quad {
                 .map(|data| &data.udata)
         }
         #[inline]
+        fn connection(&self) -> &WeakConnection {
+            &self.backend
+        }
+        #[inline]
         fn from_id(conn: &mut ConnectionHandle, id: ObjectId) -> Result<Self, InvalidId> {
             if !same_interface(id.interface(), Self::interface()) && !id.is_null() {
                 return Err(InvalidId);
             }
             let version = conn.object_info(id.clone()).map(|info| info.version).unwrap_or(0);
             let data = conn.get_object_data(id.clone()).ok();
-            Ok(Quad { id, data, version })
+            let backend = conn.downgrade();
+            Ok(Quad { id, data, version, backend })
         }
         fn parse_event(
             conn: &mut ConnectionHandle,
@@ -1077,7 +2179,7 @@ pub mod quad {
             &self,
             conn: &mut ConnectionHandle,
             msg: Self::Request,
-        ) -> Result<Message<ObjectId>, InvalidId> {
+        ) -> Result<Message<ObjectId>, RequestError> {
             match msg {
                 Request::Destroy {} => Ok(Message {
                     sender_id: self.id.clone(),
@@ -1087,10 +2189,20 @@ pub mod quad {
             }
         }
     }
+    impl<D, U> EventChildren<D, U> for Quad {
+        #[inline]
+        fn event_created_child(_: u16, _: &U, _: &QueueHandle<D>) -> Option<Arc<dyn ObjectData>> {
+            None
+        }
+    }
     impl Quad {
         #[allow(clippy::too_many_arguments)]
-        pub fn destroy(&self, conn: &mut ConnectionHandle) {
-            let _ = conn.send_request(self, Request::Destroy {}, None);
+        pub fn destroy(&self) -> Result<(), RequestError> {
+            self.send_request(Request::Destroy {})
+        }
+        #[allow(clippy::too_many_arguments)]
+        pub fn destroy_with_handle(&self, conn: &mut ConnectionHandle) -> Result<(), RequestError> {
+            conn.send_request(self, Request::Destroy {}, None).map(|_| ())
         }
     }
 }
@@ -0,0 +1,331 @@
+pub mod handler {
+    #[allow(unused_imports)]
+    use super::wayland_client::{
+        backend::{protocol::WEnum, ObjectId},
+        ConnectionHandle, DelegateDispatch, Dispatch, QueueHandle,
+    };
+    #[doc = "Handler of all the events of the `test-protocol` protocol\n\nThe user data of the objects of each interface is set by the associated types of this trait. Its implementation is wired to the [`Dispatch`] implementations of your state by the `delegate_test_protocol!` macro."]
+    pub trait TestProtocolHandler: Sized {
+        #[doc = "User data of the `wl_callback` objects"]
+        type WlCallbackData: Send + Sync + 'static;
+        #[doc = "User data of the `test_global` objects"]
+        type TestGlobalData: Send + Sync + 'static;
+        #[doc = "User data of the `secondary` objects"]
+        type SecondaryData: Send + Sync + 'static;
+        #[doc = "User data of the `tertiary` objects"]
+        type TertiaryData: Send + Sync + 'static;
+        #[doc = "User data of the `quad` objects"]
+        type QuadData: Send + Sync + 'static;
+        #[doc = "The `wl_callback.done` event\n\ndone event"]
+        #[allow(clippy::too_many_arguments)]
+        fn wl_callback_done(
+            &mut self,
+            proxy: &super::wl_callback::WlCallback,
+            callback_data: u32,
+            data: &Self::WlCallbackData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<Self>,
+        );
+        #[doc = "The `test_global.many_args_evt` event\n\nan event with every possible non-object arg"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_many_args_evt(
+            &mut self,
+            proxy: &super::test_global::TestGlobal,
+            unsigned_int: u32,
+            signed_int: i32,
+            fixed_point: f64,
+            number_array: Vec<u8>,
+            some_text: String,
+            file_descriptor: ::std::os::unix::io::RawFd,
+            data: &Self::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<Self>,
+        );
+        #[doc = "The `test_global.ack_secondary` event\n\nacking the creation of a secondary"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_ack_secondary(
+            &mut self,
+            proxy: &super::test_global::TestGlobal,
+            sec: super::secondary::Secondary,
+            data: &Self::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<Self>,
+        );
+        #[doc = "The `test_global.cycle_quad` event\n\ncreate a new quad optionally replacing a previous one"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_cycle_quad(
+            &mut self,
+            proxy: &super::test_global::TestGlobal,
+            new_quad: super::quad::Quad,
+            old_quad: Option<super::quad::Quad>,
+            data: &Self::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<Self>,
+        );
+        #[doc = "The `test_global.legacy_evt` event\n\nan event superseded by ack_secondary"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_legacy_evt(
+            &mut self,
+            proxy: &super::test_global::TestGlobal,
+            data: &Self::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<Self>,
+        );
+        #[doc = "A `wl_callback` object has been destroyed\n\nBy default this method does nothing."]
+        fn wl_callback_destroyed(
+            &mut self,
+            _object: ObjectId,
+            _data: &Self::WlCallbackData,
+            _connhandle: &mut ConnectionHandle,
+            _qhandle: &QueueHandle<Self>,
+        ) {
+        }
+        #[doc = "A `test_global` object has been destroyed\n\nBy default this method does nothing."]
+        fn test_global_destroyed(
+            &mut self,
+            _object: ObjectId,
+            _data: &Self::TestGlobalData,
+            _connhandle: &mut ConnectionHandle,
+            _qhandle: &QueueHandle<Self>,
+        ) {
+        }
+        #[doc = "A `secondary` object has been destroyed\n\nBy default this method does nothing."]
+        fn secondary_destroyed(
+            &mut self,
+            _object: ObjectId,
+            _data: &Self::SecondaryData,
+            _connhandle: &mut ConnectionHandle,
+            _qhandle: &QueueHandle<Self>,
+        ) {
+        }
+        #[doc = "A `tertiary` object has been destroyed\n\nBy default this method does nothing."]
+        fn tertiary_destroyed(
+            &mut self,
+            _object: ObjectId,
+            _data: &Self::TertiaryData,
+            _connhandle: &mut ConnectionHandle,
+            _qhandle: &QueueHandle<Self>,
+        ) {
+        }
+        #[doc = "A `quad` object has been destroyed\n\nBy default this method does nothing."]
+        fn quad_destroyed(
+            &mut self,
+            _object: ObjectId,
+            _data: &Self::QuadData,
+            _connhandle: &mut ConnectionHandle,
+            _qhandle: &QueueHandle<Self>,
+        ) {
+        }
+    }
+    #[doc = "Delegate type implementing the events of the `test-protocol` protocol from [`TestProtocolHandler`]"]
+    #[derive(Debug)]
+    pub struct TestProtocolAdapter<D>(::std::marker::PhantomData<D>);
+    impl<D>
+        DelegateDispatch<
+            super::wl_callback::WlCallback,
+            <D as TestProtocolHandler>::WlCallbackData,
+            D,
+        > for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::wl_callback::WlCallback, <D as TestProtocolHandler>::WlCallbackData>
+            + TestProtocolHandler,
+    {
+        fn event(
+            state: &mut D,
+            proxy: &super::wl_callback::WlCallback,
+            event: super::wl_callback::Event,
+            data: &<D as TestProtocolHandler>::WlCallbackData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            match event {
+                super::wl_callback::Event::Done { callback_data: arg_callback_data } => {
+                    <D as TestProtocolHandler>::wl_callback_done(
+                        state,
+                        proxy,
+                        arg_callback_data,
+                        data,
+                        connhandle,
+                        qhandle,
+                    )
+                }
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            object: ObjectId,
+            data: &<D as TestProtocolHandler>::WlCallbackData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            <D as TestProtocolHandler>::wl_callback_destroyed(
+                state, object, data, connhandle, qhandle,
+            )
+        }
+    }
+    impl<D>
+        DelegateDispatch<
+            super::test_global::TestGlobal,
+            <D as TestProtocolHandler>::TestGlobalData,
+            D,
+        > for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::test_global::TestGlobal, <D as TestProtocolHandler>::TestGlobalData>
+            + TestProtocolHandler,
+    {
+        fn event(
+            state: &mut D,
+            proxy: &super::test_global::TestGlobal,
+            event: super::test_global::Event,
+            data: &<D as TestProtocolHandler>::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            match event {
+                super::test_global::Event::ManyArgsEvt {
+                    unsigned_int: arg_unsigned_int,
+                    signed_int: arg_signed_int,
+                    fixed_point: arg_fixed_point,
+                    number_array: arg_number_array,
+                    some_text: arg_some_text,
+                    file_descriptor: arg_file_descriptor,
+                } => <D as TestProtocolHandler>::test_global_many_args_evt(
+                    state,
+                    proxy,
+                    arg_unsigned_int,
+                    arg_signed_int,
+                    arg_fixed_point,
+                    arg_number_array,
+                    arg_some_text,
+                    arg_file_descriptor,
+                    data,
+                    connhandle,
+                    qhandle,
+                ),
+                super::test_global::Event::AckSecondary { sec: arg_sec } => {
+                    <D as TestProtocolHandler>::test_global_ack_secondary(
+                        state, proxy, arg_sec, data, connhandle, qhandle,
+                    )
+                }
+                super::test_global::Event::CycleQuad {
+                    new_quad: arg_new_quad,
+                    old_quad: arg_old_quad,
+                } => <D as TestProtocolHandler>::test_global_cycle_quad(
+                    state,
+                    proxy,
+                    arg_new_quad,
+                    arg_old_quad,
+                    data,
+                    connhandle,
+                    qhandle,
+                ),
+                super::test_global::Event::LegacyEvt {} => {
+                    <D as TestProtocolHandler>::test_global_legacy_evt(
+                        state, proxy, data, connhandle, qhandle,
+                    )
+                }
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            object: ObjectId,
+            data: &<D as TestProtocolHandler>::TestGlobalData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            <D as TestProtocolHandler>::test_global_destroyed(
+                state, object, data, connhandle, qhandle,
+            )
+        }
+    }
+    impl<D>
+        DelegateDispatch<super::secondary::Secondary, <D as TestProtocolHandler>::SecondaryData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::secondary::Secondary, <D as TestProtocolHandler>::SecondaryData>
+            + TestProtocolHandler,
+    {
+        #[allow(unused_variables)]
+        fn event(
+            state: &mut D,
+            proxy: &super::secondary::Secondary,
+            event: super::secondary::Event,
+            data: &<D as TestProtocolHandler>::SecondaryData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            match event {}
+        }
+        fn destroyed(
+            state: &mut D,
+            object: ObjectId,
+            data: &<D as TestProtocolHandler>::SecondaryData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            <D as TestProtocolHandler>::secondary_destroyed(
+                state, object, data, connhandle, qhandle,
+            )
+        }
+    }
+    impl<D> DelegateDispatch<super::tertiary::Tertiary, <D as TestProtocolHandler>::TertiaryData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::tertiary::Tertiary, <D as TestProtocolHandler>::TertiaryData>
+            + TestProtocolHandler,
+    {
+        #[allow(unused_variables)]
+        fn event(
+            state: &mut D,
+            proxy: &super::tertiary::Tertiary,
+            event: super::tertiary::Event,
+            data: &<D as TestProtocolHandler>::TertiaryData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            match event {}
+        }
+        fn destroyed(
+            state: &mut D,
+            object: ObjectId,
+            data: &<D as TestProtocolHandler>::TertiaryData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            <D as TestProtocolHandler>::tertiary_destroyed(state, object, data, connhandle, qhandle)
+        }
+    }
+    impl<D> DelegateDispatch<super::quad::Quad, <D as TestProtocolHandler>::QuadData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::quad::Quad, <D as TestProtocolHandler>::QuadData> + TestProtocolHandler,
+    {
+        #[allow(unused_variables)]
+        fn event(
+            state: &mut D,
+            proxy: &super::quad::Quad,
+            event: super::quad::Event,
+            data: &<D as TestProtocolHandler>::QuadData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            match event {}
+        }
+        fn destroyed(
+            state: &mut D,
+            object: ObjectId,
+            data: &<D as TestProtocolHandler>::QuadData,
+            connhandle: &mut ConnectionHandle,
+            qhandle: &QueueHandle<D>,
+        ) {
+            <D as TestProtocolHandler>::quad_destroyed(state, object, data, connhandle, qhandle)
+        }
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use super::wayland_client::delegate_dispatch as __delegate_dispatch;
+    #[doc = "Implements [`Dispatch`] for all the interfaces of the `test-protocol` protocol by delegating them to [`TestProtocolAdapter`]\n\nThe user data of each interface is the matching associated type of the [`TestProtocolHandler`] implementation of your state. The second argument is the path of the module in which the protocol was generated:\n\n```ignore\nprotocol::handler::delegate_test_protocol!(State => protocol);\n```"]
+    macro_rules ! delegate_test_protocol { ($ dispatch_from : ty => $ ($ module : ident) :: +) => { $ ($ module) :: + :: handler :: __delegate_dispatch ! ($ dispatch_from : [$ ($ module) :: + :: wl_callback :: WlCallback : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: WlCallbackData , $ ($ module) :: + :: test_global :: TestGlobal : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: TestGlobalData , $ ($ module) :: + :: secondary :: Secondary : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: SecondaryData , $ ($ module) :: + :: tertiary :: Tertiary : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: TertiaryData , $ ($ module) :: + :: quad :: Quad : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: QuadData] => $ ($ module) :: + :: handler :: TestProtocolAdapter < $ dispatch_from >) ; } ; }
+    #[allow(unused_imports)]
+    pub(crate) use delegate_test_protocol;
+}
@@ -15,6 +15,7 @@ pub static WL_DISPLAY_INTERFACE: wayland_backend::protocol::Interface = wayland_
             name: "sync",
             signature: &[wayland_backend::protocol::ArgumentType::NewId(wayland_backend::protocol::AllowNull::No)],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: Some(&WL_CALLBACK_INTERFACE),
             arg_interfaces: &[],
@@ -23,6 +24,7 @@ pub static WL_DISPLAY_INTERFACE: wayland_backend::protocol::Interface = wayland_
             name: "get_registry",
             signature: &[wayland_backend::protocol::ArgumentType::NewId(wayland_backend::protocol::AllowNull::No)],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: Some(&WL_REGISTRY_INTERFACE),
             arg_interfaces: &[],
@@ -37,6 +39,7 @@ pub static WL_DISPLAY_INTERFACE: wayland_backend::protocol::Interface = wayland_
                 wayland_backend::protocol::ArgumentType::Str(wayland_backend::protocol::AllowNull::No),
             ],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[&wayland_backend::protocol::ANONYMOUS_INTERFACE],
@@ -45,6 +48,7 @@ pub static WL_DISPLAY_INTERFACE: wayland_backend::protocol::Interface = wayland_
             name: "delete_id",
             signature: &[wayland_backend::protocol::ArgumentType::Uint],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[],
@@ -102,6 +106,7 @@ pub static WL_REGISTRY_INTERFACE: wayland_backend::protocol::Interface = wayland
             wayland_backend::protocol::ArgumentType::NewId(wayland_backend::protocol::AllowNull::No),
         ],
         since: 1u32,
+        deprecated_since: None,
         is_destructor: false,
         child_interface: None,
         arg_interfaces: &[],
@@ -115,6 +120,7 @@ pub static WL_REGISTRY_INTERFACE: wayland_backend::protocol::Interface = wayland
                 wayland_backend::protocol::ArgumentType::Uint,
             ],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[],
@@ -123,6 +129,7 @@ pub static WL_REGISTRY_INTERFACE: wayland_backend::protocol::Interface = wayland
             name: "global_remove",
             signature: &[wayland_backend::protocol::ArgumentType::Uint],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[],
@@ -165,6 +172,7 @@ pub static WL_CALLBACK_INTERFACE: wayland_backend::protocol::Interface = wayland
         name: "done",
         signature: &[wayland_backend::protocol::ArgumentType::Uint],
         since: 1u32,
+        deprecated_since: None,
         is_destructor: true,
         child_interface: None,
         arg_interfaces: &[],
@@ -201,6 +209,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
                 wayland_backend::protocol::ArgumentType::Fd,
             ],
             since: 1u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[],
@@ -209,6 +218,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
             name: "get_secondary",
             signature: &[wayland_backend::protocol::ArgumentType::NewId(wayland_backend::protocol::AllowNull::No)],
             since: 2u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: Some(&SECONDARY_INTERFACE),
             arg_interfaces: &[],
@@ -217,6 +227,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
             name: "get_tertiary",
             signature: &[wayland_backend::protocol::ArgumentType::NewId(wayland_backend::protocol::AllowNull::No)],
             since: 3u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: Some(&TERTIARY_INTERFACE),
             arg_interfaces: &[],
@@ -229,6 +240,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
                 wayland_backend::protocol::ArgumentType::Uint,
             ],
             since: 3u32,
+            deprecated_since: None,
             is_destructor: false,
             child_interface: None,
             arg_interfaces: &[&SECONDARY_INTERFACE, &TERTIARY_INTERFACE],
@@ -237,10 +249,29 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
             name: "destroy",
             signature: &[],
             since: 4u32,
+            deprecated_since: None,
             is_destructor: true,
             child_interface: None,
             arg_interfaces: &[]
         },
+        wayland_backend::protocol::MessageDesc {
+            name: "reset",
+            signature: &[],
+            since: 1u32,
+            deprecated_since: Some(3u32),
+            is_destructor: false,
+            child_interface: None,
+            arg_interfaces: &[],
+        },
+        wayland_backend::protocol::MessageDesc {
+            name: "set_mode",
+            signature: &[wayland_backend::protocol::ArgumentType::Uint],
+            since: 2u32,
+            deprecated_since: None,
+            is_destructor: false,
+            child_interface: None,
+            arg_interfaces: &[],
+        },
     ],
     events: &[
     wayland_backend::protocol::MessageDesc {
@@ -254,6 +285,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
             wayland_backend::protocol::ArgumentType::Fd,
         ],
         since: 1u32,
+        deprecated_since: None,
         is_destructor: false,
         child_interface: None,
         arg_interfaces: &[],
@@ -262,6 +294,7 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
         name: "ack_secondary",
         signature: &[wayland_backend::protocol::ArgumentType::Object(wayland_backend::protocol::AllowNull::No)],
         since: 1u32,
+        deprecated_since: None,
         is_destructor: false,
         child_interface: None,
         arg_interfaces: &[&SECONDARY_INTERFACE],
@@ -273,10 +306,20 @@ pub static TEST_GLOBAL_INTERFACE: wayland_backend::protocol::Interface = wayland
             wayland_backend::protocol::ArgumentType::Object(wayland_backend::protocol::AllowNull::Yes),
         ],
         since: 1u32,
+        deprecated_since: None,
         is_destructor: false,
         child_interface: Some(&QUAD_INTERFACE),
         arg_interfaces: &[&QUAD_INTERFACE],
     },
+    wayland_backend::protocol::MessageDesc {
+        name: "legacy_evt",
+        signature: &[],
+        since: 1u32,
+        deprecated_since: Some(2u32),
+        is_destructor: false,
+        child_interface: None,
+        arg_interfaces: &[],
+    },
 ],
     c_ptr: Some(unsafe { &test_global_interface }),
 };
@@ -291,7 +334,7 @@ static mut test_global_requests_link_types: [*const wayland_backend::protocol::w
     unsafe { &tertiary_interface as *const wayland_backend::protocol::wl_interface },
     NULLPTR as *const wayland_backend::protocol::wl_interface,
 ];
-pub static mut test_global_requests: [wayland_backend::protocol::wl_message; 5] = [
+pub static mut test_global_requests: [wayland_backend::protocol::wl_message; 7] = [
     wayland_backend::protocol::wl_message {
         name: b"many_args\0" as *const u8 as *const std::os::raw::c_char,
         signature: b"uifash\0" as *const u8 as *const std::os::raw::c_char,
@@ -317,6 +360,16 @@ pub static mut test_global_requests: [wayland_backend::protocol::wl_message; 5]
         signature: b"4\0" as *const u8 as *const std::os::raw::c_char,
         types: unsafe { &types_null as *const _ },
     },
+    wayland_backend::protocol::wl_message {
+        name: b"reset\0" as *const u8 as *const std::os::raw::c_char,
+        signature: b"\0" as *const u8 as *const std::os::raw::c_char,
+        types: unsafe { &types_null as *const _ },
+    },
+    wayland_backend::protocol::wl_message {
+        name: b"set_mode\0" as *const u8 as *const std::os::raw::c_char,
+        signature: b"2u\0" as *const u8 as *const std::os::raw::c_char,
+        types: unsafe { &types_null as *const _ },
+    },
 ];
 static mut test_global_events_ack_secondary_types:
     [*const wayland_backend::protocol::wl_interface; 1] =
@@ -326,7 +379,7 @@ static mut test_global_events_cycle_quad_types:
     [unsafe { &quad_interface as *const wayland_backend::protocol::wl_interface }, unsafe {
         &quad_interface as *const wayland_backend::protocol::wl_interface
     }];
-pub static mut test_global_events: [wayland_backend::protocol::wl_message; 3] = [
+pub static mut test_global_events: [wayland_backend::protocol::wl_message; 4] = [
     wayland_backend::protocol::wl_message {
         name: b"many_args_evt\0" as *const u8 as *const std::os::raw::c_char,
         signature: b"uifash\0" as *const u8 as *const std::os::raw::c_char,
@@ -342,14 +395,19 @@ pub static mut test_global_events: [wayland_backend::protocol::wl_message; 3] =
         signature: b"n?o\0" as *const u8 as *const std::os::raw::c_char,
         types: unsafe { &test_global_events_cycle_quad_types as *const _ },
     },
+    wayland_backend::protocol::wl_message {
+        name: b"legacy_evt\0" as *const u8 as *const std::os::raw::c_char,
+        signature: b"\0" as *const u8 as *const std::os::raw::c_char,
+        types: unsafe { &types_null as *const _ },
+    },
 ];
 pub static mut test_global_interface: wayland_backend::protocol::wl_interface =
     wayland_backend::protocol::wl_interface {
         name: b"test_global\0" as *const u8 as *const std::os::raw::c_char,
         version: 3,
-        request_count: 5,
+        request_count: 7,
         requests: unsafe { &test_global_requests as *const _ },
-        event_count: 3,
+        event_count: 4,
         events: unsafe { &test_global_events as *const _ },
     };
 pub static SECONDARY_INTERFACE: wayland_backend::protocol::Interface = wayland_backend::protocol::Interface {
@@ -359,6 +417,7 @@ pub static SECONDARY_INTERFACE: wayland_backend::protocol::Interface = wayland_b
         name: "destroy",
         signature: &[],
         since: 2u32,
+        deprecated_since: None,
         is_destructor: true,
         child_interface: None,
         arg_interfaces: &[],
@@ -388,6 +447,7 @@ pub static TERTIARY_INTERFACE: wayland_backend::protocol::Interface = wayland_ba
         name: "destroy",
         signature: &[],
         since: 3u32,
+        deprecated_since: None,
         is_destructor: true,
         child_interface: None,
         arg_interfaces: &[],
@@ -417,6 +477,7 @@ pub static mut tertiary_interface: wayland_backend::protocol::wl_interface =
             name: "destroy",
             signature: &[],
             since: 3u32,
+            deprecated_since: None,
             is_destructor: true,
             child_interface: None,
             arg_interfaces: &[],
@@ -2,7 +2,7 @@
 pub mod wl_callback {
     use super::wayland_server::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
         Dispatch, DispatchError, DisplayHandle, New, Resource, ResourceData,
@@ -10,9 +10,46 @@ pub mod wl_callback {
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_DONE_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[MessageInfo {
+        name: "done",
+        opcode: 0u16,
+        since: 1u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {}
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {
@@ -22,6 +59,35 @@ pub mod wl_callback {
             callback_data: u32
         },
     }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::Done { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct WlCallback {
         id: ObjectId,
@@ -34,6 +100,11 @@ pub mod wl_callback {
         }
     }
     impl std::cmp::Eq for WlCallback {}
+    impl std::hash::Hash for WlCallback {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_server::Resource for WlCallback {
         type Request = Request;
         type Event = Event;
@@ -104,12 +175,109 @@ pub mod wl_callback {
 pub mod test_global {
     use super::wayland_server::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
         Dispatch, DispatchError, DisplayHandle, New, Resource, ResourceData,
     };
     use std::sync::Arc;
+    #[repr(u32)]
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum Mode {
+        #[doc = "the fast mode"]
+        Fast = 0,
+        #[doc = "the legacy mode"]
+        #[deprecated(note = "Deprecated since version 3 of the interface")]
+        Legacy = 1,
+        #[doc = "the turbo mode"]
+        Turbo = 2,
+    }
+    #[allow(deprecated)]
+    impl std::convert::TryFrom<u32> for Mode {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Mode, ()> {
+            match val {
+                0 => Ok(Mode::Fast),
+                1 => Ok(Mode::Legacy),
+                2 => Ok(Mode::Turbo),
+                _ => Err(()),
+            }
+        }
+    }
+    impl std::convert::From<Mode> for u32 {
+        fn from(val: Mode) -> u32 {
+            val as u32
+        }
+    }
+    #[allow(deprecated)]
+    impl Mode {
+        #[doc = r" The minimal version of the interface supporting this value"]
+        pub fn since(&self) -> u32 {
+            match self {
+                Mode::Fast => 1u32,
+                Mode::Legacy => 1u32,
+                Mode::Turbo => 3u32,
+            }
+        }
+    }
+    bitflags::bitflags! { pub struct Features : u32 { const None = 0 ; const Basic = 1 ; const Extended = 2 ; } }
+    impl Features {
+        #[doc = r" The minimal version of the interface supporting these flags"]
+        pub fn since(&self) -> u32 {
+            let mut since = 1;
+            if self.contains(Features::Extended) {
+                since = since.max(2u32);
+            }
+            since
+        }
+    }
+    impl std::convert::TryFrom<u32> for Features {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Features, ()> {
+            Features::from_bits(val).ok_or(())
+        }
+    }
+    impl std::convert::From<Features> for u32 {
+        fn from(val: Features) -> u32 {
+            val.bits()
+        }
+    }
+    #[repr(u32)]
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    #[non_exhaustive]
+    pub enum Error {
+        #[doc = "invalid mode value"]
+        InvalidMode = 0,
+    }
+    impl std::convert::TryFrom<u32> for Error {
+        type Error = ();
+        fn try_from(val: u32) -> Result<Error, ()> {
+            match val {
+                0 => Ok(Error::InvalidMode),
+                _ => Err(()),
+            }
+        }
+    }
+    impl std::convert::From<Error> for u32 {
+        fn from(val: Error) -> u32 {
+            val as u32
+        }
+    }
+    impl Error {
+        #[doc = r" The minimal version of the interface supporting this value"]
+        pub fn since(&self) -> u32 {
+            match self {
+                Error::InvalidMode => 1u32,
+            }
+        }
+        #[doc = r" A short description of this error"]
+        pub fn description(&self) -> &'static str {
+            match self {
+                Error::InvalidMode => "invalid mode value",
+            }
+        }
+    }
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_MANY_ARGS_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this request"]
@@ -120,12 +288,101 @@ pub mod test_global {
     pub const REQ_LINK_SINCE: u32 = 3u32;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 4u32;
+    #[doc = r" The minimal object version supporting this request"]
+    pub const REQ_RESET_SINCE: u32 = 1u32;
+    #[doc = r" The minimal object version supporting this request"]
+    pub const REQ_SET_MODE_SINCE: u32 = 2u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_MANY_ARGS_EVT_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_ACK_SECONDARY_SINCE: u32 = 1u32;
     #[doc = r" The minimal object version supporting this event"]
     pub const EVT_CYCLE_QUAD_SINCE: u32 = 1u32;
+    #[doc = r" The minimal object version supporting this event"]
+    pub const EVT_LEGACY_EVT_SINCE: u32 = 1u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "many_args",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "get_secondary",
+            opcode: 1u16,
+            since: 2u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "get_tertiary",
+            opcode: 2u16,
+            since: 3u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "link",
+            opcode: 3u16,
+            since: 3u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "destroy",
+            opcode: 4u16,
+            since: 4u32,
+            deprecated_since: None,
+            is_destructor: true,
+        },
+        MessageInfo {
+            name: "reset",
+            opcode: 5u16,
+            since: 1u32,
+            deprecated_since: Some(3u32),
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "set_mode",
+            opcode: 6u16,
+            since: 2u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+    ];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[
+        MessageInfo {
+            name: "many_args_evt",
+            opcode: 0u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "ack_secondary",
+            opcode: 1u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "cycle_quad",
+            opcode: 2u16,
+            since: 1u32,
+            deprecated_since: None,
+            is_destructor: false,
+        },
+        MessageInfo {
+            name: "legacy_evt",
+            opcode: 3u16,
+            since: 1u32,
+            deprecated_since: Some(2u32),
+            is_destructor: false,
+        },
+    ];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
@@ -158,6 +415,45 @@ pub mod test_global {
         Link { sec: super::secondary::Secondary, ter: Option<super::tertiary::Tertiary>, time: u32 },
         #[doc = "This is a destructor, once received this object cannot be used any longer.\nOnly available since version 4 of the interface"]
         Destroy,
+        #[doc = "reset the global\n\n\n\nDeprecated since version 3 of the interface"]
+        Reset,
+        #[doc = "Only available since version 2 of the interface"]
+        SetMode { mode: WEnum<Mode> },
+    }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::ManyArgs { .. } => 0u16,
+                Request::GetSecondary { .. } => 1u16,
+                Request::GetTertiary { .. } => 2u16,
+                Request::Link { .. } => 3u16,
+                Request::Destroy { .. } => 4u16,
+                Request::Reset { .. } => 5u16,
+                Request::SetMode { .. } => 6u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
     }
     #[derive(Debug)]
     #[non_exhaustive]
@@ -181,6 +477,40 @@ pub mod test_global {
         AckSecondary { sec: super::secondary::Secondary },
         #[doc = "create a new quad optionally replacing a previous one"]
         CycleQuad { new_quad: super::quad::Quad, old_quad: Option<super::quad::Quad> },
+        #[doc = "an event superseded by ack_secondary\n\n\n\nDeprecated since version 2 of the interface"]
+        LegacyEvt,
+    }
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Event::ManyArgsEvt { .. } => 0u16,
+                Event::AckSecondary { .. } => 1u16,
+                Event::CycleQuad { .. } => 2u16,
+                Event::LegacyEvt { .. } => 3u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
     }
     #[derive(Debug, Clone)]
     pub struct TestGlobal {
@@ -194,6 +524,11 @@ pub mod test_global {
         }
     }
     impl std::cmp::Eq for TestGlobal {}
+    impl std::hash::Hash for TestGlobal {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_server::Resource for TestGlobal {
         type Request = Request;
         type Event = Event;
@@ -352,6 +687,40 @@ pub mod test_global {
                         Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
                     }
                 }
+                5u16 => {
+                    if let [] = &msg.args[..] {
+                        Ok((me, Request::Reset {}))
+                    } else {
+                        Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
+                    }
+                }
+                6u16 => {
+                    if let [Argument::Uint(mode)] = &msg.args[..] {
+                        let version = me.version();
+                        Ok((
+                            me,
+                            Request::SetMode {
+                                mode: match WEnum::<Mode>::from(*mode as u32) {
+                                    WEnum::Value(val) if val.since() <= version => {
+                                        WEnum::Value(val)
+                                    }
+                                    _ => {
+                                        return Err(DispatchError::InvalidValue {
+                                            value: *mode as u32,
+                                            code: 0u32,
+                                            interface: Self::interface().name,
+                                            request: "set_mode",
+                                            arg: "mode",
+                                            msg,
+                                        })
+                                    }
+                                },
+                            },
+                        ))
+                    } else {
+                        Err(DispatchError::BadMessage { msg, interface: Self::interface().name })
+                    }
+                }
                 _ => Err(DispatchError::BadMessage { msg, interface: Self::interface().name }),
             }
         }
@@ -397,6 +766,11 @@ pub mod test_global {
                         }
                     ],
                 }),
+                Event::LegacyEvt {} => Ok(Message {
+                    sender_id: self.id.clone(),
+                    opcode: 3u16,
+                    args: smallvec::smallvec![],
+                }),
             }
         }
         fn __set_object_data(
@@ -407,6 +781,18 @@ pub mod test_global {
         }
     }
     impl TestGlobal {
+        #[doc = r" Post a protocol error on this object"]
+        #[doc = r""]
+        #[doc = r" This shadows [`Resource::post_error()`] to only accept the error codes of this interface."]
+        #[inline]
+        pub fn post_error(&self, dh: &mut DisplayHandle, error: Error, message: impl Into<String>) {
+            <Self as Resource>::post_error(self, dh, error, message)
+        }
+        #[doc = r" Post a protocol error on this object, using the description of the error as message"]
+        #[inline]
+        pub fn post_error_default(&self, dh: &mut DisplayHandle, error: Error) {
+            <Self as Resource>::post_error(self, dh, error, error.description())
+        }
         #[allow(clippy::too_many_arguments)]
         pub fn many_args_evt(
             &self,
@@ -447,12 +833,17 @@ pub mod test_global {
         ) {
             let _ = conn.send_event(self, Event::CycleQuad { new_quad: new_quad.clone(), old_quad: old_quad.cloned() });
         }
+        #[deprecated(note = "Deprecated since version 2 of the interface")]
+        #[allow(clippy::too_many_arguments)]
+        pub fn legacy_evt(&self, conn: &mut DisplayHandle) {
+            let _ = conn.send_event(self, Event::LegacyEvt {});
+        }
     }
 }
 pub mod secondary {
     use super::wayland_server::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
         Dispatch, DispatchError, DisplayHandle, New, Resource, ResourceData,
@@ -460,15 +851,81 @@ pub mod secondary {
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 2u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 2u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once received this object cannot be used any longer.\nOnly available since version 2 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Secondary {
         id: ObjectId,
@@ -481,6 +938,11 @@ pub mod secondary {
         }
     }
     impl std::cmp::Eq for Secondary {}
+    impl std::hash::Hash for Secondary {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_server::Resource for Secondary {
         type Request = Request;
         type Event = Event;
@@ -547,7 +1009,7 @@ pub mod secondary {
 pub mod tertiary {
     use super::wayland_server::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
         Dispatch, DispatchError, DisplayHandle, New, Resource, ResourceData,
@@ -555,15 +1017,81 @@ pub mod tertiary {
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 3u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 3u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once received this object cannot be used any longer.\nOnly available since version 3 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Tertiary {
         id: ObjectId,
@@ -576,6 +1104,11 @@ pub mod tertiary {
         }
     }
     impl std::cmp::Eq for Tertiary {}
+    impl std::hash::Hash for Tertiary {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_server::Resource for Tertiary {
         type Request = Request;
         type Event = Event;
@@ -642,7 +1175,7 @@ pub mod tertiary {
 pub mod quad {
     use super::wayland_server::{
         backend::{
-            protocol::{same_interface, Argument, Interface, Message, WEnum},
+            protocol::{same_interface, Argument, Interface, Message, MessageInfo, WEnum},
             smallvec, InvalidId, ObjectData, ObjectId,
         },
         Dispatch, DispatchError, DisplayHandle, New, Resource, ResourceData,
@@ -650,15 +1183,81 @@ pub mod quad {
     use std::sync::Arc;
     #[doc = r" The minimal object version supporting this request"]
     pub const REQ_DESTROY_SINCE: u32 = 3u32;
+    #[doc = r" The metadata of the requests of this interface, indexed by opcode"]
+    pub const REQUESTS: &[MessageInfo] = &[MessageInfo {
+        name: "destroy",
+        opcode: 0u16,
+        since: 3u32,
+        deprecated_since: None,
+        is_destructor: true,
+    }];
+    #[doc = r" The metadata of the events of this interface, indexed by opcode"]
+    pub const EVENTS: &[MessageInfo] = &[];
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Request {
         #[doc = "This is a destructor, once received this object cannot be used any longer.\nOnly available since version 3 of the interface"]
         Destroy,
     }
+    impl Request {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {
+                Request::Destroy { .. } => 0u16,
+            }
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &REQUESTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug)]
     #[non_exhaustive]
     pub enum Event {}
+    impl Event {
+        #[doc = r" The opcode of this message"]
+        #[inline]
+        pub fn opcode(&self) -> u16 {
+            match *self {}
+        }
+        #[doc = r" The metadata of this message"]
+        #[inline]
+        pub fn info(&self) -> &'static MessageInfo {
+            &EVENTS[self.opcode() as usize]
+        }
+        #[doc = r" The name of this message"]
+        #[inline]
+        pub fn name(&self) -> &'static str {
+            self.info().name
+        }
+        #[doc = r" The minimal object version supporting this message"]
+        #[inline]
+        pub fn since(&self) -> u32 {
+            self.info().since
+        }
+        #[doc = r" Whether this message is a destructor"]
+        #[inline]
+        pub fn is_destructor(&self) -> bool {
+            self.info().is_destructor
+        }
+    }
     #[derive(Debug, Clone)]
     pub struct Quad {
         id: ObjectId,
@@ -671,6 +1270,11 @@ pub mod quad {
         }
     }
     impl std::cmp::Eq for Quad {}
+    impl std::hash::Hash for Quad {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.id.hash(state)
+        }
+    }
     impl super::wayland_server::Resource for Quad {
         type Request = Request;
         type Event = Event;
@@ -0,0 +1,436 @@
+pub mod handler {
+    #[allow(unused_imports)]
+    use super::wayland_server::{
+        backend::{protocol::WEnum, ClientId, ObjectId},
+        Client, DataInit, DelegateDispatch, DelegateGlobalDispatch, DelegateGlobalDispatchBase,
+        Dispatch, DisplayHandle, GlobalDispatch, New,
+    };
+    #[doc = "Handler of all the requests of the `test-protocol` protocol\n\nThe user data of the resources of each interface and the data of each global are set by the associated types of this trait. Its implementation is wired to the [`Dispatch`] and [`GlobalDispatch`] implementations of your state by the `delegate_test_protocol!` macro."]
+    pub trait TestProtocolHandler: Sized {
+        #[doc = "User data of the `wl_callback` resources"]
+        type WlCallbackData: Send + Sync + 'static;
+        #[doc = "User data of the `test_global` resources"]
+        type TestGlobalData: Send + Sync + 'static;
+        #[doc = "User data of the `secondary` resources"]
+        type SecondaryData: Send + Sync + 'static;
+        #[doc = "User data of the `tertiary` resources"]
+        type TertiaryData: Send + Sync + 'static;
+        #[doc = "User data of the `quad` resources"]
+        type QuadData: Send + Sync + 'static;
+        #[doc = "Data of the `test_global` globals"]
+        type TestGlobalGlobalData: Send + Sync + 'static;
+        #[doc = "A client bound the `test_global` global"]
+        fn test_global_bind(
+            &mut self,
+            dhandle: &mut DisplayHandle<'_>,
+            client: &Client,
+            resource: New<super::test_global::TestGlobal>,
+            global_data: &Self::TestGlobalGlobalData,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.many_args` request\n\na request with every possible non-object arg"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_many_args(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            unsigned_int: u32,
+            signed_int: i32,
+            fixed_point: f64,
+            number_array: Vec<u8>,
+            some_text: String,
+            file_descriptor: ::std::os::unix::io::RawFd,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.get_secondary` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_get_secondary(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            sec: New<super::secondary::Secondary>,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.get_tertiary` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_get_tertiary(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            ter: New<super::tertiary::Tertiary>,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.link` request\n\nlink a secondary and a tertiary"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_link(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            sec: super::secondary::Secondary,
+            ter: Option<super::tertiary::Tertiary>,
+            time: u32,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.destroy` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_destroy(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.reset` request\n\nreset the global"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_reset(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `test_global.set_mode` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn test_global_set_mode(
+            &mut self,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            mode: WEnum<super::test_global::Mode>,
+            data: &Self::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `secondary.destroy` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn secondary_destroy(
+            &mut self,
+            client: &Client,
+            resource: &super::secondary::Secondary,
+            data: &Self::SecondaryData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `tertiary.destroy` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn tertiary_destroy(
+            &mut self,
+            client: &Client,
+            resource: &super::tertiary::Tertiary,
+            data: &Self::TertiaryData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "The `quad.destroy` request"]
+        #[allow(clippy::too_many_arguments)]
+        fn quad_destroy(
+            &mut self,
+            client: &Client,
+            resource: &super::quad::Quad,
+            data: &Self::QuadData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, Self>,
+        );
+        #[doc = "A `wl_callback` resource has been destroyed\n\nBy default this method does nothing."]
+        fn wl_callback_destroyed(
+            &mut self,
+            _client: ClientId,
+            _resource: ObjectId,
+            _data: &Self::WlCallbackData,
+        ) {
+        }
+        #[doc = "A `test_global` resource has been destroyed\n\nBy default this method does nothing."]
+        fn test_global_destroyed(
+            &mut self,
+            _client: ClientId,
+            _resource: ObjectId,
+            _data: &Self::TestGlobalData,
+        ) {
+        }
+        #[doc = "A `secondary` resource has been destroyed\n\nBy default this method does nothing."]
+        fn secondary_destroyed(
+            &mut self,
+            _client: ClientId,
+            _resource: ObjectId,
+            _data: &Self::SecondaryData,
+        ) {
+        }
+        #[doc = "A `tertiary` resource has been destroyed\n\nBy default this method does nothing."]
+        fn tertiary_destroyed(
+            &mut self,
+            _client: ClientId,
+            _resource: ObjectId,
+            _data: &Self::TertiaryData,
+        ) {
+        }
+        #[doc = "A `quad` resource has been destroyed\n\nBy default this method does nothing."]
+        fn quad_destroyed(
+            &mut self,
+            _client: ClientId,
+            _resource: ObjectId,
+            _data: &Self::QuadData,
+        ) {
+        }
+    }
+    #[doc = "Delegate type implementing the requests of the `test-protocol` protocol from [`TestProtocolHandler`]"]
+    #[derive(Debug)]
+    pub struct TestProtocolAdapter<D>(::std::marker::PhantomData<D>);
+    impl<D>
+        DelegateDispatch<
+            super::wl_callback::WlCallback,
+            <D as TestProtocolHandler>::WlCallbackData,
+            D,
+        > for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::wl_callback::WlCallback, <D as TestProtocolHandler>::WlCallbackData>
+            + TestProtocolHandler,
+    {
+        #[allow(unused_variables)]
+        fn request(
+            state: &mut D,
+            client: &Client,
+            resource: &super::wl_callback::WlCallback,
+            request: super::wl_callback::Request,
+            data: &<D as TestProtocolHandler>::WlCallbackData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            match request {}
+        }
+        fn destroyed(
+            state: &mut D,
+            client: ClientId,
+            resource: ObjectId,
+            data: &<D as TestProtocolHandler>::WlCallbackData,
+        ) {
+            <D as TestProtocolHandler>::wl_callback_destroyed(state, client, resource, data)
+        }
+    }
+    impl<D>
+        DelegateDispatch<
+            super::test_global::TestGlobal,
+            <D as TestProtocolHandler>::TestGlobalData,
+            D,
+        > for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::test_global::TestGlobal, <D as TestProtocolHandler>::TestGlobalData>
+            + TestProtocolHandler,
+    {
+        fn request(
+            state: &mut D,
+            client: &Client,
+            resource: &super::test_global::TestGlobal,
+            request: super::test_global::Request,
+            data: &<D as TestProtocolHandler>::TestGlobalData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            match request {
+                super::test_global::Request::ManyArgs {
+                    unsigned_int: arg_unsigned_int,
+                    signed_int: arg_signed_int,
+                    fixed_point: arg_fixed_point,
+                    number_array: arg_number_array,
+                    some_text: arg_some_text,
+                    file_descriptor: arg_file_descriptor,
+                } => <D as TestProtocolHandler>::test_global_many_args(
+                    state,
+                    client,
+                    resource,
+                    arg_unsigned_int,
+                    arg_signed_int,
+                    arg_fixed_point,
+                    arg_number_array,
+                    arg_some_text,
+                    arg_file_descriptor,
+                    data,
+                    dhandle,
+                    data_init,
+                ),
+                super::test_global::Request::GetSecondary { sec: arg_sec } => {
+                    <D as TestProtocolHandler>::test_global_get_secondary(
+                        state, client, resource, arg_sec, data, dhandle, data_init,
+                    )
+                }
+                super::test_global::Request::GetTertiary { ter: arg_ter } => {
+                    <D as TestProtocolHandler>::test_global_get_tertiary(
+                        state, client, resource, arg_ter, data, dhandle, data_init,
+                    )
+                }
+                super::test_global::Request::Link {
+                    sec: arg_sec,
+                    ter: arg_ter,
+                    time: arg_time,
+                } => <D as TestProtocolHandler>::test_global_link(
+                    state, client, resource, arg_sec, arg_ter, arg_time, data, dhandle, data_init,
+                ),
+                super::test_global::Request::Destroy {} => {
+                    <D as TestProtocolHandler>::test_global_destroy(
+                        state, client, resource, data, dhandle, data_init,
+                    )
+                }
+                super::test_global::Request::Reset {} => {
+                    <D as TestProtocolHandler>::test_global_reset(
+                        state, client, resource, data, dhandle, data_init,
+                    )
+                }
+                super::test_global::Request::SetMode { mode: arg_mode } => {
+                    <D as TestProtocolHandler>::test_global_set_mode(
+                        state, client, resource, arg_mode, data, dhandle, data_init,
+                    )
+                }
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            client: ClientId,
+            resource: ObjectId,
+            data: &<D as TestProtocolHandler>::TestGlobalData,
+        ) {
+            <D as TestProtocolHandler>::test_global_destroyed(state, client, resource, data)
+        }
+    }
+    impl<D>
+        DelegateDispatch<super::secondary::Secondary, <D as TestProtocolHandler>::SecondaryData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::secondary::Secondary, <D as TestProtocolHandler>::SecondaryData>
+            + TestProtocolHandler,
+    {
+        fn request(
+            state: &mut D,
+            client: &Client,
+            resource: &super::secondary::Secondary,
+            request: super::secondary::Request,
+            data: &<D as TestProtocolHandler>::SecondaryData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            match request {
+                super::secondary::Request::Destroy {} => {
+                    <D as TestProtocolHandler>::secondary_destroy(
+                        state, client, resource, data, dhandle, data_init,
+                    )
+                }
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            client: ClientId,
+            resource: ObjectId,
+            data: &<D as TestProtocolHandler>::SecondaryData,
+        ) {
+            <D as TestProtocolHandler>::secondary_destroyed(state, client, resource, data)
+        }
+    }
+    impl<D> DelegateDispatch<super::tertiary::Tertiary, <D as TestProtocolHandler>::TertiaryData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::tertiary::Tertiary, <D as TestProtocolHandler>::TertiaryData>
+            + TestProtocolHandler,
+    {
+        fn request(
+            state: &mut D,
+            client: &Client,
+            resource: &super::tertiary::Tertiary,
+            request: super::tertiary::Request,
+            data: &<D as TestProtocolHandler>::TertiaryData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            match request {
+                super::tertiary::Request::Destroy {} => {
+                    <D as TestProtocolHandler>::tertiary_destroy(
+                        state, client, resource, data, dhandle, data_init,
+                    )
+                }
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            client: ClientId,
+            resource: ObjectId,
+            data: &<D as TestProtocolHandler>::TertiaryData,
+        ) {
+            <D as TestProtocolHandler>::tertiary_destroyed(state, client, resource, data)
+        }
+    }
+    impl<D> DelegateDispatch<super::quad::Quad, <D as TestProtocolHandler>::QuadData, D>
+        for TestProtocolAdapter<D>
+    where
+        D: Dispatch<super::quad::Quad, <D as TestProtocolHandler>::QuadData> + TestProtocolHandler,
+    {
+        fn request(
+            state: &mut D,
+            client: &Client,
+            resource: &super::quad::Quad,
+            request: super::quad::Request,
+            data: &<D as TestProtocolHandler>::QuadData,
+            dhandle: &mut DisplayHandle<'_>,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            match request {
+                super::quad::Request::Destroy {} => <D as TestProtocolHandler>::quad_destroy(
+                    state, client, resource, data, dhandle, data_init,
+                ),
+            }
+        }
+        fn destroyed(
+            state: &mut D,
+            client: ClientId,
+            resource: ObjectId,
+            data: &<D as TestProtocolHandler>::QuadData,
+        ) {
+            <D as TestProtocolHandler>::quad_destroyed(state, client, resource, data)
+        }
+    }
+    impl<D: TestProtocolHandler> DelegateGlobalDispatchBase<super::test_global::TestGlobal>
+        for TestProtocolAdapter<D>
+    {
+        type GlobalData = <D as TestProtocolHandler>::TestGlobalGlobalData;
+    }
+    impl<D> DelegateGlobalDispatch<super::test_global::TestGlobal, D> for TestProtocolAdapter<D>
+    where
+        D: GlobalDispatch<
+                super::test_global::TestGlobal,
+                GlobalData = <D as TestProtocolHandler>::TestGlobalGlobalData,
+            > + TestProtocolHandler,
+    {
+        fn bind(
+            state: &mut D,
+            dhandle: &mut DisplayHandle<'_>,
+            client: &Client,
+            resource: New<super::test_global::TestGlobal>,
+            global_data: &<D as TestProtocolHandler>::TestGlobalGlobalData,
+            data_init: &mut DataInit<'_, D>,
+        ) {
+            <D as TestProtocolHandler>::test_global_bind(
+                state,
+                dhandle,
+                client,
+                resource,
+                global_data,
+                data_init,
+            )
+        }
+    }
+    #[doc(hidden)]
+    #[allow(unused_imports)]
+    pub(crate) use super::wayland_server::{
+        delegate_dispatch as __delegate_dispatch,
+        delegate_global_dispatch as __delegate_global_dispatch,
+    };
+    #[doc = "Implements [`Dispatch`] and [`GlobalDispatch`] for all the interfaces of the `test-protocol` protocol by delegating them to [`TestProtocolAdapter`]\n\nThe user data of each interface is the matching associated type of the [`TestProtocolHandler`] implementation of your state. The second argument is the path of the module in which the protocol was generated:\n\n```ignore\nprotocol::handler::delegate_test_protocol!(State => protocol);\n```"]
+    macro_rules ! delegate_test_protocol { ($ dispatch_from : ty => $ ($ module : ident) :: +) => { $ ($ module) :: + :: handler :: __delegate_dispatch ! ($ dispatch_from : [$ ($ module) :: + :: wl_callback :: WlCallback : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: WlCallbackData , $ ($ module) :: + :: test_global :: TestGlobal : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: TestGlobalData , $ ($ module) :: + :: secondary :: Secondary : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: SecondaryData , $ ($ module) :: + :: tertiary :: Tertiary : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: TertiaryData , $ ($ module) :: + :: quad :: Quad : < $ dispatch_from as $ ($ module) :: + :: handler :: TestProtocolHandler > :: QuadData] => $ ($ module) :: + :: handler :: TestProtocolAdapter < $ dispatch_from >) ; $ ($ module) :: + :: handler :: __delegate_global_dispatch ! ($ dispatch_from : [$ ($ module) :: + :: test_global :: TestGlobal] => $ ($ module) :: + :: handler :: TestProtocolAdapter < $ dispatch_from >) ; } ; }
+    #[allow(unused_imports)]
+    pub(crate) use delegate_test_protocol;
+}
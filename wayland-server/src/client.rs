@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{os::unix::io::RawFd, sync::Arc};
 
 use wayland_backend::{
     protocol::ProtocolError,
@@ -37,11 +37,23 @@ impl Client {
         handle.inner.handle().get_client_credentials(self.id.clone())
     }
 
-    pub fn create_resource<I: Resource + 'static, D: Dispatch<I> + 'static>(
+    /// Returns the file descriptor of the socket of this client
+    ///
+    /// It should only be used to monitor the socket for readability, for example when the display was
+    /// created with [`Display::new_without_poll()`](crate::Display::new_without_poll).
+    pub fn get_fd(&self, handle: &mut DisplayHandle<'_>) -> Result<RawFd, InvalidId> {
+        handle.inner.handle().get_client_fd(self.id.clone())
+    }
+
+    pub fn create_resource<
+        I: Resource + 'static,
+        U: Send + Sync + 'static,
+        D: Dispatch<I, U> + 'static,
+    >(
         &self,
         handle: &mut DisplayHandle<'_>,
         version: u32,
-        user_data: <D as Dispatch<I>>::UserData,
+        user_data: U,
     ) -> Result<I, InvalidId> {
         let id = handle
             .inner
@@ -69,9 +81,17 @@ impl Client {
         I::from_id(handle, object_id)
     }
 
+    /// Immediately disconnects this client, without waiting for its pending messages to be sent
     pub fn kill(&self, handle: &mut DisplayHandle<'_>, error: ProtocolError) {
         handle.inner.handle().kill_client(self.id.clone(), DisconnectReason::ProtocolError(error))
     }
+
+    /// Disconnects this client once its pending messages and the error of `reason` (if any) are sent
+    ///
+    /// See [`Handle::kill_client_after_flush()`](wayland_backend::server::Handle::kill_client_after_flush).
+    pub fn disconnect(&self, handle: &mut DisplayHandle<'_>, reason: DisconnectReason) {
+        handle.inner.handle().kill_client_after_flush(self.id.clone(), reason)
+    }
 }
 
 impl PartialEq for Client {
@@ -1,15 +1,16 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use wayland_backend::server::{ClientId, ObjectData, ObjectId};
 
-use crate::{Client, DisplayHandle, Resource};
+use crate::{Client, DispatchError, DisplayHandle, Resource};
 
 /// A trait which provides an implementation for handling a client's requests from a resource with some type
 /// of associated user data.
-pub trait Dispatch<I: Resource>: Sized {
-    /// The user data associated with the type of resource.
-    type UserData: Send + Sync + 'static;
-
+///
+/// The handler is selected by both the interface `I` of the resource and the type `U` of the user data it
+/// was initialized with, so that a single state can handle resources of the same interface with different
+/// kinds of user data.
+pub trait Dispatch<I: Resource, U>: Sized {
     /// Called when a request from a client is processed.
     ///
     /// The implementation of this function will vary depending on what protocol is being implemented. Typically
@@ -20,7 +21,7 @@ pub trait Dispatch<I: Resource>: Sized {
         client: &Client,
         resource: &I,
         request: I::Request,
-        data: &Self::UserData,
+        data: &U,
         dhandle: &mut DisplayHandle<'_>,
         data_init: &mut DataInit<'_, Self>,
     );
@@ -36,7 +37,7 @@ pub trait Dispatch<I: Resource>: Sized {
     /// convenience.
     ///
     /// By default this method does nothing.
-    fn destroyed(&mut self, _client: ClientId, _resource: ObjectId, _data: &Self::UserData) {}
+    fn destroyed(&mut self, _client: ClientId, _resource: ObjectId, _data: &U) {}
 }
 
 #[derive(Debug)]
@@ -64,13 +65,17 @@ pub struct DataInit<'a, D> {
 }
 
 impl<'a, D> DataInit<'a, D> {
-    pub fn init<I: Resource + 'static>(
+    /// Initialize the user data of a newly created resource
+    ///
+    /// The [`Dispatch`] implementation handling the requests of this resource is selected by the interface `I`
+    /// and the type `U` of the provided user data.
+    pub fn init<I: Resource + 'static, U: Send + Sync + 'static>(
         &mut self,
         resource: New<I>,
-        data: <D as Dispatch<I>>::UserData,
+        data: U,
     ) -> I
     where
-        D: Dispatch<I> + 'static,
+        D: Dispatch<I, U> + 'static,
     {
         let arc = Arc::new(ResourceData::<I, _>::new(data));
         *self.store = Some(arc.clone());
@@ -78,26 +83,149 @@ impl<'a, D> DataInit<'a, D> {
         obj.__set_object_data(arc);
         obj
     }
+
+    /// Initialize a newly created resource with a closure handling its requests
+    ///
+    /// This is the counterpart of [`init()`](DataInit::init) for the resources handled by a [`ClosureData`]
+    /// rather than by a [`Dispatch`] implementation of your state.
+    pub fn init_closure<I: Resource + 'static>(
+        &mut self,
+        resource: New<I>,
+        data: ClosureData<I, D>,
+    ) -> I
+    where
+        D: 'static,
+    {
+        let arc = Arc::new(data);
+        *self.store = Some(arc.clone());
+        let mut obj = resource.id;
+        obj.__set_object_data(arc);
+        obj
+    }
 }
 
 /*
- * Dispatch delegation helpers.
+ * Closure-based handlers
  */
 
-/// The base trait used to define a delegate type to hand some type of resource.
-pub trait DelegateDispatchBase<I: Resource> {
-    /// The type of user data the delegate holds.
-    type UserData: Send + Sync + 'static;
+type ClosureHandler<I, D> = dyn FnMut(
+        &mut D,
+        &Client,
+        &I,
+        <I as Resource>::Request,
+        &mut DisplayHandle<'_>,
+        &mut DataInit<'_, D>,
+    ) + Send;
+
+/// A handler of the requests of a resource implemented by a closure
+///
+/// A resource initialized with [`DataInit::init_closure()`] has its requests processed by the provided
+/// closure rather than by a dedicated [`Dispatch`] implementation of your state. Such resources can be
+/// freely mixed with trait-based ones on the same [`Display`](crate::Display).
+///
+/// These resources do not go through the [`Dispatch`] trait, which your state can thus implement
+/// generically over the user data type. They have no user data accessible with
+/// [`Resource::data()`].
+pub struct ClosureData<I: Resource, D> {
+    handler: Mutex<Box<ClosureHandler<I, D>>>,
+}
+
+impl<I: Resource, D> ClosureData<I, D> {
+    /// Create a new closure-based handler
+    pub fn new<F>(handler: F) -> ClosureData<I, D>
+    where
+        F: FnMut(&mut D, &Client, &I, I::Request, &mut DisplayHandle<'_>, &mut DataInit<'_, D>)
+            + Send
+            + 'static,
+    {
+        ClosureData { handler: Mutex::new(Box::new(handler)) }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+impl<I: Resource, D> std::fmt::Debug for ClosureData<I, D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClosureData").finish_non_exhaustive()
+    }
+}
+
+impl<I: Resource + 'static, D: 'static> ObjectData<D> for ClosureData<I, D> {
+    fn request(
+        self: Arc<Self>,
+        handle: &mut wayland_backend::server::Handle<D>,
+        data: &mut D,
+        client_id: wayland_backend::server::ClientId,
+        msg: wayland_backend::protocol::Message<wayland_backend::server::ObjectId>,
+    ) -> Option<Arc<dyn ObjectData<D>>> {
+        on_request::<I, D, _>(
+            handle,
+            client_id,
+            msg,
+            |client, resource, request, dhandle, data_init| {
+                (self.handler.lock().unwrap())(data, client, resource, request, dhandle, data_init)
+            },
+        )
+    }
+
+    fn destroyed(
+        &self,
+        _: &mut D,
+        _: wayland_backend::server::ClientId,
+        _: wayland_backend::server::ObjectId,
+    ) {
+    }
 }
 
+// parse a request and give it to `handler`, returning the object data of the resource it created
+fn on_request<I: Resource, D, F>(
+    handle: &mut wayland_backend::server::Handle<D>,
+    client_id: wayland_backend::server::ClientId,
+    msg: wayland_backend::protocol::Message<wayland_backend::server::ObjectId>,
+    handler: F,
+) -> Option<Arc<dyn ObjectData<D>>>
+where
+    F: FnOnce(&Client, &I, I::Request, &mut DisplayHandle<'_>, &mut DataInit<'_, D>),
+{
+    let mut dhandle = DisplayHandle::from_handle(handle);
+    let client = match Client::from_id(&mut dhandle, client_id) {
+        Ok(v) => v,
+        Err(_) => {
+            log::error!("Receiving a request from a dead client ?!");
+            return None;
+        }
+    };
+
+    let (resource, request) = match I::parse_request(&mut dhandle, msg) {
+        Ok(v) => v,
+        Err(e) => {
+            if let DispatchError::InvalidValue { ref msg, code, .. } = e {
+                // the interface defines a protocol error for this invalid value
+                let message = std::ffi::CString::new(e.to_string()).unwrap();
+                dhandle.inner.handle().post_error(msg.sender_id.clone(), code, message);
+            } else {
+                log::warn!("Dispatching error encountered: {:?}, killing client.", e);
+                // TODO: Kill client
+            }
+            return None;
+        }
+    };
+
+    let mut new_data = None;
+
+    handler(&client, &resource, request, &mut dhandle, &mut DataInit { store: &mut new_data });
+
+    new_data
+}
+
+/*
+ * Dispatch delegation helpers.
+ */
+
 /// A trait which defines a delegate to handle some type of resource.
 ///
-/// This trait is useful for building modular handlers of resources.
-pub trait DelegateDispatch<
-    I: Resource,
-    D: Dispatch<I, UserData = <Self as DelegateDispatchBase<I>>::UserData>,
->: Sized + DelegateDispatchBase<I>
-{
+/// This trait is useful for building modular handlers of resources. Like [`Dispatch`], it is keyed on both
+/// the interface `I` of the resource and the type `U` of its user data.
+pub trait DelegateDispatch<I: Resource, U, D: Dispatch<I, U>>: Sized {
     /// Called when a request from a client is processed.
     ///
     /// The implementation of this function will vary depending on what protocol is being implemented. Typically
@@ -108,7 +236,7 @@ pub trait DelegateDispatch<
         client: &Client,
         resource: &I,
         request: I::Request,
-        data: &Self::UserData,
+        data: &U,
         dhandle: &mut DisplayHandle<'_>,
         data_init: &mut DataInit<'_, D>,
     );
@@ -124,7 +252,7 @@ pub trait DelegateDispatch<
     /// convenience.
     ///
     /// By default this method does nothing.
-    fn destroyed(_state: &mut D, _client: ClientId, _resource: ObjectId, _data: &Self::UserData) {}
+    fn destroyed(_state: &mut D, _client: ClientId, _resource: ObjectId, _data: &U) {}
 }
 
 impl<I, U> ResourceData<I, U> {
@@ -133,8 +261,8 @@ impl<I, U> ResourceData<I, U> {
     }
 }
 
-impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, UserData = U> + 'static>
-    ObjectData<D> for ResourceData<I, U>
+impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, U> + 'static> ObjectData<D>
+    for ResourceData<I, U>
 {
     fn request(
         self: Arc<Self>,
@@ -143,37 +271,15 @@ impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, UserData =
         client_id: wayland_backend::server::ClientId,
         msg: wayland_backend::protocol::Message<wayland_backend::server::ObjectId>,
     ) -> Option<Arc<dyn ObjectData<D>>> {
-        let mut dhandle = DisplayHandle::from_handle(handle);
-        let client = match Client::from_id(&mut dhandle, client_id) {
-            Ok(v) => v,
-            Err(_) => {
-                log::error!("Receiving a request from a dead client ?!");
-                return None;
-            }
-        };
-
-        let (resource, request) = match I::parse_request(&mut dhandle, msg) {
-            Ok(v) => v,
-            Err(e) => {
-                log::warn!("Dispatching error encountered: {:?}, killing client.", e);
-                // TODO: Kill client
-                return None;
-            }
-        };
-        let udata = resource.data::<U>().expect("Wrong user_data value for object");
-
-        let mut new_data = None;
-
-        data.request(
-            &client,
-            &resource,
-            request,
-            udata,
-            &mut dhandle,
-            &mut DataInit { store: &mut new_data },
-        );
-
-        new_data
+        on_request::<I, D, _>(
+            handle,
+            client_id,
+            msg,
+            |client, resource, request, dhandle, data_init| {
+                let udata = resource.data::<U>().expect("Wrong user_data value for object");
+                data.request(client, resource, request, udata, dhandle, data_init)
+            },
+        )
     }
 
     fn destroyed(
@@ -202,24 +308,20 @@ impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, UserData =
 /// ```
 /// use wayland_server::{delegate_dispatch, protocol::wl_output};
 /// #
-/// # use wayland_server::{DelegateDispatch, DelegateDispatchBase, Dispatch};
+/// # use wayland_server::{DelegateDispatch, Dispatch};
 /// #
 /// # struct DelegateToMe;
 /// #
-/// # impl DelegateDispatchBase<wl_output::WlOutput> for DelegateToMe {
-/// #     type UserData = ();
-/// # }
-/// #
-/// # impl<D> DelegateDispatch<wl_output::WlOutput, D> for DelegateToMe
+/// # impl<D> DelegateDispatch<wl_output::WlOutput, (), D> for DelegateToMe
 /// # where
-/// #     D: Dispatch<wl_output::WlOutput, UserData = Self::UserData> + AsMut<DelegateToMe>,
+/// #     D: Dispatch<wl_output::WlOutput, ()> + AsMut<DelegateToMe>,
 /// # {
 /// #     fn request(
 /// #         _state: &mut D,
 /// #         _client: &wayland_server::Client,
 /// #         _resource: &wl_output::WlOutput,
 /// #         _request: wl_output::Request,
-/// #         _data: &Self::UserData,
+/// #         _data: &(),
 /// #         _dhandle: &mut wayland_server::DisplayHandle,
 /// #         _data_init: &mut wayland_server::DataInit<'_, D>,
 /// #     ) {
@@ -234,8 +336,8 @@ impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, UserData =
 ///     delegate: DelegateToMe,
 /// }
 ///
-/// // Use delegate_dispatch to implement Dispatch<wl_output::WlOutput> for ExampleApp.
-/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput] => DelegateToMe);
+/// // Use delegate_dispatch to implement Dispatch<wl_output::WlOutput, ()> for ExampleApp.
+/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput: ()] => DelegateToMe);
 ///
 /// // But DelegateToMe requires that ExampleApp implements AsMut<DelegateToMe>, so we provide this impl
 /// impl AsMut<DelegateToMe> for ExampleApp {
@@ -253,29 +355,27 @@ impl<I: Resource + 'static, U: Send + Sync + 'static, D: Dispatch<I, UserData =
 ///
 /// ```ignore
 /// # // This is not tested because xdg_output is in wayland-protocols.
-/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput, xdg_output::XdgOutput] => OutputDelegate);
+/// delegate_dispatch!(ExampleApp: [wl_output::WlOutput: OutputData, xdg_output::XdgOutput: OutputData] => OutputDelegate);
 /// ```
 #[macro_export]
 macro_rules! delegate_dispatch {
-    ($dispatch_from: ty: [$($interface: ty),*] => $dispatch_to: ty) => {
+    ($dispatch_from: ty: [$($interface: ty: $udata: ty),* $(,)?] => $dispatch_to: ty) => {
         $(
-            impl $crate::Dispatch<$interface> for $dispatch_from {
-                type UserData = <$dispatch_to as $crate::DelegateDispatchBase<$interface>>::UserData;
-
+            impl $crate::Dispatch<$interface, $udata> for $dispatch_from {
                 fn request(
                     &mut self,
                     client: &$crate::Client,
                     resource: &$interface,
                     request: <$interface as $crate::Resource>::Request,
-                    data: &Self::UserData,
+                    data: &$udata,
                     dhandle: &mut $crate::DisplayHandle<'_>,
                     data_init: &mut $crate::DataInit<'_, Self>,
                 ) {
-                    <$dispatch_to as $crate::DelegateDispatch<$interface, Self>>::request(self, client, resource, request, data, dhandle, data_init)
+                    <$dispatch_to as $crate::DelegateDispatch<$interface, $udata, Self>>::request(self, client, resource, request, data, dhandle, data_init)
                 }
 
-                fn destroyed(&mut self, client: $crate::backend::ClientId, resource: $crate::backend::ObjectId, data: &Self::UserData) {
-                    <$dispatch_to as $crate::DelegateDispatch<$interface, Self>>::destroyed(self, client, resource, data)
+                fn destroyed(&mut self, client: $crate::backend::ClientId, resource: $crate::backend::ObjectId, data: &$udata) {
+                    <$dispatch_to as $crate::DelegateDispatch<$interface, $udata, Self>>::destroyed(self, client, resource, data)
                 }
             }
         )*
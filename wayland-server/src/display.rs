@@ -1,6 +1,8 @@
 use std::{
-    os::unix::net::UnixStream,
-    sync::{Arc, Mutex, MutexGuard},
+    ffi::CString,
+    os::unix::{io::RawFd, net::UnixStream},
+    sync::{Arc, Mutex, MutexGuard, Weak},
+    time::Duration,
 };
 
 use wayland_backend::{
@@ -19,11 +21,30 @@ use crate::{
 #[derive(Debug, Clone)]
 pub struct Display<D> {
     backend: Arc<Mutex<Backend<D>>>,
+    flush_notifier: Arc<FlushNotifier>,
 }
 
 impl<D: 'static> Display<D> {
     pub fn new() -> Result<Display<D>, InitError> {
-        Ok(Display { backend: Arc::new(Mutex::new(Backend::new()?)) })
+        Self::from_backend(Backend::new()?)
+    }
+
+    /// Create a display whose clients are not monitored by an internal poll
+    ///
+    /// The sockets of the clients, given by [`Client::get_fd()`], should then be monitored by your event
+    /// loop, and each client dispatched with [`Display::dispatch_client()`] when its socket is readable.
+    ///
+    /// With the system backend, libwayland still monitors the clients with its own event loop, so this is
+    /// identical to [`Display::new()`]. Monitoring the sockets of the clients works all the same.
+    pub fn new_without_poll() -> Result<Display<D>, InitError> {
+        Self::from_backend(Backend::new_without_poll()?)
+    }
+
+    fn from_backend(backend: Backend<D>) -> Result<Display<D>, InitError> {
+        Ok(Display {
+            backend: Arc::new(Mutex::new(backend)),
+            flush_notifier: Arc::new(FlushNotifier::new().map_err(InitError::Io)?),
+        })
     }
 
     pub fn handle(&self) -> DisplayHandle<'_> {
@@ -47,10 +68,58 @@ impl<D: 'static> Display<D> {
         self.backend.lock().unwrap().dispatch_all_clients(data)
     }
 
+    /// Dispatch the pending requests of a single client
+    ///
+    /// With the system backend, the pending requests of the other clients are dispatched as well.
+    pub fn dispatch_client(&self, data: &mut D, client: &Client) -> std::io::Result<usize> {
+        self.backend.lock().unwrap().dispatch_client(data, client.id())
+    }
+
+    /// Flush the pending events of a single client
+    pub fn flush_client(&self, client: &Client) -> std::io::Result<()> {
+        self.backend.lock().unwrap().flush(Some(client.id()))
+    }
+
     pub fn flush_clients(&self) -> std::io::Result<()> {
+        // reset the notification first, so that events sent concurrently are not missed
+        self.flush_notifier.clear();
         self.backend.lock().unwrap().flush(None)
     }
 
+    /// Returns a handle to send events from other threads
+    pub fn event_sender(&self) -> EventSender<D> {
+        EventSender {
+            backend: Arc::downgrade(&self.backend),
+            flush_notifier: self.flush_notifier.clone(),
+        }
+    }
+
+    /// Returns a file descriptor signaling that events have been sent through an [`EventSender`]
+    ///
+    /// It becomes readable when events have been sent through an `EventSender` since the last call to
+    /// [`Display::flush_clients()`], and should be monitored by your event loop to flush them.
+    pub fn flush_fd(&self) -> RawFd {
+        self.flush_notifier.read_fd
+    }
+
+    /// Gracefully disconnects all clients
+    ///
+    /// A `wl_display.error` with the `implementation` code and the given `message` is posted to all clients.
+    /// This method then blocks until it has been sent to all of them, or until `timeout` has elapsed.
+    ///
+    /// Clients that did not read their pending messages before the timeout are disconnected anyway, and
+    /// this method then returns an error of kind `TimedOut`. In all cases, once this method returns all clients
+    /// are disconnected with [`DisconnectReason::ServerKill`](crate::backend::DisconnectReason::ServerKill)
+    /// and their objects are destroyed.
+    pub fn shutdown(
+        &self,
+        data: &mut D,
+        message: CString,
+        timeout: Duration,
+    ) -> std::io::Result<()> {
+        self.backend.lock().unwrap().shutdown(data, message, timeout)
+    }
+
     pub fn create_global<I: Resource + 'static>(
         &self,
         version: u32,
@@ -79,6 +148,54 @@ impl<D: 'static> Display<D> {
     }
 }
 
+/// A handle to send events to clients from any thread
+///
+/// Events sent through an `EventSender` are directly queued in the outgoing buffers of the clients, and are
+/// thus ordered with all other events by the acquisition of the display lock. The file descriptor
+/// returned by [`Display::flush_fd()`] then becomes readable, to signal that
+/// [`Display::flush_clients()`] should be invoked.
+///
+/// This handle must not be used from within the dispatch callbacks of the display, as the display lock is
+/// already held at that point.
+#[derive(Debug)]
+pub struct EventSender<D> {
+    backend: Weak<Mutex<Backend<D>>>,
+    flush_notifier: Arc<FlushNotifier>,
+}
+
+impl<D> Clone for EventSender<D> {
+    fn clone(&self) -> Self {
+        EventSender { backend: self.backend.clone(), flush_notifier: self.flush_notifier.clone() }
+    }
+}
+
+impl<D: 'static> EventSender<D> {
+    /// Send an event to a resource
+    ///
+    /// Returns an error if the resource or the display are no longer alive.
+    pub fn send_event<I: Resource>(&self, resource: &I, event: I::Event) -> Result<(), InvalidId> {
+        self.with_handle(|handle| handle.send_event(resource, event))?
+    }
+
+    /// Access the display from the current thread
+    ///
+    /// The display is locked while the closure runs, and a flush is requested afterwards. Returns an
+    /// error if the display is no longer alive.
+    pub fn with_handle<T>(
+        &self,
+        f: impl FnOnce(&mut DisplayHandle<'_>) -> T,
+    ) -> Result<T, InvalidId> {
+        let backend = self.backend.upgrade().ok_or(InvalidId)?;
+        let ret = f(&mut DisplayHandle {
+            inner: HandleInner::Guard(
+                (&*backend as &Mutex<dyn ErasedDisplayHandle>).lock().unwrap(),
+            ),
+        });
+        self.flush_notifier.notify();
+        Ok(ret)
+    }
+}
+
 pub struct DisplayHandle<'a> {
     pub(crate) inner: HandleInner<'a>,
 }
@@ -173,7 +290,9 @@ pub(crate) trait ErasedDisplayHandle: downcast_rs::Downcast {
         &mut self,
         id: ClientId,
     ) -> Result<Arc<dyn std::any::Any + Send + Sync>, InvalidId>;
+    fn get_client_fd(&mut self, id: ClientId) -> Result<RawFd, InvalidId>;
     fn kill_client(&mut self, id: ClientId, reason: DisconnectReason);
+    fn kill_client_after_flush(&mut self, id: ClientId, reason: DisconnectReason);
 }
 
 downcast_rs::impl_downcast!(ErasedDisplayHandle);
@@ -227,9 +346,17 @@ impl<D: 'static> ErasedDisplayHandle for Handle<D> {
         Handle::<D>::get_client_data(self, id).map(|udata| udata.into_any_arc())
     }
 
+    fn get_client_fd(&mut self, id: ClientId) -> Result<RawFd, InvalidId> {
+        Handle::<D>::get_client_fd(self, id)
+    }
+
     fn kill_client(&mut self, id: ClientId, reason: DisconnectReason) {
         Handle::<D>::kill_client(self, id, reason)
     }
+
+    fn kill_client_after_flush(&mut self, id: ClientId, reason: DisconnectReason) {
+        Handle::<D>::kill_client_after_flush(self, id, reason)
+    }
 }
 
 impl<D: 'static> ErasedDisplayHandle for Backend<D> {
@@ -281,7 +408,61 @@ impl<D: 'static> ErasedDisplayHandle for Backend<D> {
         Handle::<D>::get_client_data(self.handle(), id).map(|udata| udata.into_any_arc())
     }
 
+    fn get_client_fd(&mut self, id: ClientId) -> Result<RawFd, InvalidId> {
+        Handle::<D>::get_client_fd(self.handle(), id)
+    }
+
     fn kill_client(&mut self, id: ClientId, reason: DisconnectReason) {
         Handle::<D>::kill_client(self.handle(), id, reason)
     }
+
+    fn kill_client_after_flush(&mut self, id: ClientId, reason: DisconnectReason) {
+        Handle::<D>::kill_client_after_flush(self.handle(), id, reason)
+    }
+}
+
+/// A pollable file descriptor signaling that a flush is needed
+#[derive(Debug)]
+struct FlushNotifier {
+    read_fd: RawFd,
+    write_fd: RawFd,
+}
+
+impl FlushNotifier {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn new() -> std::io::Result<FlushNotifier> {
+        use nix::sys::eventfd::{eventfd, EfdFlags};
+        let fd = eventfd(0, EfdFlags::EFD_CLOEXEC | EfdFlags::EFD_NONBLOCK)?;
+        Ok(FlushNotifier { read_fd: fd, write_fd: fd })
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn new() -> std::io::Result<FlushNotifier> {
+        use nix::fcntl::OFlag;
+        let (read_fd, write_fd) = nix::unistd::pipe2(OFlag::O_CLOEXEC | OFlag::O_NONBLOCK)?;
+        Ok(FlushNotifier { read_fd, write_fd })
+    }
+
+    fn notify(&self) {
+        // if the write fails, the notifier is already readable
+        let _ = nix::unistd::write(self.write_fd, &1u64.to_ne_bytes());
+    }
+
+    fn clear(&self) {
+        let mut buf = [0u8; 8];
+        while let Ok(n) = nix::unistd::read(self.read_fd, &mut buf) {
+            if n == 0 || self.read_fd == self.write_fd {
+                break;
+            }
+        }
+    }
+}
+
+impl Drop for FlushNotifier {
+    fn drop(&mut self) {
+        let _ = nix::unistd::close(self.read_fd);
+        if self.write_fd != self.read_fd {
+            let _ = nix::unistd::close(self.write_fd);
+        }
+    }
 }
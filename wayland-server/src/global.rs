@@ -4,10 +4,7 @@ use wayland_backend::server::{
     ClientData, ClientId, GlobalHandler, GlobalId, Handle, ObjectData, ObjectId,
 };
 
-use crate::{
-    dispatch::{DelegateDispatch, DelegateDispatchBase},
-    Client, DataInit, Dispatch, DisplayHandle, New, Resource,
-};
+use crate::{Client, DataInit, DisplayHandle, New, Resource};
 
 pub(crate) struct GlobalData<I: Resource, D: GlobalDispatch<I>> {
     pub(crate) data: <D as GlobalDispatch<I>>::GlobalData,
@@ -19,6 +16,17 @@ impl<I: Resource + 'static, D: GlobalDispatch<I> + 'static> GlobalHandler<D> for
         <D as GlobalDispatch<I>>::can_view(client, &self.data)
     }
 
+    fn advertised_version(
+        &self,
+        id: ClientId,
+        data: &Arc<dyn ClientData<D>>,
+        _: GlobalId,
+        version: u32,
+    ) -> Option<u32> {
+        let client = Client { id, data: data.clone().into_any_arc() };
+        <D as GlobalDispatch<I>>::advertised_version(client, &self.data, version)
+    }
+
     fn bind(
         self: Arc<Self>,
         handle: &mut Handle<D>,
@@ -54,7 +62,10 @@ impl<I: Resource + 'static, D: GlobalDispatch<I> + 'static> GlobalHandler<D> for
 
 /// A trait which provides an implementation for handling advertisement of a global to clients with some type
 /// of associated user data.
-pub trait GlobalDispatch<I: Resource>: Dispatch<I> {
+///
+/// The resources created by the [`bind()`](GlobalDispatch::bind) callback are handled by the
+/// [`Dispatch`](crate::Dispatch) implementation matching the user data they are initialized with.
+pub trait GlobalDispatch<I: Resource>: Sized {
     /// Data associated with the global.
     type GlobalData: Send + Sync + 'static;
 
@@ -84,6 +95,32 @@ pub trait GlobalDispatch<I: Resource>: Dispatch<I> {
     fn can_view(_client: Client, _global_data: &Self::GlobalData) -> bool {
         true
     }
+
+    /// Returns the version of the global advertised to some client.
+    ///
+    /// `version` is the version the global was created with, a higher returned value is capped to it. If
+    /// this function returns `None`, the global is hidden from the client as if [`can_view`] returned false.
+    /// Attempts of the client to bind the global with a version higher than the advertised one will raise a
+    /// protocol error. With the system backend, the global is always advertised with `version` and only a
+    /// `None` return value is taken into account.
+    ///
+    /// One use of this function is advertising a lower version of a global to legacy clients known to
+    /// misbehave with the newer one.
+    ///
+    /// The default implementation advertises `version` to all clients allowed by [`can_view`].
+    ///
+    /// [`can_view`]: GlobalDispatch::can_view
+    fn advertised_version(
+        client: Client,
+        global_data: &Self::GlobalData,
+        version: u32,
+    ) -> Option<u32> {
+        if Self::can_view(client, global_data) {
+            Some(version)
+        } else {
+            None
+        }
+    }
 }
 
 /*
@@ -91,7 +128,7 @@ pub trait GlobalDispatch<I: Resource>: Dispatch<I> {
  */
 
 /// The base trait used to define a delegate type to handle some global.
-pub trait DelegateGlobalDispatchBase<I: Resource>: DelegateDispatchBase<I> {
+pub trait DelegateGlobalDispatchBase<I: Resource> {
     /// Data associated with the global.
     type GlobalData: Send + Sync + 'static;
 }
@@ -101,9 +138,8 @@ pub trait DelegateGlobalDispatchBase<I: Resource>: DelegateDispatchBase<I> {
 /// This trait is useful for building modular handlers for globals.
 pub trait DelegateGlobalDispatch<
     I: Resource,
-    D: GlobalDispatch<I, GlobalData = <Self as DelegateGlobalDispatchBase<I>>::GlobalData>
-        + Dispatch<I, UserData = Self::UserData>,
->: Sized + DelegateGlobalDispatchBase<I> + DelegateDispatch<I, D>
+    D: GlobalDispatch<I, GlobalData = <Self as DelegateGlobalDispatchBase<I>>::GlobalData>,
+>: Sized + DelegateGlobalDispatchBase<I>
 {
     /// Called when a client has bound this global.
     ///
@@ -131,6 +167,29 @@ pub trait DelegateGlobalDispatch<
     fn can_view(_client: Client, _global_data: &Self::GlobalData) -> bool {
         true
     }
+
+    /// Returns the version of the global advertised to some client.
+    ///
+    /// `version` is the version the global was created with, a higher returned value is capped to it. If
+    /// this function returns `None`, the global is hidden from the client as if [`can_view`] returned false.
+    /// Attempts of the client to bind the global with a version higher than the advertised one will raise a
+    /// protocol error. With the system backend, the global is always advertised with `version` and only a
+    /// `None` return value is taken into account.
+    ///
+    /// The default implementation advertises `version` to all clients allowed by [`can_view`].
+    ///
+    /// [`can_view`]: DelegateGlobalDispatch::can_view
+    fn advertised_version(
+        client: Client,
+        global_data: &Self::GlobalData,
+        version: u32,
+    ) -> Option<u32> {
+        if Self::can_view(client, global_data) {
+            Some(version)
+        } else {
+            None
+        }
+    }
 }
 
 #[macro_export]
@@ -154,6 +213,10 @@ macro_rules! delegate_global_dispatch {
                 fn can_view(client: $crate::Client, global_data: &Self::GlobalData) -> bool {
                     <$dispatch_to as $crate::DelegateGlobalDispatch<$interface, Self>>::can_view(client, global_data)
                 }
+
+                fn advertised_version(client: $crate::Client, global_data: &Self::GlobalData, version: u32) -> Option<u32> {
+                    <$dispatch_to as $crate::DelegateGlobalDispatch<$interface, Self>>::advertised_version(client, global_data, version)
+                }
             }
         )*
     };
@@ -12,8 +12,8 @@ mod global;
 pub mod socket;
 
 pub use client::Client;
-pub use dispatch::{DataInit, DelegateDispatch, DelegateDispatchBase, Dispatch, New, ResourceData};
-pub use display::{Display, DisplayHandle};
+pub use dispatch::{ClosureData, DataInit, DelegateDispatch, Dispatch, New, ResourceData};
+pub use display::{Display, DisplayHandle, EventSender};
 pub use global::{DelegateGlobalDispatch, DelegateGlobalDispatchBase, GlobalDispatch};
 
 pub mod backend {
@@ -27,6 +27,25 @@ pub mod backend {
 
 pub use wayland_backend::protocol::WEnum;
 
+/// Generated interfaces for the core wayland protocol
+///
+/// Interfaces defining an `error` enum get a `post_error()` method only accepting the codes of this enum:
+///
+/// ```no_run
+/// # use wayland_server::{protocol::wl_shm, DisplayHandle};
+/// fn reject_format(shm: &wl_shm::WlShm, dh: &mut DisplayHandle<'_>) {
+///     shm.post_error(dh, wl_shm::Error::InvalidFormat, "unsupported format");
+/// }
+/// ```
+///
+/// Error codes of another interface are rejected at compile time:
+///
+/// ```compile_fail
+/// # use wayland_server::{protocol::{wl_display, wl_shm}, DisplayHandle};
+/// fn reject_format(shm: &wl_shm::WlShm, dh: &mut DisplayHandle<'_>) {
+///     shm.post_error(dh, wl_display::Error::InvalidObject, "unsupported format");
+/// }
+/// ```
 pub mod protocol {
     use self::__interfaces::*;
     use crate as wayland_server;
@@ -61,11 +80,27 @@ pub trait Resource: Sized {
         req: Self::Event,
     ) -> Result<Message<ObjectId>, InvalidId>;
 
+    /// Post a protocol error on this object
+    ///
+    /// This accepts any error code, the generated `post_error()` method of the interfaces defining an
+    /// `error` enum should be preferred as it only accepts the codes of this enum.
     #[inline]
     fn post_error(&self, dh: &mut DisplayHandle, code: impl Into<u32>, error: impl Into<String>) {
         dh.post_error(self, code.into(), error.into())
     }
 
+    /// Checks if this resource is still alive
+    #[inline]
+    fn is_alive(&self, dh: &mut DisplayHandle) -> bool {
+        dh.object_info(self.id()).is_ok()
+    }
+
+    /// Create a weak handle to this resource, which does not keep its user data alive
+    #[inline]
+    fn downgrade(&self) -> Weak<Self> {
+        Weak { id: self.id(), _iface: std::marker::PhantomData }
+    }
+
     #[doc(hidden)]
     fn __set_object_data(
         &mut self,
@@ -73,10 +108,80 @@ pub trait Resource: Sized {
     );
 }
 
+/// A weak handle to a resource
+///
+/// It can be upgraded back into the resource as long as it has not been destroyed, and can be compared
+/// and hashed like the resource it was created from.
+pub struct Weak<I> {
+    id: ObjectId,
+    _iface: std::marker::PhantomData<fn() -> I>,
+}
+
+impl<I: Resource> Weak<I> {
+    /// Try to upgrade this handle into the resource
+    ///
+    /// Returns an error if the resource has been destroyed.
+    pub fn upgrade(&self, dh: &mut DisplayHandle) -> Result<I, InvalidId> {
+        dh.object_info(self.id.clone())?;
+        I::from_id(dh, self.id.clone())
+    }
+
+    /// Checks if the resource is still alive
+    pub fn is_alive(&self, dh: &mut DisplayHandle) -> bool {
+        dh.object_info(self.id.clone()).is_ok()
+    }
+
+    /// The ID of the resource
+    pub fn id(&self) -> ObjectId {
+        self.id.clone()
+    }
+}
+
+impl<I> Clone for Weak<I> {
+    fn clone(&self) -> Self {
+        Weak { id: self.id.clone(), _iface: std::marker::PhantomData }
+    }
+}
+
+impl<I> PartialEq for Weak<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<I> Eq for Weak<I> {}
+
+impl<I: Resource> PartialEq<I> for Weak<I> {
+    fn eq(&self, other: &I) -> bool {
+        self.id == other.id()
+    }
+}
+
+impl<I> std::hash::Hash for Weak<I> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<I> std::fmt::Debug for Weak<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Weak").field(&self.id).finish()
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum DispatchError {
     #[error("Bad message for interface {interface} : {msg:?}")]
     BadMessage { msg: Message<ObjectId>, interface: &'static str },
     #[error("Unexpected interface {interface} for message {msg:?}")]
     NoHandler { msg: Message<ObjectId>, interface: &'static str },
+    #[error("Invalid value {value} for argument {arg} of {interface}.{request}")]
+    InvalidValue {
+        msg: Message<ObjectId>,
+        interface: &'static str,
+        request: &'static str,
+        arg: &'static str,
+        value: u32,
+        code: u32,
+    },
 }
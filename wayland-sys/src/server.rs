@@ -49,6 +49,7 @@ external_library!(WaylandServer, "wayland-server",
         fn wl_client_get_display(*mut wl_client) -> *mut wl_display,
         fn wl_client_get_credentials(*mut wl_client, *mut pid_t, *mut uid_t, *mut gid_t) -> (),
         fn wl_client_get_object(*mut wl_client, u32) -> *mut wl_resource,
+        fn wl_client_get_fd(*mut wl_client) -> c_int,
         fn wl_client_add_destroy_listener(*mut wl_client, *mut wl_listener) -> (),
         fn wl_client_get_destroy_listener(*mut wl_client, wl_notify_func_t) -> *mut wl_listener,
         fn wl_client_post_no_memory(*mut wl_client) -> (),
@@ -0,0 +1,246 @@
+// Throughput benchmark of a connection shared between several threads
+//
+// Several threads send wl_surface requests while an other thread concurrently reads and
+// dispatches events, and the server runs on the main thread.
+//
+// Run it with `cargo bench -p wayland-tests --bench concurrent_requests`.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[macro_use]
+#[path = "../tests/helpers/mod.rs"]
+mod helpers;
+
+use helpers::{roundtrip, wayc, ways, TestServer};
+
+use wayc::Proxy;
+
+const REQUESTS_PER_THREAD: usize = 51_200;
+// the system libwayland considers the connection dead if its outgoing buffer overflows while the
+// socket is full, so each thread flushes after a batch of requests, like after a frame
+const REQUESTS_PER_BATCH: usize = 64;
+const SYNCS_IN_FLIGHT: usize = 16;
+
+fn main() {
+    for &senders in &[1, 2, 4, 8] {
+        let (elapsed, events) = run(senders);
+        let requests = senders * REQUESTS_PER_THREAD;
+        println!(
+            "{} sender thread(s): {} requests in {:?} ({:.0} requests/s), {} events dispatched concurrently",
+            senders,
+            requests,
+            elapsed,
+            requests as f64 / elapsed.as_secs_f64(),
+            events,
+        );
+    }
+}
+
+fn run(senders: usize) -> (Duration, usize) {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_compositor::WlCompositor>(1, ());
+    let mut server_ddata = ServerHandler { commits: 0 };
+
+    let (_, mut client) = server.add_client();
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut client_ddata = ClientHandler {
+        globals: wayc::globals::GlobalList::new(),
+        display: client.display.clone(),
+        stop: stop.clone(),
+        in_flight: 0,
+        events: 0,
+    };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let compositor = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            1..2,
+            (),
+        )
+        .unwrap();
+    let surfaces = (0..senders)
+        .map(|_| compositor.create_surface(&client.event_queue.handle(), ()).unwrap())
+        .collect::<Vec<_>>();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // keep some events flowing for the reading thread
+    for _ in 0..SYNCS_IN_FLIGHT {
+        client.display.sync(&client.event_queue.handle(), ()).unwrap();
+        client_ddata.in_flight += 1;
+    }
+
+    let senders_done = Arc::new(AtomicUsize::new(0));
+    let reader_done = Arc::new(AtomicBool::new(false));
+    let start = Instant::now();
+
+    let sender_threads = surfaces
+        .into_iter()
+        .map(|surface| {
+            let conn = client.conn.clone();
+            let senders_done = senders_done.clone();
+            std::thread::spawn(move || {
+                for _ in 0..(REQUESTS_PER_THREAD / REQUESTS_PER_BATCH) {
+                    for _ in 0..REQUESTS_PER_BATCH {
+                        surface.send_request(wayc::protocol::wl_surface::Request::Commit).unwrap();
+                    }
+                    flush_all(&conn);
+                }
+                senders_done.fetch_add(1, Ordering::AcqRel);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut event_queue = client.event_queue;
+    let reader_thread = {
+        let reader_done = reader_done.clone();
+        std::thread::spawn(move || {
+            while !client_ddata.stop.load(Ordering::Acquire) || client_ddata.in_flight > 0 {
+                match event_queue.blocking_dispatch(&mut client_ddata) {
+                    Ok(_) => {}
+                    // the senders may fill the socket faster than the server reads it
+                    Err(wayc::DispatchError::Backend(wayc::backend::WaylandError::Io(e)))
+                        if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                    Err(e) => panic!("{}", e),
+                }
+            }
+            reader_done.store(true, Ordering::Release);
+            client_ddata.events
+        })
+    };
+
+    // the server runs on this thread, which thus must never wait on the client connection
+    let mut elapsed = None;
+    let expected_commits = senders * REQUESTS_PER_THREAD;
+    while !reader_done.load(Ordering::Acquire) || server_ddata.commits < expected_commits {
+        let poll_fd = server.display.backend().lock().unwrap().poll_fd();
+        let mut fds = [nix::poll::PollFd::new(poll_fd, nix::poll::PollFlags::POLLIN)];
+        let _ = nix::poll::poll(&mut fds, 10);
+        server.answer(&mut server_ddata);
+        if elapsed.is_none() && senders_done.load(Ordering::Acquire) == senders {
+            elapsed = Some(start.elapsed());
+            stop.store(true, Ordering::Release);
+        }
+    }
+
+    for thread in sender_threads {
+        thread.join().unwrap();
+    }
+    let events = reader_thread.join().unwrap();
+
+    assert_eq!(server_ddata.commits, expected_commits);
+
+    (elapsed.unwrap(), events)
+}
+
+fn flush_all(conn: &wayc::Connection) {
+    loop {
+        match conn.flush() {
+            Err(wayc::backend::WaylandError::Io(e))
+                if e.kind() == std::io::ErrorKind::WouldBlock =>
+            {
+                std::thread::sleep(Duration::from_millis(1))
+            }
+            ret => return ret.unwrap(),
+        }
+    }
+}
+
+/*
+ * Client handler
+ */
+
+struct ClientHandler {
+    globals: wayc::globals::GlobalList,
+    display: wayc::protocol::wl_display::WlDisplay,
+    stop: Arc<AtomicBool>,
+    in_flight: usize,
+    events: usize,
+}
+
+impl AsMut<wayc::globals::GlobalList> for ClientHandler {
+    fn as_mut(&mut self) -> &mut wayc::globals::GlobalList {
+        &mut self.globals
+    }
+}
+
+wayc::delegate_dispatch!(ClientHandler:
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
+);
+
+client_ignore_impl!(ClientHandler => [
+    wayc::protocol::wl_compositor::WlCompositor,
+    wayc::protocol::wl_surface::WlSurface
+]);
+
+impl wayc::Dispatch<wayc::protocol::wl_callback::WlCallback, ()> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &wayc::protocol::wl_callback::WlCallback,
+        _: wayc::protocol::wl_callback::Event,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        qhandle: &wayc::QueueHandle<Self>,
+    ) {
+        self.events += 1;
+        self.in_flight -= 1;
+        if !self.stop.load(Ordering::Acquire) {
+            self.display.sync(qhandle, ()).unwrap();
+            self.in_flight += 1;
+        }
+    }
+}
+
+/*
+ * Server handler
+ */
+
+struct ServerHandler {
+    commits: usize,
+}
+
+server_ignore_global_impl!(ServerHandler => [ways::protocol::wl_compositor::WlCompositor]);
+
+impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor, ()> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ways::protocol::wl_compositor::WlCompositor,
+        request: ways::protocol::wl_compositor::Request,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        init: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_compositor::Request::CreateSurface { id } = request {
+            init.init(id, ());
+        } else {
+            panic!("Unexpected request!");
+        }
+    }
+}
+
+impl ways::Dispatch<ways::protocol::wl_surface::WlSurface, ()> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ways::protocol::wl_surface::WlSurface,
+        request: ways::protocol::wl_surface::Request,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_surface::Request::Commit = request {
+            self.commits += 1;
+        } else {
+            panic!("Unexpected request!");
+        }
+    }
+}
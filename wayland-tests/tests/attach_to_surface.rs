@@ -26,28 +26,22 @@ fn attach_null() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // Initial sync
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let compositor = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
             (),
         )
         .unwrap();
-    let surface = compositor
-        .create_surface(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
-    surface.attach(&mut client.conn.handle(), None, 0, 0);
+    let surface = compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+    surface.attach(None, 0, 0).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -68,18 +62,14 @@ fn attach_buffer() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // Initial sync
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let shm = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_shm::WlShm, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_shm::WlShm, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -90,42 +80,21 @@ fn attach_buffer() {
     let mut file = tempfile::tempfile().unwrap();
     write!(file, "I like trains!").unwrap();
     file.flush().unwrap();
-    let pool = shm
-        .create_pool(
-            &mut client.conn.handle(),
-            file.as_raw_fd(),
-            42,
-            &client.event_queue.handle(),
-            (),
-        )
-        .unwrap();
-    let buffer = pool
-        .create_buffer(
-            &mut client.conn.handle(),
-            0,
-            0,
-            0,
-            0,
-            Format::Argb8888,
-            &client.event_queue.handle(),
-            (),
-        )
-        .unwrap();
+    let pool = shm.create_pool(file.as_raw_fd(), 42, &client.event_queue.handle(), ()).unwrap();
+    let buffer =
+        pool.create_buffer(0, 0, 0, 0, Format::Argb8888, &client.event_queue.handle(), ()).unwrap();
 
     let compositor = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
             (),
         )
         .unwrap();
-    let surface = compositor
-        .create_surface(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
-    surface.attach(&mut client.conn.handle(), Some(&buffer), 0, 0);
+    let surface = compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+    surface.attach(Some(&buffer), 0, 0).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -150,8 +119,7 @@ struct ServerHandler {
     fd_found: Option<(RawFd, Option<ServerBuffer>)>,
 }
 
-impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -169,8 +137,7 @@ impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor> for ServerHandl
     }
 }
 
-impl ways::Dispatch<ways::protocol::wl_surface::WlSurface> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ways::protocol::wl_surface::WlSurface, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -191,8 +158,7 @@ impl ways::Dispatch<ways::protocol::wl_surface::WlSurface> for ServerHandler {
     }
 }
 
-impl ways::Dispatch<ways::protocol::wl_shm::WlShm> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ways::protocol::wl_shm::WlShm, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -213,9 +179,7 @@ impl ways::Dispatch<ways::protocol::wl_shm::WlShm> for ServerHandler {
     }
 }
 
-impl ways::Dispatch<ways::protocol::wl_shm_pool::WlShmPool> for ServerHandler {
-    type UserData = ();
-
+impl ways::Dispatch<ways::protocol::wl_shm_pool::WlShmPool, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -256,7 +220,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
@@ -12,17 +12,13 @@ fn constructor_dead() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_seat::WlSeat, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_seat::WlSeat, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -30,9 +26,9 @@ fn constructor_dead() {
         )
         .unwrap();
 
-    seat.release(&mut client.conn.handle());
+    seat.release().unwrap();
 
-    assert!(seat.get_pointer(&mut client.conn.handle(), &client.event_queue.handle(), ()).is_err());
+    assert!(seat.get_pointer(&client.event_queue.handle(), ()).is_err());
 }
 
 #[test]
@@ -43,17 +39,13 @@ fn send_constructor_wrong_type() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_seat::WlSeat, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_seat::WlSeat, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -71,7 +63,7 @@ fn send_constructor_wrong_type() {
                 client
                     .event_queue
                     .handle()
-                    .make_data::<wayc::protocol::wl_keyboard::WlKeyboard>(()),
+                    .make_data::<wayc::protocol::wl_keyboard::WlKeyboard, _>(()),
             ),
         )
         .unwrap();
@@ -95,7 +87,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
@@ -110,8 +102,7 @@ client_ignore_impl!(ClientHandler => [
 
 struct ServerHandler;
 
-impl ways::Dispatch<ways::protocol::wl_seat::WlSeat> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ways::protocol::wl_seat::WlSeat, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -23,10 +23,7 @@ fn main() {
 
     let mut globals = wayc::globals::GlobalList::new();
 
-    client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut globals, &mut ServerData).unwrap();
     // check that we connected to the right compositor
@@ -43,6 +40,7 @@ fn main() {
             object_id: 1,
             object_interface: "wl_display".into(),
             message: "I don't like you!".into(),
+            message_name: None,
         },
     );
 
@@ -30,3 +30,199 @@ fn client_roundtrip() {
 
     server_thread.join().unwrap();
 }
+
+#[test]
+fn client_external_reading() {
+    let kill_switch = Arc::new(AtomicBool::new(false));
+    let server_kill_switch = kill_switch.clone();
+
+    let mut server = TestServer::new();
+
+    let (_, client) = server.add_client::<()>();
+
+    let server_thread = ::std::thread::spawn(move || loop {
+        server.display.dispatch_clients(&mut ()).unwrap();
+        server.display.flush_clients().unwrap();
+        if server_kill_switch.load(Ordering::Acquire) {
+            break;
+        }
+    });
+
+    let mut event_queue = client.conn.new_event_queue::<SyncState>();
+    event_queue.set_reading_mode(wayc::ReadingMode::External).unwrap();
+    let queue_fd = event_queue.queue_fd().unwrap();
+
+    client.display.sync(&event_queue.handle(), ()).unwrap();
+
+    // the events of this queue are read by the main thread
+    let queue_thread = ::std::thread::spawn(move || {
+        let mut state = SyncState { done: false };
+        while !state.done {
+            event_queue.blocking_dispatch(&mut state).unwrap();
+        }
+        // the queue fd is no longer readable once its events are dispatched
+        assert!(!fd_readable(event_queue.queue_fd().unwrap()));
+        event_queue
+    });
+
+    // the callback of this roundtrip is answered after the one of the event queue
+    client.conn.roundtrip().unwrap();
+
+    let event_queue = queue_thread.join().unwrap();
+    assert_eq!(event_queue.queue_fd().unwrap(), queue_fd);
+
+    kill_switch.store(true, Ordering::Release);
+
+    server_thread.join().unwrap();
+}
+
+#[test]
+fn client_queue_fd_across_dispatches() {
+    let kill_switch = Arc::new(AtomicBool::new(false));
+    let server_kill_switch = kill_switch.clone();
+
+    let mut server = TestServer::new();
+
+    let (_, client) = server.add_client::<()>();
+
+    let server_thread = ::std::thread::spawn(move || loop {
+        server.display.dispatch_clients(&mut ()).unwrap();
+        server.display.flush_clients().unwrap();
+        if server_kill_switch.load(Ordering::Acquire) {
+            break;
+        }
+    });
+
+    let mut event_queue = client.conn.new_event_queue::<SyncState>();
+    let queue_fd = event_queue.queue_fd().unwrap();
+    let mut state = SyncState { done: false };
+
+    // the queue fd starts readable, as events may already be waiting in the queue
+    assert!(fd_readable(queue_fd));
+    assert_eq!(event_queue.dispatch_pending(&mut state).unwrap(), 0);
+
+    for _ in 0..5 {
+        client.display.sync(&event_queue.handle(), ()).unwrap();
+        assert!(!fd_readable(queue_fd));
+
+        // the event for this queue is read by the roundtrip, and wakes the queue fd up every time
+        client.conn.roundtrip().unwrap();
+        assert!(fd_readable(queue_fd));
+
+        state.done = false;
+        assert!(event_queue.dispatch_pending(&mut state).unwrap() > 0);
+        assert!(state.done);
+        assert!(!fd_readable(queue_fd));
+    }
+
+    kill_switch.store(true, Ordering::Release);
+
+    server_thread.join().unwrap();
+}
+
+fn fd_readable(fd: std::os::unix::io::RawFd) -> bool {
+    let mut fds = [nix::poll::PollFd::new(fd, nix::poll::PollFlags::POLLIN)];
+    nix::poll::poll(&mut fds, 0).unwrap() > 0
+}
+
+struct SyncState {
+    done: bool,
+}
+
+impl wayc::Dispatch<wayc::protocol::wl_callback::WlCallback, ()> for SyncState {
+    fn event(
+        &mut self,
+        _: &wayc::protocol::wl_callback::WlCallback,
+        _: wayc::protocol::wl_callback::Event,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.done = true;
+    }
+}
+
+#[test]
+fn client_timeouts() {
+    let mut server = TestServer::new();
+
+    let (_, mut client) = server.add_client::<()>();
+
+    // the server does not answer
+    let timeout = std::time::Duration::from_millis(50);
+    assert!(matches!(client.conn.roundtrip_timeout(timeout), Err(wayc::DispatchError::Timeout)));
+    assert!(matches!(
+        client.conn.blocking_dispatch_timeout(timeout),
+        Err(wayc::DispatchError::Timeout)
+    ));
+    assert!(matches!(
+        client.event_queue.blocking_dispatch_timeout(&mut (), timeout),
+        Err(wayc::DispatchError::Timeout)
+    ));
+
+    // the answer to the first sync is then received
+    server.answer(&mut ());
+    assert!(client.conn.blocking_dispatch_timeout(std::time::Duration::from_secs(5)).unwrap() > 0);
+}
+
+#[test]
+fn client_sync_handle() {
+    let kill_switch = Arc::new(AtomicBool::new(false));
+    let server_kill_switch = kill_switch.clone();
+
+    let mut server = TestServer::new();
+
+    let (_, client) = server.add_client::<()>();
+
+    let sync = client.conn.sync().unwrap();
+    assert!(!sync.is_done());
+
+    let server_thread = ::std::thread::spawn(move || loop {
+        server.display.dispatch_clients(&mut ()).unwrap();
+        server.display.flush_clients().unwrap();
+        if server_kill_switch.load(Ordering::Acquire) {
+            break;
+        }
+    });
+
+    while !sync.is_done() {
+        client.conn.blocking_dispatch().unwrap();
+    }
+
+    kill_switch.store(true, Ordering::Release);
+
+    server_thread.join().unwrap();
+}
+
+#[test]
+fn client_sync_handle_connection_lost() {
+    use std::future::Future;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::Release);
+        }
+    }
+
+    let mut server = TestServer::<()>::new();
+
+    let (_, client) = server.add_client::<()>();
+
+    let mut sync = client.conn.sync().unwrap();
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = Waker::from(flag.clone());
+    let mut cx = Context::from_waker(&waker);
+    assert!(std::pin::Pin::new(&mut sync).poll(&mut cx).is_pending());
+
+    // the server goes away without answering
+    std::mem::drop(server);
+    assert!(client.conn.blocking_dispatch().is_err());
+
+    // the pending sync is woken up and resolves to the error
+    assert!(flag.0.load(Ordering::Acquire));
+    assert!(matches!(std::pin::Pin::new(&mut sync).poll(&mut cx), Poll::Ready(Err(_))));
+    assert!(!sync.is_done());
+}
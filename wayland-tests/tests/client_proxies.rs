@@ -16,17 +16,13 @@ fn proxy_equals() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let compositor1 = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -36,8 +32,7 @@ fn proxy_equals() {
 
     let compositor2 = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -61,17 +56,13 @@ fn proxy_user_data() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let compositor1 = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -81,8 +72,7 @@ fn proxy_user_data() {
 
     let compositor2 = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -107,17 +97,13 @@ fn dead_proxies() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let output = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -134,7 +120,7 @@ fn dead_proxies() {
     assert!(client.conn.handle().object_info(output2.id()).is_ok());
 
     // kill the output
-    output.release(&mut client.conn.handle());
+    output.release().unwrap();
 
     // dead proxies are still equal
     assert!(output == output2);
@@ -142,6 +128,54 @@ fn dead_proxies() {
     assert!(client.conn.handle().object_info(output2.id()).is_err());
 }
 
+#[test]
+fn weak_proxies() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(3, ());
+    let mut server_ddata = ServerHandler { output: None };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let output = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            3..4,
+            (),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let weak = output.downgrade();
+    assert!(weak == output);
+    assert!(output.is_alive());
+    assert!(weak.is_alive());
+    assert!(weak.upgrade().unwrap() == output);
+
+    // proxies and weak handles can be used as keys
+    let mut set = std::collections::HashSet::new();
+    set.insert(output.clone());
+    assert!(set.contains(&weak.upgrade().unwrap()));
+    let mut weak_set = std::collections::HashSet::new();
+    weak_set.insert(weak.clone());
+    assert!(weak_set.contains(&output.downgrade()));
+
+    // kill the output
+    output.release().unwrap();
+
+    assert!(!output.is_alive());
+    assert!(!weak.is_alive());
+    assert!(weak.upgrade().is_err());
+    assert!(weak == output);
+}
+
 #[test]
 fn dead_object_argument() {
     let mut server = TestServer::new();
@@ -152,17 +186,54 @@ fn dead_object_argument() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let output = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            3..4,
+            (),
+        )
+        .unwrap();
+    let compositor = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            1..2,
+            0,
+        )
+        .unwrap();
+
+    compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+    output.release().unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert!(client_ddata.entered);
+}
+
+#[test]
+fn handle_free_requests() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_compositor::WlCompositor>(1, ());
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(3, ());
+    let mut server_ddata = ServerHandler { output: None };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -171,8 +242,7 @@ fn dead_object_argument() {
         .unwrap();
     let compositor = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -180,12 +250,37 @@ fn dead_object_argument() {
         )
         .unwrap();
 
-    compositor.create_surface(&mut client.conn.handle(), &client.event_queue.handle(), ()).unwrap();
-    output.release(&mut client.conn.handle());
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // no ConnectionHandle is needed to send requests through the proxies
+    let surface = compositor
+        .send_constructor::<wayc::protocol::wl_surface::WlSurface>(
+            wayc::protocol::wl_compositor::Request::CreateSurface {},
+            client.event_queue.handle().make_data::<wayc::protocol::wl_surface::WlSurface, _>(()),
+        )
+        .unwrap();
+    surface.send_request(wayc::protocol::wl_surface::Request::Commit).unwrap();
+    // which is also what the generated request methods do
+    surface.commit().unwrap();
+    // while their handle-based variants remain available for batched use
+    let mut handle = client.conn.handle();
+    surface.damage_with_handle(&mut handle, 0, 0, 10, 10).unwrap();
+    surface.commit_with_handle(&mut handle).unwrap();
+    std::mem::drop(handle);
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     assert!(client_ddata.entered);
+    assert!(surface.connection().upgrade().is_some());
+
+    // once the connection is dropped, sending requests fails
+    std::mem::drop(client);
+    assert!(surface.connection().upgrade().is_none());
+    assert!(matches!(
+        surface.send_request(wayc::protocol::wl_surface::Request::Commit),
+        Err(wayc::RequestError::Disconnected)
+    ));
+    assert!(matches!(surface.commit(), Err(wayc::RequestError::Disconnected)));
 }
 
 struct ServerHandler {
@@ -207,8 +302,7 @@ impl ways::GlobalDispatch<ways::protocol::wl_output::WlOutput> for ServerHandler
     }
 }
 
-impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -254,12 +348,10 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
-impl wayc::Dispatch<wayc::protocol::wl_compositor::WlCompositor> for ClientHandler {
-    type UserData = usize;
-
+impl wayc::Dispatch<wayc::protocol::wl_compositor::WlCompositor, usize> for ClientHandler {
     fn event(
         &mut self,
         _: &wayc::protocol::wl_compositor::WlCompositor,
@@ -271,9 +363,7 @@ impl wayc::Dispatch<wayc::protocol::wl_compositor::WlCompositor> for ClientHandl
     }
 }
 
-impl wayc::Dispatch<wayc::protocol::wl_surface::WlSurface> for ClientHandler {
-    type UserData = ();
-
+impl wayc::Dispatch<wayc::protocol::wl_surface::WlSurface, ()> for ClientHandler {
     fn event(
         &mut self,
         _: &wayc::protocol::wl_surface::WlSurface,
@@ -283,7 +373,9 @@ impl wayc::Dispatch<wayc::protocol::wl_surface::WlSurface> for ClientHandler {
         _: &wayc::QueueHandle<Self>,
     ) {
         if let wayc::protocol::wl_surface::Event::Enter { output } = event {
-            assert!(connhandle.get_object_data(output.id()).is_err());
+            if output.data::<()>().is_none() {
+                assert!(connhandle.get_object_data(output.id()).is_err());
+            }
             self.entered = true;
         } else {
             panic!("Unexpected event: {:?}", event);
@@ -0,0 +1,129 @@
+#[macro_use]
+mod helpers;
+
+use helpers::{roundtrip, wayc, ways, TestServer};
+
+use ways::protocol::wl_compositor::{
+    Request as ServerCompositorRequest, WlCompositor as ServerCompositor,
+};
+use ways::protocol::wl_surface::WlSurface as ServerSurface;
+
+use wayc::protocol::wl_callback::{Event as CallbackEvent, WlCallback};
+use wayc::protocol::wl_compositor::WlCompositor as ClientCompositor;
+use wayc::Proxy;
+
+#[test]
+fn closure_handlers() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerCompositor>(1, ());
+    let mut server_ddata = ServerHandler { surfaces: 0 };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new(), done: 0 };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // the compositor is handled by a trait implementation client-side, and a closure server-side
+    let compositor = client_ddata
+        .globals
+        .bind::<ClientCompositor, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
+        .unwrap();
+
+    compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+    compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+
+    client
+        .display
+        .send_constructor::<WlCallback>(
+            wayc::protocol::wl_display::Request::Sync {},
+            client.event_queue.handle().make_closure_data(wayc::ClosureData::new(
+                |state: &mut ClientHandler, _: &WlCallback, event, _, _| {
+                    if let CallbackEvent::Done { .. } = event {
+                        state.done += 1;
+                    }
+                },
+            )),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(server_ddata.surfaces, 2);
+    assert_eq!(client_ddata.done, 1);
+}
+
+struct ServerHandler {
+    surfaces: usize,
+}
+
+impl ways::GlobalDispatch<ServerCompositor> for ServerHandler {
+    type GlobalData = ();
+
+    fn bind(
+        &mut self,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &ways::Client,
+        resource: ways::New<ServerCompositor>,
+        _: &(),
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        data_init.init_closure(
+            resource,
+            ways::ClosureData::new(
+                |state: &mut ServerHandler, _, _: &ServerCompositor, request, _, data_init| {
+                    if let ServerCompositorRequest::CreateSurface { id } = request {
+                        data_init.init(id, ());
+                        state.surfaces += 1;
+                    }
+                },
+            ),
+        );
+    }
+}
+
+// implementations generic over the user data do not conflict with the closure handlers
+impl<U: Send + Sync + 'static> ways::Dispatch<ServerSurface, U> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ServerSurface,
+        _: ways::protocol::wl_surface::Request,
+        _: &U,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+    }
+}
+
+struct ClientHandler {
+    globals: wayc::globals::GlobalList,
+    done: usize,
+}
+
+impl AsMut<wayc::globals::GlobalList> for ClientHandler {
+    fn as_mut(&mut self) -> &mut wayc::globals::GlobalList {
+        &mut self.globals
+    }
+}
+
+wayc::delegate_dispatch!(ClientHandler:
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
+);
+
+client_ignore_impl!(ClientHandler => [ClientCompositor]);
+
+impl<U: Send + Sync + 'static> wayc::Dispatch<wayc::protocol::wl_surface::WlSurface, U>
+    for ClientHandler
+{
+    fn event(
+        &mut self,
+        _: &wayc::protocol::wl_surface::WlSurface,
+        _: wayc::protocol::wl_surface::Event,
+        _: &U,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+    }
+}
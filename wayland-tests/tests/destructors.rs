@@ -17,17 +17,13 @@ fn resource_destructor_request() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let output = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -37,7 +33,7 @@ fn resource_destructor_request() {
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
-    output.release(&mut client.conn.handle());
+    output.release().unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -53,17 +49,13 @@ fn resource_destructor_cleanup() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -92,17 +84,13 @@ fn client_destructor_cleanup() {
         server.add_client_with_data(Arc::new(DestructorClientData(destructor_called.clone())));
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -119,6 +107,56 @@ fn client_destructor_cleanup() {
     assert!(destructor_called.load(Ordering::Acquire));
 }
 
+#[test]
+fn proxy_destructor_request() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(3, ());
+    let mut server_ddata = ServerHandler { destructor_called: Arc::new(AtomicBool::new(false)) };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let output = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            3..4,
+            (),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+    assert!(client_ddata.log.is_empty());
+
+    output.release().unwrap();
+
+    // the notification is delivered through the event queue
+    assert!(client_ddata.log.is_empty());
+    client.event_queue.dispatch_pending(&mut client_ddata).unwrap();
+    assert_eq!(client_ddata.log, ["output destroyed"]);
+}
+
+#[test]
+fn proxy_destructor_event() {
+    let mut server = TestServer::<ServerHandler>::new();
+    let mut server_ddata = ServerHandler { destructor_called: Arc::new(AtomicBool::new(false)) };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    client.display.sync(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // the destruction is notified after the destructor event
+    assert_eq!(client_ddata.log, ["callback done", "callback destroyed"]);
+}
+
 struct DestructorClientData(Arc<AtomicBool>);
 
 impl ways::backend::ClientData<ServerHandler> for DestructorClientData {
@@ -154,8 +192,7 @@ impl ways::GlobalDispatch<ways::protocol::wl_output::WlOutput> for ServerHandler
     }
 }
 
-impl ways::Dispatch<ways::protocol::wl_output::WlOutput> for ServerHandler {
-    type UserData = ServerUData;
+impl ways::Dispatch<ways::protocol::wl_output::WlOutput, ServerUData> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
@@ -171,7 +208,7 @@ impl ways::Dispatch<ways::protocol::wl_output::WlOutput> for ServerHandler {
         &mut self,
         _client: wayland_backend::server::ClientId,
         _resource: wayland_backend::server::ObjectId,
-        data: &Self::UserData,
+        data: &ServerUData,
     ) {
         data.0.store(true, Ordering::Release);
     }
@@ -179,11 +216,12 @@ impl ways::Dispatch<ways::protocol::wl_output::WlOutput> for ServerHandler {
 
 struct ClientHandler {
     globals: wayc::globals::GlobalList,
+    log: Vec<&'static str>,
 }
 
 impl ClientHandler {
     fn new() -> ClientHandler {
-        ClientHandler { globals: Default::default() }
+        ClientHandler { globals: Default::default(), log: Vec::new() }
     }
 }
 
@@ -194,9 +232,50 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
-client_ignore_impl!(ClientHandler => [
-    wayc::protocol::wl_output::WlOutput
-]);
+impl wayc::Dispatch<wayc::protocol::wl_output::WlOutput, ()> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &wayc::protocol::wl_output::WlOutput,
+        _: wayc::protocol::wl_output::Event,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+    }
+
+    fn destroyed(
+        &mut self,
+        _: wayc::backend::ObjectId,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.log.push("output destroyed");
+    }
+}
+
+impl wayc::Dispatch<wayc::protocol::wl_callback::WlCallback, ()> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &wayc::protocol::wl_callback::WlCallback,
+        _: wayc::protocol::wl_callback::Event,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.log.push("callback done");
+    }
+
+    fn destroyed(
+        &mut self,
+        _: wayc::backend::ObjectId,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.log.push("callback destroyed");
+    }
+}
@@ -0,0 +1,209 @@
+#[macro_use]
+mod helpers;
+
+use helpers::{roundtrip, wayc, ways, TestServer};
+
+use wayc::Proxy;
+
+use ways::protocol::{
+    wl_compositor::WlCompositor as ServerCompositor, wl_surface::WlSurface as ServerSurface,
+};
+
+use wayc::protocol::{
+    wl_compositor::WlCompositor as ClientCompositor, wl_surface::WlSurface as ClientSurface,
+};
+
+#[test]
+fn multiple_udata_types() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerCompositor>(1, ());
+    let mut server_ddata = ServerHandler { surfaces: 0, log: Vec::new() };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata =
+        ClientHandler { globals: wayc::globals::GlobalList::new(), log: Vec::new() };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let compositor = client_ddata
+        .globals
+        .bind::<ClientCompositor, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
+        .unwrap();
+
+    let window = compositor.create_surface(&client.event_queue.handle(), ClientWindow).unwrap();
+    let cursor = compositor.create_surface(&client.event_queue.handle(), ClientCursor).unwrap();
+
+    assert!(window.data::<ClientWindow>().is_some());
+    assert!(cursor.data::<ClientCursor>().is_some());
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    window.commit().unwrap();
+    cursor.commit().unwrap();
+    window.destroy().unwrap();
+    cursor.destroy().unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(
+        server_ddata.log,
+        ["window commit", "cursor commit", "window destroyed", "cursor destroyed"]
+    );
+    assert_eq!(client_ddata.log, ["window destroyed", "cursor destroyed"]);
+}
+
+/*
+ * Server handler
+ */
+
+struct ServerHandler {
+    surfaces: usize,
+    log: Vec<&'static str>,
+}
+
+struct ServerWindow;
+struct ServerCursor;
+
+impl ways::Dispatch<ServerCompositor, ()> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ServerCompositor,
+        request: ways::protocol::wl_compositor::Request,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_compositor::Request::CreateSurface { id } = request {
+            // the first surface is the window, the second the cursor
+            if self.surfaces == 0 {
+                data_init.init(id, ServerWindow);
+            } else {
+                data_init.init(id, ServerCursor);
+            }
+            self.surfaces += 1;
+        }
+    }
+}
+
+impl ways::Dispatch<ServerSurface, ServerWindow> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ServerSurface,
+        request: ways::protocol::wl_surface::Request,
+        _: &ServerWindow,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_surface::Request::Commit = request {
+            self.log.push("window commit");
+        }
+    }
+
+    fn destroyed(
+        &mut self,
+        _: ways::backend::ClientId,
+        _: ways::backend::ObjectId,
+        _: &ServerWindow,
+    ) {
+        self.log.push("window destroyed");
+    }
+}
+
+impl ways::Dispatch<ServerSurface, ServerCursor> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ServerSurface,
+        request: ways::protocol::wl_surface::Request,
+        _: &ServerCursor,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_surface::Request::Commit = request {
+            self.log.push("cursor commit");
+        }
+    }
+
+    fn destroyed(
+        &mut self,
+        _: ways::backend::ClientId,
+        _: ways::backend::ObjectId,
+        _: &ServerCursor,
+    ) {
+        self.log.push("cursor destroyed");
+    }
+}
+
+server_ignore_global_impl!(ServerHandler => [ServerCompositor]);
+
+/*
+ * Client handler
+ */
+
+struct ClientHandler {
+    globals: wayc::globals::GlobalList,
+    log: Vec<&'static str>,
+}
+
+struct ClientWindow;
+struct ClientCursor;
+
+impl AsMut<wayc::globals::GlobalList> for ClientHandler {
+    fn as_mut(&mut self) -> &mut wayc::globals::GlobalList {
+        &mut self.globals
+    }
+}
+
+wayc::delegate_dispatch!(ClientHandler:
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
+);
+
+client_ignore_impl!(ClientHandler => [ClientCompositor]);
+
+impl wayc::Dispatch<ClientSurface, ClientWindow> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &ClientSurface,
+        _: wayc::protocol::wl_surface::Event,
+        _: &ClientWindow,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+    }
+
+    fn destroyed(
+        &mut self,
+        _: wayc::backend::ObjectId,
+        _: &ClientWindow,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.log.push("window destroyed");
+    }
+}
+
+impl wayc::Dispatch<ClientSurface, ClientCursor> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &ClientSurface,
+        _: wayc::protocol::wl_surface::Event,
+        _: &ClientCursor,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+    }
+
+    fn destroyed(
+        &mut self,
+        _: wayc::backend::ObjectId,
+        _: &ClientCursor,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.log.push("cursor destroyed");
+    }
+}
@@ -17,10 +17,7 @@ fn simple_global() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
@@ -42,10 +39,7 @@ fn multi_versions() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
@@ -67,10 +61,7 @@ fn dynamic_global() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
     assert!(client_ddata.globals.list().len() == 1);
@@ -105,39 +96,23 @@ fn range_instantiate() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
     let compositor = client_ddata
         .globals
-        .bind::<WlCompositor, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..5,
-            (),
-        )
+        .bind::<WlCompositor, _, _>(&client.event_queue.handle(), &registry, 1..5, ())
         .unwrap();
     assert!(compositor.version() == 4);
     let shell = client_ddata
         .globals
-        .bind::<WlShell, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..3,
-            (),
-        )
+        .bind::<WlShell, _, _>(&client.event_queue.handle(), &registry, 1..3, ())
         .unwrap();
     assert!(shell.version() == 1);
 
     assert!(matches!(
-        client_ddata.globals.bind::<WlCompositor, _>(
-            &mut client.conn.handle(),
+        client_ddata.globals.bind::<WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             5..6,
@@ -150,8 +125,7 @@ fn range_instantiate() {
         })
     ));
     assert!(matches!(
-        client_ddata.globals.bind::<WlOutput, _>(
-            &mut client.conn.handle(),
+        client_ddata.globals.bind::<WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..4,
@@ -161,6 +135,162 @@ fn range_instantiate() {
     ));
 }
 
+#[test]
+fn range_clamping() {
+    use wayc::protocol::wl_compositor::WlCompositor;
+    use wayc::Proxy;
+
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerCompositor>(4, ());
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let compositor = client_ddata
+        .globals
+        .bind::<WlCompositor, _, _>(&client.event_queue.handle(), &registry, 1..3, ())
+        .unwrap();
+    assert_eq!(compositor.version(), 2);
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+}
+
+#[test]
+fn bind_all_instances() {
+    use wayc::protocol::wl_output::WlOutput;
+    use wayc::Proxy;
+
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerOutput>(1, ());
+    server.display.create_global::<ServerCompositor>(1, ());
+    server.display.create_global::<ServerOutput>(3, ());
+    server.display.create_global::<ServerOutput>(2, ());
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let outputs = client_ddata
+        .globals
+        .bind_all::<WlOutput, _, _>(&client.event_queue.handle(), &registry, 2..4, ())
+        .unwrap();
+    let mut versions = outputs.iter().map(|o| o.version()).collect::<Vec<_>>();
+    versions.sort_unstable();
+    assert_eq!(versions, [2, 3]);
+
+    // binding the first instance skips the one with a too low version
+    let output = client_ddata
+        .globals
+        .bind::<WlOutput, _, _>(&client.event_queue.handle(), &registry, 2..3, ())
+        .unwrap();
+    assert_eq!(output.version(), 2);
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+}
+
+#[test]
+fn global_events() {
+    use wayc::globals::GlobalEvent;
+    use wayc::protocol::wl_output::WlOutput;
+
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerCompositor>(1, ());
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
+    client_ddata.globals.set_record_events(true);
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let events = client_ddata.globals.take_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], GlobalEvent::New(desc) if desc.interface == "wl_compositor"));
+
+    let output = server.display.create_global::<ServerOutput>(2, ());
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let events = client_ddata.globals.take_events();
+    assert_eq!(events.len(), 1);
+    let name = match &events[0] {
+        GlobalEvent::New(desc) => {
+            assert_eq!(desc.interface, "wl_output");
+            assert_eq!(desc.version, 2);
+            desc.name
+        }
+        other => panic!("Unexpected event: {:?}", other),
+    };
+
+    client_ddata
+        .globals
+        .bind_name::<WlOutput, _, _>(&client.event_queue.handle(), &registry, name, 1..4, ())
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    server.display.remove_global(output);
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let events = client_ddata.globals.take_events();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], GlobalEvent::Removed(desc) if desc.name == name));
+    assert!(client_ddata.globals.take_events().is_empty());
+    assert_eq!(client_ddata.globals.list().len(), 1);
+
+    // the changes are no longer recorded once disabled
+    client_ddata.globals.set_record_events(false);
+    server.display.create_global::<ServerOutput>(2, ());
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    assert!(client_ddata.globals.take_events().is_empty());
+    assert_eq!(client_ddata.globals.list().len(), 2);
+}
+
+#[test]
+fn registry_queue_init() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let kill_switch = Arc::new(AtomicBool::new(false));
+    let server_kill_switch = kill_switch.clone();
+
+    let mut server = TestServer::new();
+    server.display.create_global::<ServerCompositor>(1, ());
+    server.display.create_global::<ServerOutput>(2, ());
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
+    client_ddata.globals.set_record_events(true);
+
+    let server_thread = ::std::thread::spawn(move || loop {
+        server.display.dispatch_clients(&mut ServerHandler).unwrap();
+        server.display.flush_clients().unwrap();
+        if server_kill_switch.load(Ordering::Acquire) {
+            break;
+        }
+    });
+
+    wayc::globals::registry_queue_init(&client.conn, &mut client.event_queue, &mut client_ddata)
+        .unwrap();
+
+    kill_switch.store(true, Ordering::Release);
+    server_thread.join().unwrap();
+
+    assert_eq!(client_ddata.globals.list().len(), 2);
+    assert!(client_ddata.globals.take_events().is_empty());
+}
+
 #[test]
 #[should_panic]
 fn wrong_version_create_global() {
@@ -178,17 +308,12 @@ fn wrong_global() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // instantiate a wrong global, this should kill the client
     // but currently does not fail on native_lib
 
-    registry
-        .bind::<WlOutput, _>(&mut client.conn.handle(), 1, 1, &client.event_queue.handle(), ())
-        .unwrap();
+    registry.bind::<WlOutput, _, _>(1, 1, &client.event_queue.handle(), ()).unwrap();
 
     assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
 }
@@ -203,16 +328,11 @@ fn wrong_global_version() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // instantiate a global with wrong version, this should kill the client
 
-    registry
-        .bind::<WlCompositor, _>(&mut client.conn.handle(), 1, 2, &client.event_queue.handle(), ())
-        .unwrap();
+    registry.bind::<WlCompositor, _, _>(1, 2, &client.event_queue.handle(), ()).unwrap();
 
     assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
 }
@@ -227,16 +347,11 @@ fn invalid_global_version() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // instantiate a global with version 0, which is invalid this should kill the client
 
-    registry
-        .bind::<WlCompositor, _>(&mut client.conn.handle(), 1, 0, &client.event_queue.handle(), ())
-        .unwrap();
+    registry.bind::<WlCompositor, _, _>(1, 0, &client.event_queue.handle(), ()).unwrap();
 
     assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
 }
@@ -251,16 +366,11 @@ fn wrong_global_id() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     // instantiate a global with version 0, which is invalid this should kill the client
 
-    registry
-        .bind::<WlCompositor, _>(&mut client.conn.handle(), 3, 1, &client.event_queue.handle(), ())
-        .unwrap();
+    registry.bind::<WlCompositor, _, _>(3, 1, &client.event_queue.handle(), ()).unwrap();
 
     assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
 }
@@ -275,10 +385,7 @@ fn two_step_binding() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler { globals: wayc::globals::GlobalList::new() };
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
@@ -289,24 +396,12 @@ fn two_step_binding() {
 
     client_ddata
         .globals
-        .bind::<WlCompositor, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<WlCompositor, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
 
     client_ddata
         .globals
-        .bind::<WlOutput, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<WlOutput, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
@@ -328,7 +423,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
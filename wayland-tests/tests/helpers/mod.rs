@@ -118,7 +118,7 @@ impl wayc::backend::ObjectData for SyncData {
         None
     }
 
-    fn destroyed(&self, _: wayc::backend::ObjectId) {}
+    fn destroyed(self: Arc<Self>, _: wayc::backend::ObjectId) {}
 }
 
 pub struct DumbClientData;
@@ -131,13 +131,12 @@ impl<D> ways::backend::ClientData<D> for DumbClientData {
 macro_rules! client_ignore_impl {
     ($handler:ty => [$($iface:ty),*]) => {
         $(
-            impl $crate::helpers::wayc::Dispatch<$iface> for $handler {
-                type UserData = ();
+            impl $crate::helpers::wayc::Dispatch<$iface, ()> for $handler {
                 fn event(
                     &mut self,
                     _: &$iface,
                     _: <$iface as $crate::helpers::wayc::Proxy>::Event,
-                    _: &Self::UserData,
+                    _: &(),
                     _: &mut $crate::helpers::wayc::ConnectionHandle,
                     _: &$crate::helpers::wayc::QueueHandle<Self>,
                 ) {
@@ -150,14 +149,13 @@ macro_rules! client_ignore_impl {
 macro_rules! server_ignore_impl {
     ($handler:ty => [$($iface:ty),*]) => {
         $(
-            impl $crate::helpers::ways::Dispatch<$iface> for $handler {
-                type UserData = ();
+            impl $crate::helpers::ways::Dispatch<$iface, ()> for $handler {
                 fn request(
                     &mut self,
                     _: &$crate::helpers::ways::Client,
                     _: &$iface,
                     _: <$iface as $crate::helpers::ways::Resource>::Request,
-                    _: &Self::UserData,
+                    _: &(),
                     _: &mut $crate::helpers::ways::DisplayHandle<'_>,
                     _: &mut $crate::helpers::ways::DataInit<'_, Self>,
                 ) {
@@ -0,0 +1,45 @@
+#[macro_use]
+mod helpers;
+
+use helpers::{wayc, ways};
+
+#[test]
+fn client_message_info() {
+    use wayc::protocol::{wl_output, wl_registry};
+
+    let req = wl_output::Request::Release;
+    assert_eq!(req.opcode(), 0);
+    assert_eq!(req.name(), "release");
+    assert_eq!(req.since(), 3);
+    assert!(req.is_destructor());
+
+    let evt = wl_output::Event::Scale { factor: 2 };
+    assert_eq!(evt.opcode(), 3);
+    assert_eq!(evt.name(), "scale");
+    assert_eq!(evt.since(), 2);
+    assert!(!evt.is_destructor());
+    assert_eq!(evt.info(), &wl_output::EVENTS[3]);
+
+    assert_eq!(wl_registry::REQUESTS.len(), 1);
+    assert_eq!(wl_registry::REQUESTS[0].name, "bind");
+    assert_eq!(wl_registry::EVENTS[1].name, "global_remove");
+}
+
+#[test]
+fn server_message_info() {
+    use ways::protocol::wl_output;
+
+    let req = wl_output::Request::Release;
+    assert_eq!(req.opcode(), 0);
+    assert_eq!(req.name(), "release");
+    assert!(req.is_destructor());
+
+    let evt = wl_output::Event::Done;
+    assert_eq!(evt.opcode(), 2);
+    assert_eq!(evt.name(), "done");
+    assert_eq!(evt.since(), 2);
+    assert_eq!(
+        wl_output::EVENTS.iter().map(|info| info.opcode).collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4, 5]
+    );
+}
@@ -0,0 +1,123 @@
+mod test_protocol {
+    pub mod client {
+        use wayland_client;
+
+        pub mod __interfaces {
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_client_code!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+    }
+
+    pub mod server {
+        use wayland_server;
+
+        pub mod __interfaces {
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_server_code!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+    }
+}
+
+// The interfaces borrowed from test-protocol.xml are resolved and imported by the scanner, only the
+// core protocol needs to be in scope
+mod dependent_protocol {
+    pub mod client {
+        use wayland_client;
+        use wayland_client::protocol::*;
+
+        pub mod __interfaces {
+            use wayland_client::protocol::__interfaces::*;
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-dependent-protocol.xml",
+                [
+                    "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+                        => crate::test_protocol::client
+                ]
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_client_code!(
+            "../wayland-scanner/tests/scanner_assets/test-dependent-protocol.xml",
+            [
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+                    => crate::test_protocol::client
+            ]
+        );
+    }
+
+    pub mod server {
+        use wayland_server;
+        use wayland_server::protocol::*;
+
+        pub mod __interfaces {
+            use wayland_server::protocol::__interfaces::*;
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-dependent-protocol.xml",
+                [
+                    "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+                        => crate::test_protocol::server
+                ]
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_server_code!(
+            "../wayland-scanner/tests/scanner_assets/test-dependent-protocol.xml",
+            [
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+                    => crate::test_protocol::server
+            ]
+        );
+    }
+}
+
+use wayland_backend::protocol::WEnum;
+
+use dependent_protocol::server::test_wrapper_manager;
+use test_protocol::server::{secondary, test_global};
+
+#[test]
+fn dependent_interfaces() {
+    use dependent_protocol::client::__interfaces::TEST_WRAPPER_MANAGER_INTERFACE;
+
+    let wrap = &TEST_WRAPPER_MANAGER_INTERFACE.requests[0];
+    assert_eq!(wrap.child_interface.unwrap().name, "test_wrapper");
+    assert_eq!(
+        wrap.arg_interfaces.iter().map(|iface| iface.name).collect::<Vec<_>>(),
+        ["secondary", "wl_surface"]
+    );
+}
+
+// the scanner resolves the core interfaces from its own copy of the core protocol
+#[test]
+fn bundled_core_protocol_is_up_to_date() {
+    let read = |path: &str| {
+        std::fs::read_to_string(format!("{}/../{}", env!("CARGO_MANIFEST_DIR"), path)).unwrap()
+    };
+    let bundled = read("wayland-scanner/wayland.xml");
+    assert_eq!(bundled, read("wayland-client/wayland.xml"));
+    assert_eq!(bundled, read("wayland-server/wayland.xml"));
+}
+
+// the generated messages use the types of the dependency
+#[allow(dead_code)]
+fn wrap_args(
+    request: test_wrapper_manager::Request,
+) -> (secondary::Secondary, WEnum<test_global::Mode>) {
+    match request {
+        test_wrapper_manager::Request::Wrap { sec, mode, .. } => (sec, mode),
+    }
+}
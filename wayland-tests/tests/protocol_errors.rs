@@ -13,18 +13,14 @@ fn client_receive_generic_error() {
 
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
 
     // Instantiate the globals
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -56,6 +52,141 @@ fn client_receive_generic_error() {
     {
         assert_eq!(error.message, "I don't like you!");
     }
+
+    // syncing the dead connection reports the protocol error
+    match client.conn.sync() {
+        Err(wayc::backend::WaylandError::Protocol(err)) => assert_eq!(err.code, 42),
+        other => panic!("Unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn client_decode_typed_error() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_shm::WlShm>(1, ());
+
+    let (s_client, mut client) = server.add_client();
+
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_shm::WlShm, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            1..2,
+            (),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let shm = s_client
+        .object_from_protocol_id::<ways::protocol::wl_shm::WlShm>(&mut server.display.handle(), 3)
+        .unwrap();
+    shm.post_error(
+        &mut server.display.handle(),
+        ways::protocol::wl_shm::Error::InvalidStride,
+        "Bad stride",
+    );
+
+    assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
+    let error = client.conn.protocol_error_as::<wayc::protocol::wl_shm::Error>().unwrap();
+    assert_eq!(error, wayc::protocol::wl_shm::Error::InvalidStride);
+    assert_eq!(error.description(), "invalid size or stride during pool or buffer creation");
+    // the error was raised by an object of an other interface
+    assert!(client.conn.protocol_error_as::<wayc::protocol::wl_display::Error>().is_none());
+}
+
+#[test]
+fn server_post_default_error_message() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_shm::WlShm>(1, ());
+
+    let (s_client, mut client) = server.add_client();
+
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_shm::WlShm, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            1..2,
+            (),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let shm = s_client
+        .object_from_protocol_id::<ways::protocol::wl_shm::WlShm>(&mut server.display.handle(), 3)
+        .unwrap();
+    shm.post_error_default(&mut server.display.handle(), ways::protocol::wl_shm::Error::InvalidFd);
+
+    assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
+    let error = client.conn.protocol_error().unwrap();
+    assert_eq!(error.code, 2);
+    assert_eq!(error.object_interface, "wl_shm");
+    // native lib can't give us the message
+    #[cfg(not(feature = "client_system"))]
+    {
+        assert_eq!(error.message, "mmapping the file descriptor failed");
+    }
+}
+
+#[test]
+fn server_reject_invalid_enum_value() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_compositor::WlCompositor>(4, ());
+
+    let (_, mut client) = server.add_client();
+
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    let compositor = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            4..5,
+            (),
+        )
+        .unwrap();
+    let surface = compositor.create_surface(&client.event_queue.handle(), ()).unwrap();
+
+    // a known value goes through
+    surface.set_buffer_transform(wayc::protocol::wl_output::Transform::Flipped90).unwrap();
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).unwrap();
+
+    // an unknown value is rejected by the generated code with the dedicated error of the interface
+    client
+        .conn
+        .handle()
+        .send_request(
+            &surface,
+            wayc::protocol::wl_surface::Request::SetBufferTransform {
+                transform: wayc::WEnum::Unknown(42),
+            },
+            None,
+        )
+        .unwrap();
+
+    assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut ServerHandler).is_err());
+    let error = client.conn.protocol_error_as::<wayc::protocol::wl_surface::Error>().unwrap();
+    assert_eq!(error, wayc::protocol::wl_surface::Error::InvalidTransform);
 }
 
 struct ClientHandler {
@@ -75,18 +206,38 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
-    wayc::protocol::wl_compositor::WlCompositor
+    wayc::protocol::wl_compositor::WlCompositor,
+    wayc::protocol::wl_surface::WlSurface,
+    wayc::protocol::wl_shm::WlShm
 ]);
 
 struct ServerHandler;
 
+impl ways::Dispatch<ways::protocol::wl_compositor::WlCompositor, ()> for ServerHandler {
+    fn request(
+        &mut self,
+        _: &ways::Client,
+        _: &ways::protocol::wl_compositor::WlCompositor,
+        request: ways::protocol::wl_compositor::Request,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        init: &mut ways::DataInit<'_, Self>,
+    ) {
+        if let ways::protocol::wl_compositor::Request::CreateSurface { id } = request {
+            init.init(id, ());
+        }
+    }
+}
+
 server_ignore_impl!(ServerHandler => [
-    ways::protocol::wl_compositor::WlCompositor
+    ways::protocol::wl_surface::WlSurface,
+    ways::protocol::wl_shm::WlShm
 ]);
 server_ignore_global_impl!(ServerHandler => [
-    ways::protocol::wl_compositor::WlCompositor
+    ways::protocol::wl_compositor::WlCompositor,
+    ways::protocol::wl_shm::WlShm
 ]);
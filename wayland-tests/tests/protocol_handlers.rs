@@ -0,0 +1,407 @@
+#[macro_use]
+mod helpers;
+
+use helpers::{roundtrip, wayc, ways, TestServer};
+
+mod test_protocol {
+    pub mod server {
+        use crate::helpers::ways as wayland_server;
+
+        pub mod __interfaces {
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_server_code!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+        wayland_scanner::generate_server_handler!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+    }
+
+    pub mod client {
+        use crate::helpers::wayc as wayland_client;
+
+        pub mod __interfaces {
+            wayland_scanner::generate_interfaces!(
+                "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+            );
+        }
+        use self::__interfaces::*;
+
+        wayland_scanner::generate_client_code!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+        wayland_scanner::generate_client_handler!(
+            "../wayland-scanner/tests/scanner_assets/test-protocol.xml"
+        );
+    }
+}
+
+use test_protocol::{client as tc, server as ts};
+
+#[test]
+fn handlers_receive_messages() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ts::test_global::TestGlobal>(3, 10);
+    let mut server_ddata = ServerHandler { requests: Vec::new() };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata =
+        ClientHandler { globals: wayc::globals::GlobalList::new(), events: Vec::new() };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let global = client_ddata
+        .globals
+        .bind::<tc::test_global::TestGlobal, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            3..4,
+            (),
+        )
+        .unwrap();
+    let secondary = global.get_secondary(&client.event_queue.handle(), 1).unwrap();
+    global.link(&secondary, None, 42).unwrap();
+    global.set_mode(tc::test_global::Mode::Fast).unwrap();
+    secondary.destroy().unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(
+        server_ddata.requests,
+        [
+            "bind 10",
+            "get_secondary 11",
+            "link 42 11",
+            "set_mode 11",
+            "secondary.destroy 2",
+            "secondary.destroyed 2"
+        ]
+    );
+    assert_eq!(client_ddata.events, ["secondary.destroyed 1", "ack_secondary", "cycle_quad"]);
+}
+
+#[test]
+fn client_rejects_newer_enum_value() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ts::test_global::TestGlobal>(3, 10);
+    let mut server_ddata = ServerHandler { requests: Vec::new() };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata =
+        ClientHandler { globals: wayc::globals::GlobalList::new(), events: Vec::new() };
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let global = client_ddata
+        .globals
+        .bind::<tc::test_global::TestGlobal, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            2..3,
+            (),
+        )
+        .unwrap();
+    assert_eq!(wayc::Proxy::version(&global), 2);
+
+    // the turbo mode requires version 3 of the object
+    match global.set_mode(tc::test_global::Mode::Turbo) {
+        Err(wayc::RequestError::UnsupportedValue { request, arg, since, version, .. }) => {
+            assert_eq!((request, arg, since, version), ("set_mode", "mode", 3, 2));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert!(matches!(
+        global.set_mode_with_handle(&mut client.conn.handle(), tc::test_global::Mode::Turbo),
+        Err(wayc::RequestError::UnsupportedValue { .. })
+    ));
+    global.set_mode(tc::test_global::Mode::Fast).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // only the supported value reached the server
+    assert_eq!(server_ddata.requests, ["bind 10", "set_mode 11"]);
+}
+
+/*
+ * Server Handler
+ */
+
+struct ServerHandler {
+    requests: Vec<String>,
+}
+
+impl ts::handler::TestProtocolHandler for ServerHandler {
+    type WlCallbackData = ();
+    type TestGlobalData = u32;
+    type SecondaryData = u32;
+    type TertiaryData = ();
+    type QuadData = ();
+    type TestGlobalGlobalData = u32;
+
+    fn test_global_bind(
+        &mut self,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &ways::Client,
+        resource: ways::New<ts::test_global::TestGlobal>,
+        global_data: &u32,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        data_init.init(resource, global_data + 1);
+        self.requests.push(format!("bind {}", global_data));
+    }
+
+    fn test_global_many_args(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::test_global::TestGlobal,
+        _: u32,
+        _: i32,
+        _: f64,
+        _: Vec<u8>,
+        _: String,
+        _: std::os::unix::io::RawFd,
+        _: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push("many_args".into());
+    }
+
+    fn test_global_get_secondary(
+        &mut self,
+        _: &ways::Client,
+        resource: &ts::test_global::TestGlobal,
+        sec: ways::New<ts::secondary::Secondary>,
+        data: &u32,
+        dhandle: &mut ways::DisplayHandle<'_>,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        let sec = data_init.init(sec, 2);
+        resource.ack_secondary(dhandle, &sec);
+        self.requests.push(format!("get_secondary {}", data));
+    }
+
+    fn test_global_get_tertiary(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::test_global::TestGlobal,
+        ter: ways::New<ts::tertiary::Tertiary>,
+        _: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        data_init.init(ter, ());
+        self.requests.push("get_tertiary".into());
+    }
+
+    fn test_global_link(
+        &mut self,
+        client: &ways::Client,
+        resource: &ts::test_global::TestGlobal,
+        _: ts::secondary::Secondary,
+        ter: Option<ts::tertiary::Tertiary>,
+        time: u32,
+        data: &u32,
+        dhandle: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        assert!(ter.is_none());
+        let quad = client.create_resource::<ts::quad::Quad, (), Self>(dhandle, 3, ()).unwrap();
+        resource.cycle_quad(dhandle, &quad, None);
+        self.requests.push(format!("link {} {}", time, data));
+    }
+
+    fn test_global_destroy(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::test_global::TestGlobal,
+        _: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push("destroy".into());
+    }
+
+    fn test_global_reset(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::test_global::TestGlobal,
+        _: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push("reset".into());
+    }
+
+    fn test_global_set_mode(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::test_global::TestGlobal,
+        mode: ways::WEnum<ts::test_global::Mode>,
+        data: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        assert_eq!(mode, ways::WEnum::Value(ts::test_global::Mode::Fast));
+        self.requests.push(format!("set_mode {}", data));
+    }
+
+    fn secondary_destroy(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::secondary::Secondary,
+        data: &u32,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push(format!("secondary.destroy {}", data));
+    }
+
+    fn tertiary_destroy(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::tertiary::Tertiary,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push("tertiary.destroy".into());
+    }
+
+    fn quad_destroy(
+        &mut self,
+        _: &ways::Client,
+        _: &ts::quad::Quad,
+        _: &(),
+        _: &mut ways::DisplayHandle<'_>,
+        _: &mut ways::DataInit<'_, Self>,
+    ) {
+        self.requests.push("quad.destroy".into());
+    }
+
+    fn secondary_destroyed(
+        &mut self,
+        _: ways::backend::ClientId,
+        _: ways::backend::ObjectId,
+        data: &u32,
+    ) {
+        self.requests.push(format!("secondary.destroyed {}", data));
+    }
+}
+
+ts::handler::delegate_test_protocol!(ServerHandler => ts);
+
+/*
+ * Client Handler
+ */
+
+struct ClientHandler {
+    globals: wayc::globals::GlobalList,
+    events: Vec<String>,
+}
+
+impl AsMut<wayc::globals::GlobalList> for ClientHandler {
+    fn as_mut(&mut self) -> &mut wayc::globals::GlobalList {
+        &mut self.globals
+    }
+}
+
+wayc::delegate_dispatch!(ClientHandler:
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
+);
+
+impl tc::test_global::ChildrenData<ClientHandler> for () {
+    type CycleQuadData = ();
+
+    fn cycle_quad(&self) {}
+}
+
+impl tc::handler::TestProtocolHandler for ClientHandler {
+    type WlCallbackData = ();
+    type TestGlobalData = ();
+    type SecondaryData = u32;
+    type TertiaryData = ();
+    type QuadData = ();
+
+    fn wl_callback_done(
+        &mut self,
+        _: &tc::wl_callback::WlCallback,
+        _: u32,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.events.push("done".into());
+    }
+
+    fn test_global_many_args_evt(
+        &mut self,
+        _: &tc::test_global::TestGlobal,
+        _: u32,
+        _: i32,
+        _: f64,
+        _: Vec<u8>,
+        _: String,
+        _: std::os::unix::io::RawFd,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.events.push("many_args_evt".into());
+    }
+
+    fn test_global_ack_secondary(
+        &mut self,
+        _: &tc::test_global::TestGlobal,
+        _: tc::secondary::Secondary,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.events.push("ack_secondary".into());
+    }
+
+    fn test_global_cycle_quad(
+        &mut self,
+        _: &tc::test_global::TestGlobal,
+        _: tc::quad::Quad,
+        old_quad: Option<tc::quad::Quad>,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        assert!(old_quad.is_none());
+        self.events.push("cycle_quad".into());
+    }
+
+    fn test_global_legacy_evt(
+        &mut self,
+        _: &tc::test_global::TestGlobal,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.events.push("legacy_evt".into());
+    }
+
+    fn secondary_destroyed(
+        &mut self,
+        _: wayc::backend::ObjectId,
+        data: &u32,
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        self.events.push(format!("secondary.destroyed {}", data));
+    }
+}
+
+tc::handler::delegate_test_protocol!(ClientHandler => tc);
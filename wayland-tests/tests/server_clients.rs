@@ -3,7 +3,13 @@ mod helpers;
 
 use helpers::{roundtrip, wayc, ways, TestServer};
 
-use std::sync::{Arc, Mutex};
+use nix::poll::{poll, PollFd, PollFlags};
+
+use std::{
+    ffi::CString,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 #[test]
 fn client_user_data() {
@@ -18,18 +24,14 @@ fn client_user_data() {
     })));
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     // Instantiate the globals
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -47,8 +49,7 @@ fn client_user_data() {
 
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_compositor::WlCompositor, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             &client.event_queue.handle(),
             &registry,
             1..2,
@@ -76,6 +77,238 @@ fn client_credentials() {
     assert_credentials(credentials.unwrap());
 }
 
+#[test]
+fn client_kill_is_immediate() {
+    let mut server = TestServer::new();
+    let mut server_ddata = ServerHandler {};
+
+    let tracker = Arc::new(DisconnectTracker::default());
+    let (s_client, mut client) = server.add_client_with_data(tracker.clone());
+    let mut client_ddata = ClientHandler::new();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    s_client.kill(
+        &mut server.display.handle(),
+        ways::backend::protocol::ProtocolError {
+            code: 0,
+            object_id: 1,
+            object_interface: "wl_display".into(),
+            message: "I don't like you!".into(),
+            message_name: None,
+        },
+    );
+
+    // the client is disconnected without any flush
+    assert!(matches!(
+        *tracker.reason.lock().unwrap(),
+        Some(ways::backend::DisconnectReason::ProtocolError(ref err)) if err.message == "I don't like you!"
+    ));
+
+    // the socket is closed once the killed client is cleaned up
+    server.display.dispatch_clients(&mut server_ddata).unwrap();
+    assert!(client.conn.prepare_read().unwrap().read().is_err());
+}
+
+#[test]
+fn client_disconnect_sends_error() {
+    let mut server = TestServer::new();
+    let mut server_ddata = ServerHandler {};
+
+    let tracker = Arc::new(DisconnectTracker::default());
+    let (s_client, mut client) = server.add_client_with_data(tracker.clone());
+    let mut client_ddata = ClientHandler::new();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    s_client.disconnect(
+        &mut server.display.handle(),
+        ways::backend::DisconnectReason::ProtocolError(ways::backend::protocol::ProtocolError {
+            code: 0,
+            object_id: 1,
+            object_interface: "wl_display".into(),
+            message: "I don't like you!".into(),
+            message_name: None,
+        }),
+    );
+    server.display.flush_clients().unwrap();
+
+    // the server may already have closed the socket, only read what is left in it
+    assert!(client.conn.prepare_read().unwrap().read().is_err());
+
+    let error = client.conn.protocol_error().unwrap();
+    assert_eq!(error.code, 0);
+    assert_eq!(error.object_id, 1);
+    // native lib can't give us the message
+    #[cfg(not(feature = "client_system"))]
+    {
+        assert_eq!(error.message, "I don't like you!");
+    }
+
+    assert!(matches!(
+        *tracker.reason.lock().unwrap(),
+        Some(ways::backend::DisconnectReason::ProtocolError(ref err)) if err.message == "I don't like you!"
+    ));
+}
+
+#[test]
+fn client_disconnect_sends_pending_events() {
+    let mut server = TestServer::new();
+    for _ in 0..200 {
+        server.display.create_global::<ways::protocol::wl_output::WlOutput>(1, ());
+    }
+    let mut server_ddata = ServerHandler {};
+
+    let tracker = Arc::new(DisconnectTracker::default());
+    let (s_client, mut client) = server.add_client_with_data(tracker.clone());
+    let mut client_ddata = ClientHandler::new();
+
+    // the globals are more than what fits in the outgoing buffer of the server, some are still pending
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+    client.conn.flush().unwrap();
+    server.display.dispatch_clients(&mut server_ddata).unwrap();
+
+    s_client.disconnect(&mut server.display.handle(), ways::backend::DisconnectReason::ServerKill);
+    server.display.flush_clients().unwrap();
+    // the socket is closed once the disconnected client is cleaned up
+    server.display.dispatch_clients(&mut server_ddata).unwrap();
+
+    // the events read along with the end of the connection are still dispatched
+    while client.conn.prepare_read().unwrap().read().is_ok() {}
+    client.event_queue.dispatch_pending(&mut client_ddata).unwrap();
+    assert_eq!(client_ddata.globals.list().len(), 200);
+    assert!(client.conn.protocol_error().is_none());
+
+    assert!(matches!(
+        *tracker.reason.lock().unwrap(),
+        Some(ways::backend::DisconnectReason::ServerKill)
+    ));
+}
+
+#[test]
+fn client_disconnect_timeout() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(1, ());
+    let mut server_ddata = ServerHandler {};
+
+    let tracker = Arc::new(DisconnectTracker::default());
+    let (s_client, mut client) = server.add_client_with_data(tracker.clone());
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+    let output = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            1..2,
+            (),
+        )
+        .unwrap();
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let s_output = s_client
+        .object_from_protocol_id::<ways::protocol::wl_output::WlOutput>(
+            &mut server.display.handle(),
+            wayc::Proxy::id(&output).protocol_id(),
+        )
+        .unwrap();
+
+    // fill the socket of the client, which never reads it
+    loop {
+        for _ in 0..100 {
+            s_output.mode(
+                &mut server.display.handle(),
+                ways::protocol::wl_output::Mode::Current,
+                1920,
+                1080,
+                60_000,
+            );
+        }
+        if output_pending(&server, &s_client) {
+            break;
+        }
+    }
+
+    s_client.disconnect(&mut server.display.handle(), ways::backend::DisconnectReason::ServerKill);
+    server.display.flush_clients().unwrap();
+    assert!(tracker.reason.lock().unwrap().is_none());
+
+    // the client is disconnected anyway once it took too long to read its events
+    std::thread::sleep(Duration::from_millis(1100));
+    server.display.flush_clients().unwrap();
+    assert!(matches!(
+        *tracker.reason.lock().unwrap(),
+        Some(ways::backend::DisconnectReason::ServerKill)
+    ));
+}
+
+#[test]
+fn display_shutdown() {
+    let mut server = TestServer::new();
+    let mut server_ddata = ServerHandler {};
+
+    let tracker_1 = Arc::new(DisconnectTracker::default());
+    let (_, client_1) = server.add_client_with_data::<()>(tracker_1.clone());
+    let tracker_2 = Arc::new(DisconnectTracker::default());
+    let (_, client_2) = server.add_client_with_data::<()>(tracker_2.clone());
+
+    server
+        .display
+        .shutdown(
+            &mut server_ddata,
+            CString::new("Server shutting down").unwrap(),
+            Duration::from_secs(1),
+        )
+        .unwrap();
+
+    for (client, tracker) in [(&client_1, &tracker_1), (&client_2, &tracker_2)] {
+        // the server closed the sockets, only read what is left in them
+        assert!(client.conn.prepare_read().unwrap().read().is_err());
+
+        let error = client.conn.protocol_error().unwrap();
+        assert_eq!(error.code, 3);
+        // native lib can't give us the message
+        #[cfg(not(feature = "client_system"))]
+        {
+            assert_eq!(error.message, "Server shutting down");
+        }
+
+        assert!(matches!(
+            *tracker.reason.lock().unwrap(),
+            Some(ways::backend::DisconnectReason::ServerKill)
+        ));
+    }
+}
+
+#[test]
+fn dispatch_single_client() {
+    let mut server = TestServer { display: ways::Display::new_without_poll().unwrap() };
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(1, ());
+    let mut server_ddata = ServerHandler {};
+
+    let (s_client, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let fd = s_client.get_fd(&mut server.display.handle()).unwrap();
+
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+    client.conn.flush().unwrap();
+
+    // the socket of the client is monitored directly
+    let mut fds = [PollFd::new(fd, PollFlags::POLLIN)];
+    assert_eq!(poll(&mut fds, 1000).unwrap(), 1);
+    server.display.dispatch_client(&mut server_ddata, &s_client).unwrap();
+    server.display.flush_client(&s_client).unwrap();
+
+    client.event_queue.blocking_dispatch(&mut client_ddata).unwrap();
+    assert_eq!(client_ddata.globals.list().len(), 1);
+
+    // dispatching all clients still works
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+}
+
 #[cfg(any(not(feature = "server_system"), not(target_os = "freebsd")))]
 fn assert_credentials(credentials: ways::backend::Credentials) {
     assert!(credentials.pid != 0);
@@ -94,6 +327,22 @@ fn assert_credentials(_credentials: ways::backend::Credentials) {
     // see: https://bugs.freebsd.org/bugzilla/show_bug.cgi?id=246189
 }
 
+/// Flushes the client, and returns whether some of its events could not be sent
+#[cfg(not(feature = "server_system"))]
+fn output_pending<D>(server: &TestServer<D>, client: &ways::Client) -> bool {
+    server.display.flush_client(client).is_err()
+}
+
+/// Flushes the client, and returns whether some of its events could not be sent
+#[cfg(feature = "server_system")]
+fn output_pending<D>(server: &TestServer<D>, client: &ways::Client) -> bool {
+    // libwayland does not report whether the flush was complete, use the same heuristic as the backend
+    server.display.flush_client(client).unwrap();
+    let fd = client.get_fd(&mut server.display.handle()).unwrap();
+    let mut fds = [PollFd::new(fd, PollFlags::POLLOUT)];
+    poll(&mut fds, 0).unwrap() == 0
+}
+
 struct ClientHandler {
     globals: wayc::globals::GlobalList,
 }
@@ -111,7 +360,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
@@ -136,6 +385,22 @@ impl ways::backend::ClientData<ServerHandler> for Mutex<MyClientData> {
     }
 }
 
+#[derive(Default)]
+struct DisconnectTracker {
+    reason: Mutex<Option<ways::backend::DisconnectReason>>,
+}
+
+impl ways::backend::ClientData<ServerHandler> for DisconnectTracker {
+    fn initialized(&self, _: wayland_backend::server::ClientId) {}
+    fn disconnected(
+        &self,
+        _: wayland_backend::server::ClientId,
+        reason: wayland_backend::server::DisconnectReason,
+    ) {
+        *self.reason.lock().unwrap() = Some(reason);
+    }
+}
+
 server_ignore_impl!(ServerHandler => [
     ways::protocol::wl_output::WlOutput,
     ways::protocol::wl_compositor::WlCompositor
@@ -152,7 +417,9 @@ impl ways::GlobalDispatch<ways::protocol::wl_output::WlOutput> for ServerHandler
         data_init: &mut ways::DataInit<'_, Self>,
     ) {
         data_init.init(resource, ());
-        client.get_data::<Mutex<MyClientData>>().unwrap().lock().unwrap().has_output = true;
+        if let Some(data) = client.get_data::<Mutex<MyClientData>>() {
+            data.lock().unwrap().has_output = true;
+        }
     }
 }
 
@@ -27,44 +27,27 @@ fn data_offer() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<ClientSeat, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<ClientSeat, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
     let ddmgr = client_ddata
         .globals
-        .bind::<ClientDDMgr, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            3..4,
-            (),
-        )
+        .bind::<ClientDDMgr, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
         .unwrap();
 
-    ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let server_dd = server_ddata.data_device.take().unwrap();
     let s_client = server.display.handle().get_client(server_dd.id()).unwrap();
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -90,37 +73,20 @@ fn server_id_reuse() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<ClientSeat, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<ClientSeat, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
     let ddmgr = client_ddata
         .globals
-        .bind::<ClientDDMgr, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            3..4,
-            (),
-        )
+        .bind::<ClientDDMgr, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
         .unwrap();
 
-    ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -128,7 +94,7 @@ fn server_id_reuse() {
     let s_client = server.display.handle().get_client(server_dd.id()).unwrap();
     // Send a first data offer, ID should be 0xFF000000
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -143,7 +109,7 @@ fn server_id_reuse() {
 
     // Send a second data offer, ID should be 0xFF000001
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -156,13 +122,13 @@ fn server_id_reuse() {
 
     // now the client destroys the offer
 
-    client_offer.destroy(&mut client.conn.handle());
+    client_offer.destroy().unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     // Send a third data offer, server shoudl reuse id 0xFF000000
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -188,37 +154,20 @@ fn server_created_race() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<ClientSeat, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<ClientSeat, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
     let ddmgr = client_ddata
         .globals
-        .bind::<ClientDDMgr, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            3..4,
-            (),
-        )
+        .bind::<ClientDDMgr, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
         .unwrap();
 
-    ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -226,7 +175,7 @@ fn server_created_race() {
     let s_client = server.display.handle().get_client(server_dd.id()).unwrap();
     // Send a first data offer, ID should be 0xFF000000
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -248,7 +197,7 @@ fn server_created_race() {
 
     offer.offer(&mut server.display.handle(), "utf8".into());
     let client_do = client_ddata.data_offer.take().unwrap();
-    client_do.destroy(&mut client.conn.handle());
+    client_do.destroy().unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -268,55 +217,36 @@ fn creation_destruction_race() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<ClientSeat, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<ClientSeat, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
     let ddmgr = client_ddata
         .globals
-        .bind::<ClientDDMgr, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            3..4,
-            (),
-        )
+        .bind::<ClientDDMgr, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
         .unwrap();
 
     // client creates two data devices
 
-    let client_dd1 = ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    let client_dd1 = ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
     let s_dd1 = server_ddata.data_device.take().unwrap();
 
-    ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
     let s_dd2 = server_ddata.data_device.take().unwrap();
 
     // server sends a newid event to dd1 while dd1 gets destroyed
-    client_dd1.release(&mut client.conn.handle());
+    client_dd1.release().unwrap();
 
     let s_client = server.display.handle().get_client(s_dd1.id()).unwrap();
     // Send a first NewID
     let offer1 = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             s_dd1.version(),
             (),
@@ -333,7 +263,7 @@ fn creation_destruction_race() {
 
     // server sends an other unrelated newid event
     let offer2 = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             s_dd1.version(),
             (),
@@ -358,37 +288,20 @@ fn creation_destruction_queue_dispatch_race() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     let seat = client_ddata
         .globals
-        .bind::<ClientSeat, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            1..2,
-            (),
-        )
+        .bind::<ClientSeat, _, _>(&client.event_queue.handle(), &registry, 1..2, ())
         .unwrap();
     let ddmgr = client_ddata
         .globals
-        .bind::<ClientDDMgr, _>(
-            &mut client.conn.handle(),
-            &client.event_queue.handle(),
-            &registry,
-            3..4,
-            (),
-        )
+        .bind::<ClientDDMgr, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
         .unwrap();
 
-    let client_dd = ddmgr
-        .get_data_device(&mut client.conn.handle(), &seat, &client.event_queue.handle(), ())
-        .unwrap();
+    let client_dd = ddmgr.get_data_device(&seat, &client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -402,7 +315,7 @@ fn creation_destruction_queue_dispatch_race() {
 
     let s_client = server.display.handle().get_client(server_dd.id()).unwrap();
     let offer = s_client
-        .create_resource::<ServerDO, ServerHandler>(
+        .create_resource::<ServerDO, _, ServerHandler>(
             &mut server.display.handle(),
             server_dd.version(),
             (),
@@ -415,7 +328,7 @@ fn creation_destruction_queue_dispatch_race() {
 
     client.conn.prepare_read().unwrap().read().unwrap();
 
-    client_dd.release(&mut client.conn.handle());
+    client_dd.release().unwrap();
 
     client.event_queue.dispatch_pending(&mut client_ddata).unwrap();
 
@@ -448,20 +361,19 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 client_ignore_impl!(ClientHandler => [
     ClientSeat,
     ClientDDMgr
 ]);
 
-impl wayc::Dispatch<wayc::protocol::wl_data_device::WlDataDevice> for ClientHandler {
-    type UserData = ();
+impl wayc::Dispatch<wayc::protocol::wl_data_device::WlDataDevice, ()> for ClientHandler {
     fn event(
         &mut self,
         data_device: &wayc::protocol::wl_data_device::WlDataDevice,
         event: wayc::protocol::wl_data_device::Event,
-        _: &Self::UserData,
+        _: &(),
         handle: &mut wayc::ConnectionHandle,
         _: &wayc::QueueHandle<Self>,
     ) {
@@ -475,20 +387,20 @@ impl wayc::Dispatch<wayc::protocol::wl_data_device::WlDataDevice> for ClientHand
             _ => unimplemented!(),
         }
     }
-
-    wayc::event_created_child!(ClientHandler, wayc::protocol::wl_data_device::WlDataDevice, [
-        0 => (ClientDO, ())
-    ]);
 }
 
-impl wayc::Dispatch<ClientDO> for ClientHandler {
-    type UserData = ();
+impl wayc::protocol::wl_data_device::ChildrenData<ClientHandler> for () {
+    type DataOfferData = ();
+
+    fn data_offer(&self) {}
+}
 
+impl wayc::Dispatch<ClientDO, ()> for ClientHandler {
     fn event(
         &mut self,
         _: &ClientDO,
         event: wayc::protocol::wl_data_offer::Event,
-        _: &Self::UserData,
+        _: &(),
         _: &mut wayc::ConnectionHandle,
         _: &wayc::QueueHandle<Self>,
     ) {
@@ -516,14 +428,13 @@ server_ignore_global_impl!(ServerHandler => [
     ServerDDMgr
 ]);
 
-impl ways::Dispatch<ServerDDMgr> for ServerHandler {
-    type UserData = ();
+impl ways::Dispatch<ServerDDMgr, ()> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
         _: &ServerDDMgr,
         request: SDDMReq,
-        _: &Self::UserData,
+        _: &(),
         _: &mut ways::DisplayHandle<'_>,
         data_init: &mut ways::DataInit<'_, Self>,
     ) {
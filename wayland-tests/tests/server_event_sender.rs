@@ -0,0 +1,118 @@
+#[macro_use]
+mod helpers;
+
+use helpers::{roundtrip, wayc, ways, TestServer};
+
+use ways::protocol::wl_output;
+
+use wayc::protocol::wl_output::WlOutput as ClientOutput;
+
+use nix::poll::{poll, PollFd, PollFlags};
+
+#[test]
+fn send_event_from_thread() {
+    let mut server = TestServer::new();
+    server.display.create_global::<wl_output::WlOutput>(3, ());
+    let mut server_ddata = ServerHandler { outputs: Vec::new() };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    client_ddata
+        .globals
+        .bind::<ClientOutput, _, _>(&client.event_queue.handle(), &registry, 3..4, ())
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let output = server_ddata.outputs[0].clone();
+    let sender = server.display.event_sender();
+
+    // events sent from the main thread and from another thread keep their order
+    output.scale(&mut server.display.handle(), 1);
+    std::thread::spawn(move || {
+        sender.send_event(&output, wl_output::Event::Scale { factor: 2 }).unwrap();
+    })
+    .join()
+    .unwrap();
+
+    // the event loop is notified that a flush is needed
+    let mut fds = [PollFd::new(server.display.flush_fd(), PollFlags::POLLIN)];
+    assert_eq!(poll(&mut fds, 1000).unwrap(), 1);
+    server.display.flush_clients().unwrap();
+    assert_eq!(poll(&mut fds, 0).unwrap(), 0);
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(client_ddata.scales, vec![1, 2]);
+}
+
+#[test]
+fn event_sender_dead_display() {
+    let server = TestServer::<ServerHandler>::new();
+    let sender = server.display.event_sender();
+    drop(server);
+
+    assert!(sender.with_handle(|_| ()).is_err());
+}
+
+struct ClientHandler {
+    globals: wayc::globals::GlobalList,
+    scales: Vec<i32>,
+}
+
+impl ClientHandler {
+    fn new() -> ClientHandler {
+        ClientHandler { globals: Default::default(), scales: Vec::new() }
+    }
+}
+
+impl AsMut<wayc::globals::GlobalList> for ClientHandler {
+    fn as_mut(&mut self) -> &mut wayc::globals::GlobalList {
+        &mut self.globals
+    }
+}
+
+wayc::delegate_dispatch!(ClientHandler:
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
+);
+
+impl wayc::Dispatch<ClientOutput, ()> for ClientHandler {
+    fn event(
+        &mut self,
+        _: &ClientOutput,
+        event: wayc::protocol::wl_output::Event,
+        _: &(),
+        _: &mut wayc::ConnectionHandle,
+        _: &wayc::QueueHandle<Self>,
+    ) {
+        if let wayc::protocol::wl_output::Event::Scale { factor } = event {
+            self.scales.push(factor);
+        }
+    }
+}
+
+struct ServerHandler {
+    outputs: Vec<wl_output::WlOutput>,
+}
+
+impl ways::GlobalDispatch<wl_output::WlOutput> for ServerHandler {
+    type GlobalData = ();
+    fn bind(
+        &mut self,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &ways::Client,
+        output: ways::New<wl_output::WlOutput>,
+        _: &(),
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        let output = data_init.init(output, ());
+        self.outputs.push(output);
+    }
+}
+
+server_ignore_impl!(ServerHandler => [wl_output::WlOutput]);
@@ -9,7 +9,10 @@ use helpers::{roundtrip, wayc, ways, TestServer};
 
 use ways::protocol::{wl_compositor, wl_output, wl_shm};
 
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 #[test]
 fn global_filter() {
@@ -25,10 +28,7 @@ fn global_filter() {
     let (_, mut client) = server.add_client_with_data(Arc::new(MyClientData { privileged: false }));
     let mut client_ddata = ClientHandler::new();
 
-    client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -38,10 +38,7 @@ fn global_filter() {
         server.add_client_with_data(Arc::new(MyClientData { privileged: true }));
     let mut priv_client_ddata = ClientHandler::new();
 
-    priv_client
-        .display
-        .get_registry(&mut priv_client.conn.handle(), &priv_client.event_queue.handle(), ())
-        .unwrap();
+    priv_client.display.get_registry(&priv_client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut priv_client, &mut server, &mut priv_client_ddata, &mut server_ddata).unwrap();
 
@@ -79,13 +76,10 @@ fn global_filter_try_force() {
 
     // privileged client can bind it
 
-    let priv_registry = priv_client
-        .display
-        .get_registry(&mut priv_client.conn.handle(), &priv_client.event_queue.handle(), ())
-        .unwrap();
+    let priv_registry =
+        priv_client.display.get_registry(&priv_client.event_queue.handle(), ()).unwrap();
     priv_registry
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut priv_client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             1,
             1,
             &priv_client.event_queue.handle(),
@@ -95,15 +89,61 @@ fn global_filter_try_force() {
     roundtrip(&mut priv_client, &mut server, &mut priv_client_ddata, &mut server_ddata).unwrap();
 
     // unprivileged client cannot
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
     registry
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(1, 1, &client.event_queue.handle(), ())
+        .unwrap();
+
+    assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).is_err());
+}
+
+// the system backend always advertises the version of the global
+#[cfg(not(feature = "server_system"))]
+#[test]
+fn global_version_per_client() {
+    let mut server = TestServer::new();
+    // unprivileged clients only see the version 1 of the compositor
+    server.display.create_global::<ways::protocol::wl_compositor::WlCompositor>(3, ());
+    let mut server_ddata = VersionServerHandler;
+
+    let (_, mut client) = server.add_client_with_data(Arc::new(MyClientData { privileged: false }));
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(client_ddata.globals.list().len(), 1);
+    assert_eq!(client_ddata.globals.list()[0].version, 1);
+
+    let (_, mut priv_client) =
+        server.add_client_with_data(Arc::new(MyClientData { privileged: true }));
+    let mut priv_client_ddata = ClientHandler::new();
+
+    let priv_registry =
+        priv_client.display.get_registry(&priv_client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut priv_client, &mut server, &mut priv_client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(priv_client_ddata.globals.list().len(), 1);
+    assert_eq!(priv_client_ddata.globals.list()[0].version, 3);
+
+    // privileged client can bind version 3
+    priv_registry
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             1,
+            3,
+            &priv_client.event_queue.handle(),
+            (),
+        )
+        .unwrap();
+    roundtrip(&mut priv_client, &mut server, &mut priv_client_ddata, &mut server_ddata).unwrap();
+
+    // unprivileged client cannot bind above the advertised version
+    registry
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
             1,
+            3,
             &client.event_queue.handle(),
             (),
         )
@@ -112,6 +152,63 @@ fn global_filter_try_force() {
     assert!(roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).is_err());
 }
 
+#[cfg(not(feature = "server_system"))]
+#[test]
+fn global_remove_after_view_revoked() {
+    let mut server = TestServer::new();
+    let output = server.display.create_global::<ways::protocol::wl_output::WlOutput>(1, ());
+    let mut server_ddata = RevocableServerHandler;
+
+    let client_data = Arc::new(RevocableClientData { visible: AtomicBool::new(true) });
+    let (_, mut client) = server.add_client_with_data(client_data.clone());
+    let mut client_ddata = ClientHandler::new();
+
+    client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(client_ddata.globals.list().len(), 1);
+
+    // the client was told about the global, so it is told about its removal even though
+    // the global would no longer be advertised to it
+    client_data.visible.store(false, Ordering::SeqCst);
+    server.display.remove_global(output);
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert_eq!(client_ddata.globals.list().len(), 0);
+}
+
+#[cfg(feature = "server_system")]
+#[test]
+fn global_version_per_client() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_compositor::WlCompositor>(3, ());
+    let mut server_ddata = VersionServerHandler;
+
+    let (_, mut client) = server.add_client_with_data(Arc::new(MyClientData { privileged: false }));
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    // the version of the global is advertised to all clients, and can be bound
+    assert_eq!(client_ddata.globals.list().len(), 1);
+    assert_eq!(client_ddata.globals.list()[0].version, 3);
+
+    registry
+        .bind::<wayc::protocol::wl_compositor::WlCompositor, _, _>(
+            1,
+            3,
+            &client.event_queue.handle(),
+            (),
+        )
+        .unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+}
+
 struct ClientHandler {
     globals: wayc::globals::GlobalList,
 }
@@ -129,7 +226,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [
@@ -194,11 +291,73 @@ impl ways::GlobalDispatch<wl_output::WlOutput> for ServerHandler {
     }
 }
 
+struct VersionServerHandler;
+
+impl ways::GlobalDispatch<wl_compositor::WlCompositor> for VersionServerHandler {
+    type GlobalData = ();
+    fn bind(
+        &mut self,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &ways::Client,
+        resource: ways::New<wl_compositor::WlCompositor>,
+        _: &Self::GlobalData,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        data_init.init(resource, ());
+    }
+
+    fn advertised_version(client: ways::Client, _: &Self::GlobalData, version: u32) -> Option<u32> {
+        if client.get_data::<MyClientData>().unwrap().privileged {
+            Some(version)
+        } else {
+            Some(1)
+        }
+    }
+}
+
+server_ignore_impl!(VersionServerHandler => [ways::protocol::wl_compositor::WlCompositor]);
+
+struct RevocableServerHandler;
+
+impl ways::GlobalDispatch<wl_output::WlOutput> for RevocableServerHandler {
+    type GlobalData = ();
+    fn bind(
+        &mut self,
+        _: &mut ways::DisplayHandle<'_>,
+        _: &ways::Client,
+        resource: ways::New<wl_output::WlOutput>,
+        _: &Self::GlobalData,
+        data_init: &mut ways::DataInit<'_, Self>,
+    ) {
+        data_init.init(resource, ());
+    }
+
+    fn can_view(client: ways::Client, _: &Self::GlobalData) -> bool {
+        client.get_data::<RevocableClientData>().unwrap().visible.load(Ordering::SeqCst)
+    }
+}
+
+server_ignore_impl!(RevocableServerHandler => [ways::protocol::wl_output::WlOutput]);
+
+struct RevocableClientData {
+    visible: AtomicBool,
+}
+
+impl<D> ways::backend::ClientData<D> for RevocableClientData {
+    fn initialized(&self, _: wayland_backend::server::ClientId) {}
+    fn disconnected(
+        &self,
+        _: wayland_backend::server::ClientId,
+        _: wayland_backend::server::DisconnectReason,
+    ) {
+    }
+}
+
 struct MyClientData {
     privileged: bool,
 }
 
-impl ways::backend::ClientData<ServerHandler> for MyClientData {
+impl<D> ways::backend::ClientData<D> for MyClientData {
     fn initialized(&self, _: wayland_backend::server::ClientId) {}
     fn disconnected(
         &self,
@@ -19,18 +19,14 @@ fn resource_equals() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     // create two outputs
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -39,8 +35,7 @@ fn resource_equals() {
         .unwrap();
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -68,18 +63,14 @@ fn resource_user_data() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     // create two outputs
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -88,8 +79,7 @@ fn resource_user_data() {
         .unwrap();
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -114,18 +104,14 @@ fn dead_resources() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
-        .unwrap();
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
     // create two outputs
     let client_output_1 = client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -134,8 +120,7 @@ fn dead_resources() {
         .unwrap();
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -150,7 +135,7 @@ fn dead_resources() {
 
     let cloned = server_ddata.outputs[0].clone();
 
-    client_output_1.release(&mut client.conn.handle());
+    client_output_1.release().unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
@@ -160,7 +145,7 @@ fn dead_resources() {
 }
 
 #[test]
-fn get_resource() {
+fn weak_resources() {
     let mut server = TestServer::new();
     server.display.create_global::<ways::protocol::wl_output::WlOutput>(3, ());
     let mut server_ddata = ServerHandler { outputs: Vec::new() };
@@ -168,18 +153,63 @@ fn get_resource() {
     let (_, mut client) = server.add_client();
     let mut client_ddata = ClientHandler::new();
 
-    let registry = client
-        .display
-        .get_registry(&mut client.conn.handle(), &client.event_queue.handle(), ())
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    let client_output = client_ddata
+        .globals
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
+            &client.event_queue.handle(),
+            &registry,
+            3..4,
+            (),
+        )
         .unwrap();
 
     roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
 
+    let output = server_ddata.outputs[0].clone();
+    let weak = output.downgrade();
+    assert!(weak == output);
+    assert!(output.is_alive(&mut server.display.handle()));
+    assert!(weak.is_alive(&mut server.display.handle()));
+    assert!(weak.upgrade(&mut server.display.handle()).unwrap() == output);
+
+    // resources and weak handles can be used as keys
+    let mut set = std::collections::HashSet::new();
+    set.insert(output.clone());
+    assert!(set.contains(&server_ddata.outputs[0]));
+    let mut weak_set = std::collections::HashSet::new();
+    weak_set.insert(weak.clone());
+    assert!(weak_set.contains(&output.downgrade()));
+
+    client_output.release().unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
+    assert!(!output.is_alive(&mut server.display.handle()));
+    assert!(!weak.is_alive(&mut server.display.handle()));
+    assert!(weak.upgrade(&mut server.display.handle()).is_err());
+}
+
+#[test]
+fn get_resource() {
+    let mut server = TestServer::new();
+    server.display.create_global::<ways::protocol::wl_output::WlOutput>(3, ());
+    let mut server_ddata = ServerHandler { outputs: Vec::new() };
+
+    let (_, mut client) = server.add_client();
+    let mut client_ddata = ClientHandler::new();
+
+    let registry = client.display.get_registry(&client.event_queue.handle(), ()).unwrap();
+
+    roundtrip(&mut client, &mut server, &mut client_ddata, &mut server_ddata).unwrap();
+
     // create an outputs
     client_ddata
         .globals
-        .bind::<wayc::protocol::wl_output::WlOutput, _>(
-            &mut client.conn.handle(),
+        .bind::<wayc::protocol::wl_output::WlOutput, _, _>(
             &client.event_queue.handle(),
             &registry,
             3..4,
@@ -223,7 +253,7 @@ impl AsMut<wayc::globals::GlobalList> for ClientHandler {
 }
 
 wayc::delegate_dispatch!(ClientHandler:
-    [wayc::protocol::wl_registry::WlRegistry] => wayc::globals::GlobalList
+    [wayc::protocol::wl_registry::WlRegistry: ()] => wayc::globals::GlobalList
 );
 
 client_ignore_impl!(ClientHandler => [ClientOutput]);
@@ -249,15 +279,13 @@ impl ways::GlobalDispatch<wl_output::WlOutput> for ServerHandler {
 
 struct UData(usize);
 
-impl ways::Dispatch<wl_output::WlOutput> for ServerHandler {
-    type UserData = UData;
-
+impl ways::Dispatch<wl_output::WlOutput, UData> for ServerHandler {
     fn request(
         &mut self,
         _: &ways::Client,
         _: &wl_output::WlOutput,
         _: wl_output::Request,
-        _: &Self::UserData,
+        _: &UData,
         _: &mut ways::DisplayHandle<'_>,
         _: &mut ways::DataInit<'_, Self>,
     ) {